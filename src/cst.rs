@@ -0,0 +1,752 @@
+use std::clone::Clone;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+use crate::error;
+use crate::options::ParserOptions;
+use crate::parser::code::CodeOperator;
+use crate::parser::front_matter::get_front_matter;
+use crate::parser::inline::{InlineNode, find_closing, get_inline_spans};
+use crate::token::{self, TextualToken};
+use crate::token::code::CodeToken;
+use crate::token::tag::TagOperator;
+
+/// Cst Kind
+///
+/// # Description
+/// Syntax of a line of the concrete syntax tree
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value", rename_all = "snake_case"))]
+pub enum CstKind {
+    FrontMatter,
+    Blank,
+    Text,
+    Tag(TagOperator),
+    Code(CodeOperator),
+    // Line folded into the block started on a previous line i.e footnote, html block, display math
+    Continuation
+}
+
+/// Cst Inline Kind
+///
+/// # Description
+/// Syntax of an inline node of the concrete syntax tree
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CstInlineKind {
+    Text,
+    Strong,
+    Emphasis,
+    Strike,
+    Code,
+    Link,
+    Image,
+    FootnoteRef,
+    Html,
+    InlineMath,
+    Emoji,
+    Mention,
+    IssueRef,
+    TextDirective,
+    // Node parsed by an InlineParserExt
+    Custom
+}
+
+/// Cst Inline
+///
+/// # Description
+/// Inline node of the content of a line along with the byte range of its source within the content.
+/// The styles, the links and the text directives hold the nodes of their label
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CstInline {
+    kind: CstInlineKind,
+    span: Range<usize>,
+    children: Vec<CstInline>
+}
+
+/// Cst Node
+///
+/// # Description
+/// Line of the document split into its leading whitespace, its marker, its content and its trailing
+/// trivia. Concatenating the parts gives back the line with its line ending
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CstNode {
+    pub kind: CstKind,
+    // Line of the node in the parsed content, None for an inserted node
+    pub line: Option<usize>,
+    indent: String,
+    // Syntax preceding the content i.e `## `, `-. `, `[^1]: `, ```` ```js ````
+    marker: String,
+    content: String,
+    // Trailing whitespace, closing `#` sequence or `{#custom-id}` of a heading
+    trailing: String,
+    line_ending: String,
+    // Inline nodes covering the content, empty for the lines without inline markdown i.e code, html
+    inlines: Vec<CstInline>,
+    // Source of the line, emitted as long as the node isn't modified
+    raw: String,
+    modified: bool
+}
+
+/// Cst
+///
+/// # Description
+/// Lossless concrete syntax tree of a markdown. Every byte of the source belongs to a node and
+/// only the modified nodes are re-emitted when writing the document back
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cst {
+    nodes: Vec<CstNode>
+}
+
+impl CstInline {
+    /// Kind
+    ///
+    /// # Description
+    /// Return the syntax of the inline node
+    ///
+    /// # Return
+    /// &CstInlineKind
+    pub fn kind(&self) -> &CstInlineKind {
+        &self.kind
+    }
+
+    /// Span
+    ///
+    /// # Description
+    /// Return the byte range of the node within the content of its line, delimiters included
+    ///
+    /// # Return
+    /// Range<usize>
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Children
+    ///
+    /// # Description
+    /// Return the inline nodes of the label of a style, a link or a text directive
+    ///
+    /// # Return
+    /// &[CstInline]
+    pub fn children(&self) -> &[CstInline] {
+        &self.children
+    }
+}
+
+impl CstNode {
+    /// Indent
+    ///
+    /// # Description
+    /// Return the leading whitespace of the line
+    ///
+    /// # Return
+    /// &str
+    pub fn indent(&self) -> &str {
+        &self.indent
+    }
+
+    /// Marker
+    ///
+    /// # Description
+    /// Return the syntax marker preceding the content
+    ///
+    /// # Return
+    /// &str
+    pub fn marker(&self) -> &str {
+        &self.marker
+    }
+
+    /// Content
+    ///
+    /// # Description
+    /// Return the content of the line without its trivia
+    ///
+    /// # Return
+    /// &str
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Trailing
+    ///
+    /// # Description
+    /// Return the trivia following the content
+    ///
+    /// # Return
+    /// &str
+    pub fn trailing(&self) -> &str {
+        &self.trailing
+    }
+
+    /// Line Ending
+    ///
+    /// # Description
+    /// Return the line ending of the line, empty for the last line without newline
+    ///
+    /// # Return
+    /// &str
+    pub fn line_ending(&self) -> &str {
+        &self.line_ending
+    }
+
+    /// Inlines
+    ///
+    /// # Description
+    /// Return the inline nodes of the content. The spans are byte ranges of `content`
+    ///
+    /// # Return
+    /// &[CstInline]
+    pub fn inlines(&self) -> &[CstInline] {
+        &self.inlines
+    }
+
+    /// Is Modified
+    ///
+    /// # Description
+    /// Return whether the node has been edited
+    ///
+    /// # Return
+    /// bool
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// Set Content
+    ///
+    /// # Description
+    /// Replace the content of the line. The indent, the marker and the trailing trivia are kept. The
+    /// inline nodes of the previous content are dropped, `set_inline` keeps them up to date
+    ///
+    /// # Arguments
+    /// * `content` &str
+    pub fn set_content(&mut self, content: &str) {
+        self.content = content.to_string();
+        self.inlines.clear();
+        self.modified = true;
+    }
+
+    /// Set Inline
+    ///
+    /// # Description
+    /// Replace the source of a span of the content i.e the span of a link or an emphasis. The rest of
+    /// the line is kept and the inline nodes are parsed again
+    ///
+    /// # Arguments
+    /// * `span` Range<usize>
+    /// * `source` &str
+    /// * `options` &ParserOptions
+    ///
+    /// # Return
+    /// bool false when the span isn't within the content
+    pub fn set_inline(&mut self, span: Range<usize>, source: &str, options: &ParserOptions) -> bool {
+        if self.content.get(span.clone()).is_none() {
+            return false;
+        }
+
+        self.content.replace_range(span, source);
+        self.inlines = get_inlines(&self.content, 0, options);
+        self.modified = true;
+
+        true
+    }
+
+    /// Set Marker
+    ///
+    /// # Description
+    /// Replace the syntax marker of the line i.e to change the level of a heading
+    ///
+    /// # Arguments
+    /// * `marker` &str
+    pub fn set_marker(&mut self, marker: &str) {
+        self.marker = marker.to_string();
+        self.modified = true;
+    }
+
+    /// Set Trailing
+    ///
+    /// # Description
+    /// Replace the trivia following the content
+    ///
+    /// # Arguments
+    /// * `trailing` &str
+    pub fn set_trailing(&mut self, trailing: &str) {
+        self.trailing = trailing.to_string();
+        self.modified = true;
+    }
+}
+
+impl fmt::Display for CstNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.modified {
+            return f.write_str(&self.raw);
+        }
+
+        write!(f, "{}{}{}{}{}", self.indent, self.marker, self.content, self.trailing, self.line_ending)
+    }
+}
+
+impl Cst {
+    /// Nodes
+    ///
+    /// # Description
+    /// Return the nodes in order of the document
+    ///
+    /// # Return
+    /// &[CstNode]
+    pub fn nodes(&self) -> &[CstNode] {
+        &self.nodes
+    }
+
+    /// Node Mut
+    ///
+    /// # Description
+    /// Return the node at the index to edit it
+    ///
+    /// # Arguments
+    /// * `index` usize
+    ///
+    /// # Return
+    /// Option<&mut CstNode>
+    pub fn node_mut(&mut self, index: usize) -> Option<&mut CstNode> {
+        self.nodes.get_mut(index)
+    }
+
+    /// Nodes Mut
+    ///
+    /// # Description
+    /// Iterate over the nodes to edit them
+    ///
+    /// # Return
+    /// impl Iterator<Item = &mut CstNode>
+    pub fn nodes_mut(&mut self) -> impl Iterator<Item = &mut CstNode> {
+        self.nodes.iter_mut()
+    }
+
+    /// Insert Lines
+    ///
+    /// # Description
+    /// Parse the markdown and insert its lines before the node at the index. The previous node
+    /// gets a line ending when it was the last line of the document
+    ///
+    /// # Arguments
+    /// * `index` usize
+    /// * `content` &str
+    /// * `options` &ParserOptions
+    ///
+    /// # Return
+    /// Result<(), error::ParserError>
+    pub fn insert_lines(&mut self, index: usize, content: &str, options: &ParserOptions) -> Result<(), error::ParserError> {
+        let mut inserted = get_cst(content, options)?.nodes;
+        let index = index.min(self.nodes.len());
+        let is_last = index == self.nodes.len();
+
+        for node in inserted.iter_mut() {
+            node.line = None;
+        }
+
+        if let Some(last) = inserted.last_mut().filter(|n| n.line_ending.is_empty() && !is_last) {
+            last.line_ending = String::from("\n");
+            last.modified = true;
+        }
+
+        if let Some(previous) = index.checked_sub(1).and_then(|i| self.nodes.get_mut(i)) {
+            if previous.line_ending.is_empty() {
+                previous.line_ending = String::from("\n");
+                previous.modified = true;
+            }
+        }
+
+        self.nodes.splice(index..index, inserted);
+        Ok(())
+    }
+
+    /// Remove Node
+    ///
+    /// # Description
+    /// Remove the node at the index with its line ending
+    ///
+    /// # Arguments
+    /// * `index` usize
+    ///
+    /// # Return
+    /// Option<CstNode>
+    pub fn remove_node(&mut self, index: usize) -> Option<CstNode> {
+        if index >= self.nodes.len() {
+            return None;
+        }
+
+        Some(self.nodes.remove(index))
+    }
+}
+
+impl fmt::Display for Cst {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for node in &self.nodes {
+            write!(f, "{}", node)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Get Cst
+///
+/// # Description
+/// Build the concrete syntax tree of the markdown from the textual and the code tokens
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<Cst, error::ParserError>
+pub fn get_cst(content: &str, options: &ParserOptions) -> Result<Cst, error::ParserError> {
+    let tokens = token::get_textual_tokens_with_options(content, options)?;
+    let code_tokens = token::get_code_tokens(content)?;
+    let front_matter = get_front_matter(content);
+
+    // kind of the line starting the block of the continuation lines
+    let mut block = CstKind::Blank;
+    let nodes = split_lines(content)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (line, line_ending))| {
+            let kind = if front_matter.as_ref().is_some_and(|f| f.contains_line(idx)) {
+                CstKind::FrontMatter
+            } else {
+                get_kind(&tokens, &code_tokens, idx, line)
+            };
+
+            if !matches!(kind, CstKind::Continuation | CstKind::Blank) {
+                block = kind.clone();
+            }
+
+            let token_content = get_token_content(&tokens, idx);
+            let mut node = get_node(kind, idx, line, line_ending, token_content);
+            if has_inlines(&node.kind, &block) {
+                node.inlines = get_inlines(&node.content, 0, options);
+            }
+
+            node
+        })
+        .collect();
+
+    Ok(Cst { nodes })
+}
+
+/// Split Lines
+///
+/// # Description
+/// Split the content into its lines, each line along with its line ending
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Vec<(&str, &str)>
+fn split_lines(content: &str) -> Vec<(&str, &str)> {
+    let mut lines = Vec::new();
+    let mut rest = content;
+
+    while !rest.is_empty() {
+        let (line, line_ending, next) = match rest.find('\n') {
+            Some(pos) if rest[..pos].ends_with('\r') => (&rest[..pos - 1], &rest[pos - 1..=pos], &rest[pos + 1..]),
+            Some(pos) => (&rest[..pos], &rest[pos..=pos], &rest[pos + 1..]),
+            None => (rest, "", "")
+        };
+
+        lines.push((line, line_ending));
+        rest = next;
+    }
+
+    lines
+}
+
+/// Get Kind
+///
+/// # Description
+/// Get the syntax of the line from the tokens parsed at its index
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `code_tokens` &BTreeMap<usize, CodeToken>
+/// * `idx` usize
+/// * `line` &str
+///
+/// # Return
+/// CstKind
+fn get_kind(tokens: &BTreeMap<usize, TextualToken>, code_tokens: &BTreeMap<usize, CodeToken>, idx: usize, line: &str) -> CstKind {
+    if let Some(code) = code_tokens.get(&idx) {
+        return CstKind::Code(code.operator.clone());
+    }
+
+    if line.trim().is_empty() {
+        return CstKind::Blank;
+    }
+
+    match tokens.get(&idx) {
+        Some(TextualToken { tag: Some(tag), .. }) => CstKind::Tag(tag.operator.clone()),
+        Some(_) => CstKind::Text,
+        None => CstKind::Continuation
+    }
+}
+
+/// Get Token Content
+///
+/// # Description
+/// Get the content parsed at the index
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `idx` usize
+///
+/// # Return
+/// Option<&str>
+fn get_token_content<'t>(tokens: &'t BTreeMap<usize, TextualToken>, idx: usize) -> Option<&'t str> {
+    let token = tokens.get(&idx)?;
+    match (&token.tag, &token.text) {
+        // the content of a definition list gather the whole list
+        (Some(tag), _) if tag.operator != TagOperator::DefinitionList => Some(tag.content.as_ref()),
+        (None, Some(text)) => Some(text.content.as_ref()),
+        _ => None
+    }
+}
+
+/// Get Node
+///
+/// # Description
+/// Split the line into the parts of a node
+///
+/// # Arguments
+/// * `kind` CstKind
+/// * `idx` usize
+/// * `line` &str
+/// * `line_ending` &str
+/// * `token_content` Option<&str>
+///
+/// # Return
+/// CstNode
+fn get_node(kind: CstKind, idx: usize, line: &str, line_ending: &str, token_content: Option<&str>) -> CstNode {
+    let (indent, body) = match kind {
+        // whitespace of a code or a front matter line is part of its content
+        CstKind::Code(CodeOperator::BlockCodeContent) | CstKind::FrontMatter => ("", line),
+        CstKind::Blank => (line, ""),
+        _ => line.split_at(line.len() - line.trim_start().len())
+    };
+
+    let (marker, rest) = body.split_at(get_marker_length(&kind, body));
+    let (content, trailing) = match kind {
+        CstKind::Code(CodeOperator::BlockCodeContent) | CstKind::FrontMatter => (rest, ""),
+        _ => match token_content.filter(|c| !c.is_empty() && rest.starts_with(c)) {
+            Some(content) => rest.split_at(content.len()),
+            None => rest.split_at(rest.trim_end().len())
+        }
+    };
+
+    CstNode {
+        kind,
+        line: Some(idx),
+        indent: indent.to_string(),
+        marker: marker.to_string(),
+        content: content.to_string(),
+        trailing: trailing.to_string(),
+        line_ending: line_ending.to_string(),
+        inlines: Vec::new(),
+        raw: format!("{}{}", line, line_ending),
+        modified: false
+    }
+}
+
+/// Get Marker Length
+///
+/// # Description
+/// Get the byte length of the syntax marker starting the line, including the whitespace following it
+///
+/// # Arguments
+/// * `kind` &CstKind
+/// * `body` &str the line without its indentation
+///
+/// # Return
+/// usize
+fn get_marker_length(kind: &CstKind, body: &str) -> usize {
+    let length = match kind {
+        CstKind::Tag(TagOperator::Heading) => body.len() - body.trim_start_matches('#').len(),
+        CstKind::Tag(TagOperator::BlockQuote) => body.len() - body.trim_start_matches('>').len(),
+        CstKind::Tag(TagOperator::UnorderedList) => ["-.", "*.", "+."]
+            .iter()
+            .find(|m| body.starts_with(*m))
+            .map_or(0, |m| m.len()),
+        CstKind::Tag(TagOperator::OrderedList) => {
+            let digits = body.len() - body.trim_start_matches(char::is_numeric).len();
+            digits + usize::from(body[digits..].starts_with('.'))
+        },
+        CstKind::Tag(TagOperator::FootnoteDefinition) | CstKind::Tag(TagOperator::AbbreviationDefinition) => body
+            .find("]:")
+            .map_or(0, |pos| pos + 2),
+        CstKind::Continuation if body.starts_with(": ") => 1,
+        CstKind::Code(CodeOperator::BlockCodeStart) | CstKind::Code(CodeOperator::BlockCodeEnd) => {
+            let character = body.chars().next().unwrap_or('`');
+            return body.len() - body.trim_start_matches(character).len();
+        },
+        _ => return 0
+    };
+
+    length + (body[length..].len() - body[length..].trim_start().len())
+}
+
+/// Has Inlines
+///
+/// # Description
+/// Whether the content of the line is inline markdown. A continuation line is when it continues a
+/// footnote definition
+///
+/// # Arguments
+/// * `kind` &CstKind
+/// * `block` &CstKind the kind of the line starting the block
+///
+/// # Return
+/// bool
+fn has_inlines(kind: &CstKind, block: &CstKind) -> bool {
+    let kind = match kind {
+        CstKind::Continuation => block,
+        kind => kind
+    };
+
+    matches!(
+        kind,
+        CstKind::Text
+            | CstKind::Tag(TagOperator::Heading)
+            | CstKind::Tag(TagOperator::UnorderedList)
+            | CstKind::Tag(TagOperator::OrderedList)
+            | CstKind::Tag(TagOperator::BlockQuote)
+            | CstKind::Tag(TagOperator::FootnoteDefinition)
+    )
+}
+
+/// Get Inlines
+///
+/// # Description
+/// Parse the content into inline nodes, the spans are shifted by the offset of the content within the line
+///
+/// # Arguments
+/// * `content` &str
+/// * `offset` usize
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Vec<CstInline>
+fn get_inlines(content: &str, offset: usize, options: &ParserOptions) -> Vec<CstInline> {
+    get_inline_spans(content, options)
+        .into_iter()
+        .map(|(node, span)| {
+            let source = &content[span.clone()];
+            let children = match get_label_range(&node, source) {
+                Some(label) => {
+                    let children = get_inlines(&source[label.clone()], offset + span.start + label.start, options);
+                    match node {
+                        // a link can't be nested within an other link
+                        InlineNode::Link { .. } => unlink_inlines(children),
+                        _ => children
+                    }
+                },
+                None => Vec::new()
+            };
+
+            CstInline {
+                kind: get_inline_kind(&node),
+                span: offset + span.start..offset + span.end,
+                children
+            }
+        })
+        .collect()
+}
+
+/// Get Label Range
+///
+/// # Description
+/// Get the byte range of the label within the source of a node i.e the text between the delimiters
+/// of a style or the brackets of a link
+///
+/// # Arguments
+/// * `node` &InlineNode
+/// * `source` &str
+///
+/// # Return
+/// Option<Range<usize>>
+fn get_label_range(node: &InlineNode, source: &str) -> Option<Range<usize>> {
+    match node {
+        InlineNode::Strong(_) | InlineNode::Strike(_) => Some(2..source.len() - 2),
+        InlineNode::Emphasis(_) => Some(1..source.len() - 1),
+        InlineNode::Link { .. } => find_closing(source, '[', ']').map(|end| 1..end),
+        InlineNode::TextDirective { directive, .. } if directive.label.is_some() => {
+            let start = source.find('[')?;
+            find_closing(&source[start..], '[', ']').map(|end| start + 1..start + end)
+        },
+        _ => None
+    }
+}
+
+/// Unlink Inlines
+///
+/// # Description
+/// Turn the mentions and the issue references of a link label back into text
+///
+/// # Arguments
+/// * `inlines` Vec<CstInline>
+///
+/// # Return
+/// Vec<CstInline>
+fn unlink_inlines(inlines: Vec<CstInline>) -> Vec<CstInline> {
+    let mut unlinked: Vec<CstInline> = Vec::new();
+
+    for mut inline in inlines {
+        match inline.kind {
+            CstInlineKind::Mention | CstInlineKind::IssueRef => inline.kind = CstInlineKind::Text,
+            _ => inline.children = unlink_inlines(inline.children)
+        }
+
+        match unlinked.last_mut() {
+            Some(previous) if previous.kind == CstInlineKind::Text && inline.kind == CstInlineKind::Text => {
+                previous.span.end = inline.span.end;
+            },
+            _ => unlinked.push(inline)
+        }
+    }
+
+    unlinked
+}
+
+/// Get Inline Kind
+///
+/// # Description
+/// Get the syntax of an inline node
+///
+/// # Arguments
+/// * `node` &InlineNode
+///
+/// # Return
+/// CstInlineKind
+fn get_inline_kind(node: &InlineNode) -> CstInlineKind {
+    match node {
+        InlineNode::Text(_) => CstInlineKind::Text,
+        InlineNode::Strong(_) => CstInlineKind::Strong,
+        InlineNode::Emphasis(_) => CstInlineKind::Emphasis,
+        InlineNode::Strike(_) => CstInlineKind::Strike,
+        InlineNode::Code(_) => CstInlineKind::Code,
+        InlineNode::Link { .. } => CstInlineKind::Link,
+        InlineNode::Image { .. } => CstInlineKind::Image,
+        InlineNode::FootnoteRef(_) => CstInlineKind::FootnoteRef,
+        InlineNode::Html(_) => CstInlineKind::Html,
+        InlineNode::InlineMath(_) => CstInlineKind::InlineMath,
+        InlineNode::Emoji { .. } => CstInlineKind::Emoji,
+        InlineNode::Mention(_) => CstInlineKind::Mention,
+        InlineNode::IssueRef { .. } => CstInlineKind::IssueRef,
+        InlineNode::TextDirective { .. } => CstInlineKind::TextDirective,
+        InlineNode::Custom(_) => CstInlineKind::Custom
+    }
+}
@@ -2,8 +2,20 @@ use std::fmt;
 
 // Error message
 pub const EMPTY_CONTENT: &str = "Cannot parse empty content";
+pub const INVALID_FORMAT_STYLE: &str = "Unsupported bullet, emphasis or fence character in the format style";
+pub const READ_INPUT: &str = "Unable to read the markdown";
+pub const INVALID_UTF8: &str = "The markdown isn't valid UTF-8";
+pub const WRITE_OUTPUT: &str = "Unable to write the markdown";
+pub const TOC_NOT_FOUND: &str = "Unable to find the <!-- toc --> and <!-- tocstop --> markers";
+#[cfg(feature = "front_matter")]
+pub const FRONT_MATTER_DESERIALIZE: &str = "Unable to deserialize the front matter";
+#[cfg(feature = "serde")]
+pub const JSON_SERIALIZE: &str = "Unable to serialize the tokens to json";
+#[cfg(feature = "serde")]
+pub const MDAST_INVALID: &str = "Unable to read the mdast json";
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParserError {
     pub message: String
 }
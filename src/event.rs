@@ -0,0 +1,620 @@
+use std::borrow::Cow;
+use std::clone::Clone;
+use std::collections::VecDeque;
+use std::iter::{Enumerate, Peekable};
+use std::str::Lines;
+use crate::options::ParserOptions;
+use crate::parser::code::{CodeOperator, get_info_string};
+use crate::parser::directive::DirectiveMeta;
+use crate::parser::extension::CustomNode;
+use crate::parser::github::IssueRef;
+use crate::parser::heading::HeadingLevel;
+use crate::parser::inline::{InlineNode, get_inline_nodes};
+use crate::parser::table::TableAlignment;
+use crate::token::{TextualToken, TextualTokens};
+use crate::token::code::{CodeToken, CodeTokens};
+use crate::token::tag::{TagToken, TagOperator};
+
+/// Tag
+///
+/// # Description
+/// Element opened by an `Event::Start` and closed by the matching `Event::End`
+#[derive(Debug, PartialEq, Clone)]
+pub enum Tag<'a> {
+    Paragraph,
+    Heading {
+        level: HeadingLevel,
+        // Explicit {#custom-id} of the heading
+        id: Option<Cow<'a, str>>
+    },
+    BlockQuote,
+    // Info string of the fence. The lines of the code are separated by a SoftBreak
+    CodeBlock(Cow<'a, str>),
+    // Start number of an ordered list, None for an unordered list
+    List(Option<usize>),
+    Item,
+    FootnoteDefinition(Cow<'a, str>),
+    HtmlBlock,
+    DefinitionList,
+    DefinitionTerm,
+    DefinitionDescription,
+    // Alignment of the columns
+    Table(Vec<TableAlignment>),
+    // Header row of the table
+    TableHead,
+    TableRow,
+    TableCell,
+    // The blocks until the closing fence are within the container
+    ContainerDirective(DirectiveMeta),
+    // Label of a container directive
+    DirectiveLabel,
+    LeafDirective(DirectiveMeta),
+    TextDirective(DirectiveMeta),
+    Emphasis,
+    Strong,
+    Strikethrough,
+    Link {
+        url: Cow<'a, str>
+    },
+    // The alt text is the Text between the start and the end of the image
+    Image {
+        url: Cow<'a, str>
+    }
+}
+
+/// Event
+///
+/// # Description
+/// Event of the pull parser. The events are returned in the order of the document
+#[derive(Debug, PartialEq, Clone)]
+pub enum Event<'a> {
+    Start(Tag<'a>),
+    End(Tag<'a>),
+    // Borrowed from the source unless unescaped or replaced i.e. a smart punctuation
+    Text(Cow<'a, str>),
+    // Inline `code`
+    Code(Cow<'a, str>),
+    // Content of a html block
+    Html(Cow<'a, str>),
+    InlineHtml(Cow<'a, str>),
+    InlineMath(Cow<'a, str>),
+    DisplayMath(Cow<'a, str>),
+    FootnoteReference(Cow<'a, str>),
+    // Block or inline node of a custom syntax
+    Custom(Box<dyn CustomNode>),
+    // Line ending within a paragraph, a block quote or a code block
+    SoftBreak
+}
+
+/// Events
+///
+/// Pull parser over the lines of a content. The textual and the code tokens are read lazily, only the
+/// blocks being parsed are kept in memory
+pub struct Events<'a, 'o> {
+    options: &'o ParserOptions,
+    tokens: Peekable<TextualTokens<'a, 'o>>,
+    code_tokens: Peekable<CodeTokens<'a>>,
+    // Source lines, used to borrow the content of the code blocks
+    lines: Enumerate<Lines<'a>>,
+    // Events which are ready to be returned
+    queue: VecDeque<Event<'a>>,
+    // Blocks which are opened, the outermost first
+    stack: Vec<Tag<'a>>,
+    // Directive containers which are opened, the outermost first
+    directives: Vec<Tag<'a>>,
+    // Indentation of the fence of the code block being read
+    fence_indent: usize,
+    // Whether a line of the code block being read has been returned
+    in_code_content: bool,
+    // The content of a ```math fence is already part of the display math
+    in_math_fence: bool,
+    done: bool
+}
+
+impl<'a, 'o> Events<'a, 'o> {
+    /// New
+    ///
+    /// # Description
+    /// Create the pull parser. Nothing is parsed until the first event is requested
+    ///
+    /// # Arguments
+    /// * `content` &'a str
+    /// * `options` &'o ParserOptions
+    ///
+    /// # Return
+    /// Events<'a, 'o>
+    pub fn new(content: &'a str, options: &'o ParserOptions) -> Self {
+        Events {
+            options,
+            tokens: TextualTokens::new(content, options).peekable(),
+            code_tokens: CodeTokens::new(content).peekable(),
+            lines: content.lines().enumerate(),
+            queue: VecDeque::new(),
+            stack: Vec::new(),
+            directives: Vec::new(),
+            fence_indent: 0,
+            in_code_content: false,
+            in_math_fence: false,
+            done: false
+        }
+    }
+
+    /// Read Line
+    ///
+    /// # Description
+    /// Read the tokens of the next line and queue their events. Close the opened blocks at the end
+    /// of the content
+    fn read_line(&mut self) {
+        let next_token = self.tokens.peek().map(|(idx, _)| *idx);
+        let next_code = self.code_tokens.peek().map(|(idx, _)| *idx);
+        let idx = match (next_token, next_code) {
+            (Some(token), Some(code)) => token.min(code),
+            (Some(idx), None) | (None, Some(idx)) => idx,
+            (None, None) => {
+                self.close_blocks();
+                while let Some(directive) = self.directives.pop() {
+                    self.queue.push_back(Event::End(directive));
+                }
+
+                self.done = true;
+                return;
+            }
+        };
+
+        let textual = self.tokens.next_if(|(line, _)| *line == idx).map(|(_, token)| token);
+        let code = self.code_tokens.next_if(|(line, _)| *line == idx).map(|(_, token)| token);
+
+        self.push_line(textual.as_ref(), code.as_ref(), idx);
+    }
+
+    /// Push Line
+    ///
+    /// # Description
+    /// Queue the events of the tokens of a line
+    ///
+    /// # Arguments
+    /// * `textual` Option<&TextualToken>
+    /// * `code` Option<&CodeToken>
+    /// * `idx` usize
+    fn push_line(&mut self, textual: Option<&TextualToken<'a>>, code: Option<&CodeToken<'a>>, idx: usize) {
+        if let Some(tag) = textual.and_then(|t| t.tag.as_ref()).filter(|t| t.operator == TagOperator::DisplayMath) {
+            self.push_tag(tag, idx);
+            self.in_math_fence = code.is_some_and(|c| c.operator == CodeOperator::BlockCodeStart);
+            return;
+        }
+
+        if let Some(code) = code {
+            if self.in_math_fence {
+                self.in_math_fence = code.operator != CodeOperator::BlockCodeEnd;
+                return;
+            }
+
+            self.push_code(code, idx);
+            return;
+        }
+
+        if let Some(textual) = textual {
+            self.push_textual(textual, idx);
+        }
+    }
+
+    /// Get Line
+    ///
+    /// # Description
+    /// Get a line of the source. The lines are read forward only
+    ///
+    /// # Arguments
+    /// * `idx` usize
+    ///
+    /// # Return
+    /// &'a str
+    fn get_line(&mut self, idx: usize) -> &'a str {
+        self.lines
+            .find(|(line, _)| *line == idx)
+            .map_or("", |(_, line)| line)
+    }
+
+    /// Push Code
+    ///
+    /// # Description
+    /// Queue the events of a code block line. The content borrows the source
+    ///
+    /// # Arguments
+    /// * `code` &CodeToken
+    /// * `idx` usize
+    fn push_code(&mut self, code: &CodeToken<'a>, idx: usize) {
+        let line = self.get_line(idx);
+        match code.operator {
+            CodeOperator::BlockCodeStart => {
+                self.close_blocks();
+                let info = get_info_string(line);
+                self.fence_indent = get_indent(line);
+                self.in_code_content = false;
+                self.open(Tag::CodeBlock(Cow::Borrowed(info)));
+            },
+            CodeOperator::BlockCodeContent => {
+                if self.in_code_content {
+                    self.queue.push_back(Event::SoftBreak);
+                }
+
+                self.in_code_content = true;
+
+                let indent = get_indent(line).min(self.fence_indent);
+                self.queue.push_back(Event::Text(Cow::Borrowed(&line[indent..])));
+            },
+            CodeOperator::BlockCodeEnd => self.close_blocks()
+        }
+    }
+
+    /// Push Textual
+    ///
+    /// # Description
+    /// Queue the events of a textual token. Consecutive lines of text are part of the same paragraph
+    ///
+    /// # Arguments
+    /// * `textual` &TextualToken
+    /// * `idx` usize
+    fn push_textual(&mut self, textual: &TextualToken<'a>, idx: usize) {
+        if let Some(tag) = &textual.tag {
+            self.push_tag(tag, idx);
+            return;
+        }
+
+        let text = match &textual.text {
+            Some(text) => text,
+            None => return
+        };
+
+        if text.content.is_empty() {
+            self.close_blocks();
+            return;
+        }
+
+        if self.stack.first() == Some(&Tag::Paragraph) {
+            self.queue.push_back(Event::SoftBreak);
+        } else {
+            self.close_blocks();
+            self.open(Tag::Paragraph);
+        }
+
+        self.push_inline(text.content.clone());
+    }
+
+    /// Push Tag
+    ///
+    /// # Description
+    /// Queue the events of a tag token. Consecutive list items and block quote lines are grouped
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    /// * `idx` usize
+    fn push_tag(&mut self, tag: &TagToken<'a>, idx: usize) {
+        match tag.operator {
+            TagOperator::Heading => {
+                self.close_blocks();
+                let metas = tag.metas.as_ref();
+                let heading = Tag::Heading {
+                    level: metas.map_or(HeadingLevel::H1, |m| m.heading_kind.clone()),
+                    id: metas.and_then(|m| m.id.clone()).map(Cow::Owned)
+                };
+
+                self.push_element(heading, tag.content.clone());
+            },
+            TagOperator::UnorderedList | TagOperator::OrderedList => {
+                let ordered = tag.operator == TagOperator::OrderedList;
+                let is_same_list = matches!(self.stack.first(), Some(Tag::List(start)) if start.is_some() == ordered);
+                if !is_same_list {
+                    self.close_blocks();
+                    let start = self.get_line(idx)
+                        .trim_start()
+                        .split('.')
+                        .next()
+                        .and_then(|digits| digits.parse::<usize>().ok());
+
+                    self.open(Tag::List(if ordered { start.or(Some(1)) } else { None }));
+                }
+
+                self.push_element(Tag::Item, tag.content.clone());
+            },
+            TagOperator::BlockQuote => {
+                if self.stack.first() == Some(&Tag::BlockQuote) {
+                    self.queue.push_back(Event::SoftBreak);
+                } else {
+                    self.close_blocks();
+                    self.open(Tag::BlockQuote);
+                    self.open(Tag::Paragraph);
+                }
+
+                self.push_inline(tag.content.clone());
+            },
+            TagOperator::FootnoteDefinition => {
+                self.close_blocks();
+                let footnote = tag.metas.as_ref().and_then(|m| m.footnote.clone());
+                let label = footnote.as_ref().map(|f| f.label.clone()).unwrap_or_default();
+                let paragraphs = footnote.map(|f| f.paragraphs).filter(|p| !p.is_empty()).unwrap_or_else(|| vec![tag.content.to_string()]);
+
+                self.open(Tag::FootnoteDefinition(Cow::Owned(label)));
+                for paragraph in paragraphs {
+                    self.push_element(Tag::Paragraph, Cow::Owned(paragraph));
+                }
+
+                self.close_blocks();
+            },
+            TagOperator::HtmlBlock => {
+                self.close_blocks();
+                self.queue.push_back(Event::Start(Tag::HtmlBlock));
+                self.queue.push_back(Event::Html(tag.content.clone()));
+                self.queue.push_back(Event::End(Tag::HtmlBlock));
+            },
+            TagOperator::DisplayMath => {
+                self.close_blocks();
+                self.queue.push_back(Event::DisplayMath(Cow::Owned(tag.content.trim().to_string())));
+            },
+            TagOperator::DefinitionList => {
+                self.close_blocks();
+                let items = tag.metas.as_ref().and_then(|m| m.definition_list.as_ref()).map(|l| l.items.clone());
+
+                self.open(Tag::DefinitionList);
+                for item in items.unwrap_or_default() {
+                    self.push_element(Tag::DefinitionTerm, Cow::Owned(item.term));
+                    for definition in item.definitions {
+                        self.push_element(Tag::DefinitionDescription, Cow::Owned(definition));
+                    }
+                }
+
+                self.close_blocks();
+            },
+            TagOperator::Table => {
+                self.close_blocks();
+                let table = match tag.metas.as_ref().and_then(|m| m.table.clone()) {
+                    Some(table) => table,
+                    None => return
+                };
+
+                self.open(Tag::Table(table.alignments));
+                self.push_table_row(Tag::TableHead, table.header);
+                for row in table.rows {
+                    self.push_table_row(Tag::TableRow, row);
+                }
+
+                self.close_blocks();
+            },
+            TagOperator::ContainerDirective => {
+                self.close_blocks();
+                let directive = Tag::ContainerDirective(tag.metas.as_ref().and_then(|m| m.directive.clone()).unwrap_or_default());
+                self.queue.push_back(Event::Start(directive.clone()));
+                self.directives.push(directive);
+
+                if !tag.content.is_empty() {
+                    self.push_element(Tag::DirectiveLabel, tag.content.clone());
+                }
+            },
+            TagOperator::ContainerDirectiveEnd => {
+                self.close_blocks();
+                if let Some(directive) = self.directives.pop() {
+                    self.queue.push_back(Event::End(directive));
+                }
+            },
+            TagOperator::LeafDirective => {
+                self.close_blocks();
+                let directive = tag.metas.as_ref().and_then(|m| m.directive.clone()).unwrap_or_default();
+                self.push_element(Tag::LeafDirective(directive), tag.content.clone());
+            },
+            TagOperator::Custom(ref node) => {
+                self.close_blocks();
+                self.queue.push_back(Event::Custom(node.clone()));
+            },
+            // only used to annotate the text
+            TagOperator::AbbreviationDefinition => {}
+        }
+    }
+
+    /// Push Element
+    ///
+    /// # Description
+    /// Queue the start, the inline content and the end of an element
+    ///
+    /// # Arguments
+    /// * `tag` Tag<'a>
+    /// * `content` Cow<'a, str>
+    fn push_element(&mut self, tag: Tag<'a>, content: Cow<'a, str>) {
+        self.queue.push_back(Event::Start(tag.clone()));
+        self.push_inline(content);
+        self.queue.push_back(Event::End(tag));
+    }
+
+    /// Push Table Row
+    ///
+    /// # Description
+    /// Queue the header or a row of a table with its cells
+    ///
+    /// # Arguments
+    /// * `tag` Tag<'a>
+    /// * `cells` Vec<String>
+    fn push_table_row(&mut self, tag: Tag<'a>, cells: Vec<String>) {
+        self.queue.push_back(Event::Start(tag.clone()));
+        for cell in cells {
+            self.push_element(Tag::TableCell, Cow::Owned(cell));
+        }
+
+        self.queue.push_back(Event::End(tag));
+    }
+
+    /// Push Inline
+    ///
+    /// # Description
+    /// Queue the events of the inline content. The lines of the content are separated by a SoftBreak
+    ///
+    /// # Arguments
+    /// * `content` Cow<'a, str>
+    fn push_inline(&mut self, content: Cow<'a, str>) {
+        // the text of a borrowed content is borrowed from the source as well
+        let mut source_lines = match content {
+            Cow::Borrowed(source) => Some(source.lines()),
+            Cow::Owned(_) => None
+        };
+
+        for (pos, line) in content.lines().enumerate() {
+            if pos > 0 {
+                self.queue.push_back(Event::SoftBreak);
+            }
+
+            let source = source_lines.as_mut().and_then(Iterator::next).map(str::trim);
+            let nodes = get_inline_nodes(line.trim(), self.options);
+            self.push_nodes(&nodes, source);
+        }
+    }
+
+    /// Push Nodes
+    ///
+    /// # Description
+    /// Queue the events of the inline nodes
+    ///
+    /// # Arguments
+    /// * `nodes` &[InlineNode]
+    /// * `source` Option<&'a str> line of the nodes when it borrows the source
+    fn push_nodes(&mut self, nodes: &[InlineNode], source: Option<&'a str>) {
+        for node in nodes {
+            match node {
+                InlineNode::Text(text) => self.queue.push_back(Event::Text(borrow_source(text, source))),
+                InlineNode::Strong(children) => self.push_nested(Tag::Strong, children, source),
+                InlineNode::Emphasis(children) => self.push_nested(Tag::Emphasis, children, source),
+                InlineNode::Strike(children) => self.push_nested(Tag::Strikethrough, children, source),
+                InlineNode::Code(code) => self.queue.push_back(Event::Code(borrow_source(code, source))),
+                InlineNode::Link { title, url, .. } => {
+                    self.push_nested(Tag::Link { url: borrow_source(url, source) }, title, source);
+                },
+                InlineNode::Image { alt_text, url, .. } => {
+                    let image = Tag::Image { url: borrow_source(url, source) };
+                    self.queue.push_back(Event::Start(image.clone()));
+                    self.queue.push_back(Event::Text(borrow_source(alt_text, source)));
+                    self.queue.push_back(Event::End(image));
+                },
+                InlineNode::FootnoteRef(label) => self.queue.push_back(Event::FootnoteReference(borrow_source(label, source))),
+                InlineNode::Html(html) => self.queue.push_back(Event::InlineHtml(borrow_source(html, source))),
+                InlineNode::TextDirective { directive, label } => {
+                    self.push_nested(Tag::TextDirective(directive.clone()), label, source);
+                },
+                InlineNode::Custom(node) => self.queue.push_back(Event::Custom(node.clone())),
+                InlineNode::InlineMath(math) => self.queue.push_back(Event::InlineMath(borrow_source(math, source))),
+                InlineNode::Emoji { unicode, .. } => self.queue.push_back(Event::Text(Cow::Owned(unicode.clone()))),
+                InlineNode::Mention(user) => {
+                    let url = self.options.github.as_ref().map(|github| github.get_mention_url(user));
+                    self.push_reference(format!("@{}", user), url);
+                },
+                InlineNode::IssueRef { repository, number } => {
+                    let issue = IssueRef { repository: repository.clone(), number: *number, col: None };
+                    let url = self.options.github.as_ref().and_then(|github| github.get_issue_url(&issue));
+                    self.push_reference(format!("{}#{}", repository.as_deref().unwrap_or(""), number), url);
+                }
+            }
+        }
+    }
+
+    /// Push Nested
+    ///
+    /// # Description
+    /// Queue an inline element around the events of its children
+    ///
+    /// # Arguments
+    /// * `tag` Tag<'a>
+    /// * `children` &[InlineNode]
+    /// * `source` Option<&'a str>
+    fn push_nested(&mut self, tag: Tag<'a>, children: &[InlineNode], source: Option<&'a str>) {
+        self.queue.push_back(Event::Start(tag.clone()));
+        self.push_nodes(children, source);
+        self.queue.push_back(Event::End(tag));
+    }
+
+    /// Push Reference
+    ///
+    /// # Description
+    /// Queue a mention or an issue reference as a link, or as a text when the url is unknown
+    ///
+    /// # Arguments
+    /// * `text` String
+    /// * `url` Option<String>
+    fn push_reference(&mut self, text: String, url: Option<String>) {
+        match url {
+            Some(url) => {
+                let link = Tag::Link { url: Cow::Owned(url) };
+                self.queue.push_back(Event::Start(link.clone()));
+                self.queue.push_back(Event::Text(Cow::Owned(text)));
+                self.queue.push_back(Event::End(link));
+            },
+            None => self.queue.push_back(Event::Text(Cow::Owned(text)))
+        }
+    }
+
+    /// Open
+    ///
+    /// # Description
+    /// Queue the start of a block which is closed by a following line
+    ///
+    /// # Arguments
+    /// * `tag` Tag<'a>
+    fn open(&mut self, tag: Tag<'a>) {
+        self.queue.push_back(Event::Start(tag.clone()));
+        self.stack.push(tag);
+    }
+
+    /// Close Blocks
+    ///
+    /// # Description
+    /// Queue the end of the opened blocks, the innermost first
+    fn close_blocks(&mut self) {
+        while let Some(tag) = self.stack.pop() {
+            self.queue.push_back(Event::End(tag));
+        }
+    }
+}
+
+impl<'a> Iterator for Events<'a, '_> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.queue.pop_front() {
+                return Some(event);
+            }
+
+            if self.done {
+                return None;
+            }
+
+            self.read_line();
+        }
+    }
+}
+
+/// Get Indent
+///
+/// # Description
+/// Return the number of leading whitespace of a line
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// usize
+fn get_indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Borrow Source
+///
+/// # Description
+/// Borrow a text from the source line when it is written as is, i.e. not an escaped character or a
+/// smart punctuation. The text is copied otherwise
+///
+/// # Arguments
+/// * `text` &str
+/// * `source` Option<&'a str>
+///
+/// # Return
+/// Cow<'a, str>
+fn borrow_source<'a>(text: &str, source: Option<&'a str>) -> Cow<'a, str> {
+    source
+        .and_then(|source| source.find(text).map(|start| &source[start..start + text.len()]))
+        .map_or_else(|| Cow::Owned(text.to_string()), Cow::Borrowed)
+}
@@ -0,0 +1,61 @@
+use std::clone::Clone;
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::error;
+use crate::options::ParserOptions;
+use crate::parser::front_matter::{FrontMatter, get_front_matter};
+use crate::token::{self, TextualToken};
+use crate::token::code::CodeToken;
+
+// Version of the json shape, bumped on a breaking change of the serialized types
+pub const JSON_VERSION: u32 = 1;
+
+/// Parsed Markdown
+///
+/// # Description
+/// Result of the parsing of a markdown. This is the json document returned by `parse_to_json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedMarkdown<'a> {
+    pub version: u32,
+    pub front_matter: Option<FrontMatter>,
+    // Textual tokens by line index
+    pub tokens: BTreeMap<usize, TextualToken<'a>>,
+    // Code tokens by line index
+    pub code_tokens: BTreeMap<usize, CodeToken<'a>>
+}
+
+/// Get Parsed Markdown
+///
+/// # Description
+/// Parse the front matter, the textual tokens and the code tokens of the markdown
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<ParsedMarkdown, error::ParserError>
+pub fn get_parsed_markdown<'a>(content: &'a str, options: &ParserOptions) -> Result<ParsedMarkdown<'a>, error::ParserError> {
+    Ok(ParsedMarkdown {
+        version: JSON_VERSION,
+        front_matter: get_front_matter(content),
+        tokens: token::get_textual_tokens_with_options(content, options)?,
+        code_tokens: token::get_code_tokens(content)?
+    })
+}
+
+/// To Json
+///
+/// # Description
+/// Serialize the parsed markdown to json
+///
+/// # Arguments
+/// * `parsed` &ParsedMarkdown
+///
+/// # Return
+/// Result<String, error::ParserError>
+pub fn to_json(parsed: &ParsedMarkdown) -> Result<String, error::ParserError> {
+    serde_json::to_string(parsed).map_err(|err| error::ParserError {
+        message: format!("{}: {}", error::JSON_SERIALIZE, err)
+    })
+}
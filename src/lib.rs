@@ -1,9 +1,57 @@
 use std::collections::BTreeMap;
+use std::path::Path;
+mod cst;
 mod parser;
 mod error;
+mod event;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "serde")]
+mod mdast;
+mod options;
+mod query;
+mod reader;
+mod render;
+mod sanitize;
+mod section;
+mod toc;
 mod token;
+mod visit;
 mod tests;
 
+pub use cst::{Cst, CstInline, CstInlineKind, CstKind, CstNode};
+pub use event::{Event, Events, Tag};
+#[cfg(feature = "serde")]
+pub use json::ParsedMarkdown;
+#[cfg(feature = "serde")]
+pub use mdast::{MdastData, MdastNode, MdastKind, Position, Point};
+pub use options::ParserOptions;
+pub use parser::code::CodeOperator;
+pub use parser::directive::{DirectiveKind, DirectiveMeta};
+pub use parser::extension::{BlockContinuation, BlockParserExt, CustomNode, CustomNodeClone, InlineParserExt};
+pub use parser::external::{ImageMeta, LinkMeta};
+pub use parser::github::GithubOptions;
+pub use parser::heading::HeadingLevel;
+pub use render::markdown::{FormatStyle, OrderedNumbering};
+pub use render::terminal::TerminalOptions;
+pub use query::{CodeBlock, Document, Heading, Image, Link, Node, Selection};
+pub use reader::{ReaderToken, ReaderTokens};
+pub use parser::slug::{Slugger, GithubSlugger, GitlabSlugger, MdBookSlugger};
+pub use parser::table::{TableAlignment, TableMeta};
+pub use sanitize::SanitizePolicy;
+pub use parser::text_style::TextOption;
+pub use section::Section;
+pub use toc::TocEntry;
+pub use token::TextualToken;
+pub use token::code::CodeToken;
+pub use token::tag::{TagOperator, TagToken};
+pub use token::text::TextToken;
+pub use visit::{
+    Fold, Visitor, VisitorMut,
+    walk_fold_tag, walk_fold_text, walk_fold_textual,
+    walk_tag, walk_tag_mut, walk_text, walk_text_mut, walk_textual, walk_textual_mut
+};
+
 /// Parse Markdown
 ///
 /// # Description
@@ -14,10 +62,25 @@ mod tests;
 ///
 /// # Return
 /// Result<BTreeMap<usize, lexer::token::Token, error::ParserError>>
-pub fn parse_markdown(content: &str) -> Result<BTreeMap<usize, token::TextualToken>, error::ParserError> {
+pub fn parse_markdown(content: &str) -> Result<BTreeMap<usize, token::TextualToken<'_>>, error::ParserError> {
     token::get_textual_tokens(content)
 }
 
+/// Parse Markdown With Options
+///
+/// # Description
+/// Parse the markdown with the syntaxes configured by the options
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<BTreeMap<usize, token::TextualToken>, error::ParserError>
+pub fn parse_markdown_with_options<'a>(content: &'a str, options: &ParserOptions) -> Result<BTreeMap<usize, token::TextualToken<'a>>, error::ParserError> {
+    token::get_textual_tokens_with_options(content, options)
+}
+
 /// Parse Code Markdown
 ///
 /// # Description
@@ -28,6 +91,472 @@ pub fn parse_markdown(content: &str) -> Result<BTreeMap<usize, token::TextualTok
 ///
 /// # Return
 /// Result<BTreeMap<usize, token::code::CodeToken>, error::ParserError>
-pub fn parse_code_markdown(content: &str) -> Result<BTreeMap<usize, token::code::CodeToken>, error::ParserError> {
+pub fn parse_code_markdown(content: &str) -> Result<BTreeMap<usize, token::code::CodeToken<'_>>, error::ParserError> {
     token::get_code_tokens(content)
-}
\ No newline at end of file
+}
+
+/// Parse Events
+///
+/// # Description
+/// Create a pull parser returning the events of the markdown in the order of the document. The lines
+/// are parsed lazily and only the blocks being read are kept in memory, `parse_markdown` collects the
+/// tokens of the same line parser. The footnote numbers, the slugs and the abbreviations require the whole
+/// document and aren't part of the events. The inline texts borrow the source when written as is
+///
+/// # Arguments
+/// * `content` &'a str
+/// * `options` &'o ParserOptions
+///
+/// # Return
+/// Events<'a, 'o>
+pub fn parse_events<'a, 'o>(content: &'a str, options: &'o ParserOptions) -> Events<'a, 'o> {
+    Events::new(content, options)
+}
+
+/// Parse Reader
+///
+/// # Description
+/// Parse a markdown read line by line, i.e. from a file too large to be loaded in memory. The tokens
+/// are returned as soon as their block is closed. As for `parse_events`, the footnote numbers, the
+/// slugs and the abbreviations aren't set
+///
+/// # Arguments
+/// * `reader` R
+/// * `options` &ParserOptions
+///
+/// # Return
+/// ReaderTokens<R> iterator of Result<(usize, ReaderToken), error::ParserError>
+pub fn parse_reader<R: std::io::BufRead>(reader: R, options: &ParserOptions) -> ReaderTokens<'_, R> {
+    ReaderTokens::new(reader, options)
+}
+
+/// Parse Document
+///
+/// # Description
+/// Parse the markdown into a document which can be queried for its headings, links, images,
+/// code blocks and sections
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<Document, error::ParserError>
+pub fn parse_document<'a>(content: &'a str, options: &ParserOptions) -> Result<Document<'a>, error::ParserError> {
+    Document::new(content, options)
+}
+
+/// Parse Cst
+///
+/// # Description
+/// Parse the markdown into a lossless concrete syntax tree. Writing the tree back with `to_string`
+/// reproduce the content byte for byte, apart from the nodes which have been edited
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<Cst, error::ParserError>
+pub fn parse_cst(content: &str, options: &ParserOptions) -> Result<Cst, error::ParserError> {
+    cst::get_cst(content, options)
+}
+
+/// Parse Footnotes
+///
+/// # Description
+/// List the footnotes of the markdown. Footnotes are numbered in order of their first reference
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Result<token::footnote::Footnotes, error::ParserError>
+pub fn parse_footnotes(content: &str) -> Result<token::footnote::Footnotes, error::ParserError> {
+    let tokens = token::get_textual_tokens(content)?;
+
+    Ok(token::footnote::get_footnotes(&tokens))
+}
+
+
+/// Parse Front Matter
+///
+/// # Description
+/// Retrieve the YAML or TOML front matter which is located at the start of the markdown
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Result<Option<parser::front_matter::FrontMatter>, error::ParserError>
+pub fn parse_front_matter(content: &str) -> Result<Option<parser::front_matter::FrontMatter>, error::ParserError> {
+    if content.is_empty() {
+        return Err(error::ParserError{message: error::EMPTY_CONTENT.to_string()});
+    }
+
+    Ok(parser::front_matter::get_front_matter(content))
+}
+
+/// Parse To Json
+///
+/// # Description
+/// Parse the markdown and serialize the front matter, the textual tokens and the code tokens to json.
+/// The json can be deserialized back into a `ParsedMarkdown`
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<String, error::ParserError>
+#[cfg(feature = "serde")]
+pub fn parse_to_json(content: &str, options: &ParserOptions) -> Result<String, error::ParserError> {
+    let parsed = json::get_parsed_markdown(content, options)?;
+
+    json::to_json(&parsed)
+}
+
+/// Export Mdast
+///
+/// # Description
+/// Parse the markdown and serialize it as a mdast tree (the syntax tree of unified / remark) to json.
+/// The blocks hold the position of their lines
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<String, error::ParserError>
+#[cfg(feature = "serde")]
+pub fn export_mdast(content: &str, options: &ParserOptions) -> Result<String, error::ParserError> {
+    mdast::get_mdast_json(content, options)
+}
+
+/// Import Mdast
+///
+/// # Description
+/// Read a mdast tree from json and write it back as markdown. The node types which aren't supported
+/// are skipped
+///
+/// # Arguments
+/// * `json` &str
+///
+/// # Return
+/// Result<String, error::ParserError>
+#[cfg(feature = "serde")]
+pub fn import_mdast(json: &str) -> Result<String, error::ParserError> {
+    let node = mdast::parse_mdast_json(json)?;
+
+    Ok(mdast::get_markdown(&node))
+}
+
+/// Sanitize Markdown
+///
+/// # Description
+/// Apply a sanitize policy on the parsed markdown before rendering untrusted content. The options
+/// are the ones the markdown has been parsed with
+///
+/// # Arguments
+/// * `tokens` &mut BTreeMap<usize, token::TextualToken>
+/// * `policy` &SanitizePolicy
+/// * `options` &ParserOptions
+pub fn sanitize_markdown(tokens: &mut BTreeMap<usize, token::TextualToken>, policy: &SanitizePolicy, options: &ParserOptions) {
+    sanitize::sanitize_tokens(tokens, policy, options)
+}
+
+/// Render Html
+///
+/// # Description
+/// Parse the markdown and render it to html
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<String, error::ParserError>
+pub fn render_html(content: &str, options: &ParserOptions) -> Result<String, error::ParserError> {
+    let tokens = token::get_textual_tokens_with_options(content, options)?;
+    let code_tokens = token::get_code_tokens(content)?;
+
+    Ok(render::html::render_html(&tokens, &code_tokens, options))
+}
+
+/// Render Plain Text
+///
+/// # Description
+/// Parse the markdown and render it to plain text for search indexing or previews. When a max length
+/// is given, the text is truncated at a word boundary and ends with an ellipsis
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+/// * `max_length` Option<usize> max number of characters
+///
+/// # Return
+/// Result<String, error::ParserError>
+pub fn render_plain_text(content: &str, options: &ParserOptions, max_length: Option<usize>) -> Result<String, error::ParserError> {
+    let tokens = token::get_textual_tokens_with_options(content, options)?;
+    let code_tokens = token::get_code_tokens(content)?;
+    let text = render::plain::render_plain_text(&tokens, &code_tokens, options);
+
+    Ok(match max_length {
+        Some(max_length) => render::plain::truncate_plain_text(&text, max_length),
+        None => text
+    })
+}
+
+/// Render Terminal
+///
+/// # Description
+/// Parse the markdown and render it for a terminal with ANSI escape sequences, or as wrapped plain
+/// text when the colour are disabled
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+/// * `terminal` &TerminalOptions
+///
+/// # Return
+/// Result<String, error::ParserError>
+pub fn render_terminal(content: &str, options: &ParserOptions, terminal: &TerminalOptions) -> Result<String, error::ParserError> {
+    let tokens = token::get_textual_tokens_with_options(content, options)?;
+    let code_tokens = token::get_code_tokens(content)?;
+
+    Ok(render::terminal::render_terminal(&tokens, &code_tokens, options, terminal))
+}
+
+/// Format Markdown
+///
+/// # Description
+/// Parse the markdown and re-emit it in a canonical style. Formatting an already formatted content
+/// doesn't change it
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+/// * `style` &FormatStyle
+///
+/// # Return
+/// Result<String, error::ParserError>
+pub fn format_markdown(content: &str, options: &ParserOptions, style: &FormatStyle) -> Result<String, error::ParserError> {
+    style.validate()?;
+    let tokens = token::get_textual_tokens_with_options(content, options)?;
+    let code_tokens = token::get_code_tokens(content)?;
+
+    Ok(render::markdown::format_markdown(content, &tokens, &code_tokens, options, style))
+}
+
+/// Render Tokens Html
+///
+/// # Description
+/// Render tokens which have already been parsed i.e after applying a SanitizePolicy
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, token::TextualToken>
+/// * `code_tokens` &BTreeMap<usize, token::code::CodeToken>
+/// * `options` &ParserOptions
+///
+/// # Return
+/// String
+pub fn render_tokens_html(
+    tokens: &BTreeMap<usize, token::TextualToken>,
+    code_tokens: &BTreeMap<usize, token::code::CodeToken>,
+    options: &ParserOptions
+) -> String {
+    render::html::render_html(tokens, code_tokens, options)
+}
+
+/// Visit Tokens
+///
+/// # Description
+/// Walk the parsed markdown with a visitor in the order of the lines
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, token::TextualToken>
+/// * `code_tokens` &BTreeMap<usize, token::code::CodeToken>
+/// * `visitor` &mut V
+pub fn visit_tokens<V: Visitor + ?Sized>(
+    tokens: &BTreeMap<usize, token::TextualToken>,
+    code_tokens: &BTreeMap<usize, token::code::CodeToken>,
+    visitor: &mut V
+) {
+    visit::walk_document(visitor, tokens, code_tokens)
+}
+
+/// Visit Tokens Mut
+///
+/// # Description
+/// Walk the parsed markdown with a visitor updating the tokens in place i.e to rewrite the links. The
+/// options are the ones the markdown has been parsed with, they are used to sanitize the rewritten texts
+///
+/// # Arguments
+/// * `tokens` &mut BTreeMap<usize, token::TextualToken>
+/// * `code_tokens` &mut BTreeMap<usize, token::code::CodeToken>
+/// * `visitor` &mut V
+/// * `options` &ParserOptions
+pub fn visit_tokens_mut<V: VisitorMut + ?Sized>(
+    tokens: &mut BTreeMap<usize, token::TextualToken>,
+    code_tokens: &mut BTreeMap<usize, token::code::CodeToken>,
+    visitor: &mut V,
+    options: &ParserOptions
+) {
+    visit::walk_document_mut(visitor, tokens, code_tokens, options)
+}
+
+/// Fold Tokens
+///
+/// # Description
+/// Rebuild the parsed markdown with a folder which may replace or drop the tokens. The options are
+/// the ones the markdown has been parsed with, they are used to sanitize the rewritten texts
+///
+/// # Arguments
+/// * `tokens` BTreeMap<usize, token::TextualToken>
+/// * `code_tokens` BTreeMap<usize, token::code::CodeToken>
+/// * `folder` &mut F
+/// * `options` &ParserOptions
+///
+/// # Return
+/// (BTreeMap<usize, token::TextualToken>, BTreeMap<usize, token::code::CodeToken>)
+pub fn fold_tokens<'a, F: Fold<'a> + ?Sized>(
+    tokens: BTreeMap<usize, token::TextualToken<'a>>,
+    code_tokens: BTreeMap<usize, token::code::CodeToken<'a>>,
+    folder: &mut F,
+    options: &ParserOptions
+) -> (BTreeMap<usize, token::TextualToken<'a>>, BTreeMap<usize, token::code::CodeToken<'a>>) {
+    visit::fold_document(folder, tokens, code_tokens, options)
+}
+
+/// Table Of Contents
+///
+/// # Description
+/// Build the table of contents of the parsed markdown from the headings between the min and the max level
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, token::TextualToken>
+/// * `min_level` HeadingLevel
+/// * `max_level` HeadingLevel
+///
+/// # Return
+/// Vec<TocEntry>
+pub fn table_of_contents(tokens: &BTreeMap<usize, token::TextualToken>, min_level: HeadingLevel, max_level: HeadingLevel) -> Vec<TocEntry> {
+    toc::table_of_contents(tokens, min_level, max_level, &ParserOptions::default())
+}
+
+/// Table Of Contents With Options
+///
+/// # Description
+/// Build the table of contents of a markdown parsed with the options
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, token::TextualToken>
+/// * `min_level` HeadingLevel
+/// * `max_level` HeadingLevel
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Vec<TocEntry>
+pub fn table_of_contents_with_options(
+    tokens: &BTreeMap<usize, token::TextualToken>,
+    min_level: HeadingLevel,
+    max_level: HeadingLevel,
+    options: &ParserOptions
+) -> Vec<TocEntry> {
+    toc::table_of_contents(tokens, min_level, max_level, options)
+}
+
+/// Render Toc Markdown
+///
+/// # Description
+/// Render a table of contents as a nested markdown list
+///
+/// # Arguments
+/// * `entries` &[TocEntry]
+///
+/// # Return
+/// String
+pub fn render_toc_markdown(entries: &[TocEntry]) -> String {
+    toc::render_toc_markdown(entries)
+}
+
+/// Render Toc Html
+///
+/// # Description
+/// Render a table of contents as a html `<nav>`
+///
+/// # Arguments
+/// * `entries` &[TocEntry]
+///
+/// # Return
+/// String
+pub fn render_toc_html(entries: &[TocEntry]) -> String {
+    toc::render_toc_html(entries)
+}
+
+/// Update Toc
+///
+/// # Description
+/// Regenerate the table of contents between the `<!-- toc -->` and `<!-- tocstop -->` markers of the content
+///
+/// # Arguments
+/// * `content` &str
+/// * `min_level` HeadingLevel
+/// * `max_level` HeadingLevel
+///
+/// # Return
+/// Result<String, error::ParserError>
+pub fn update_toc(content: &str, min_level: HeadingLevel, max_level: HeadingLevel) -> Result<String, error::ParserError> {
+    toc::update_toc(content, min_level, max_level, &ParserOptions::default())
+}
+
+/// Update Toc With Options
+///
+/// # Description
+/// Regenerate the table of contents of the content, the headings and their slugs are parsed with the options
+///
+/// # Arguments
+/// * `content` &str
+/// * `min_level` HeadingLevel
+/// * `max_level` HeadingLevel
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<String, error::ParserError>
+pub fn update_toc_with_options(content: &str, min_level: HeadingLevel, max_level: HeadingLevel, options: &ParserOptions) -> Result<String, error::ParserError> {
+    toc::update_toc(content, min_level, max_level, options)
+}
+
+/// Update Toc File
+///
+/// # Description
+/// Regenerate the table of contents of a markdown file in place
+///
+/// # Arguments
+/// * `path` P
+/// * `min_level` HeadingLevel
+/// * `max_level` HeadingLevel
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<bool, error::ParserError> whether the file has been updated
+pub fn update_toc_file<P: AsRef<Path>>(path: P, min_level: HeadingLevel, max_level: HeadingLevel, options: &ParserOptions) -> Result<bool, error::ParserError> {
+    toc::update_toc_file(path.as_ref(), min_level, max_level, options)
+}
+
+/// Split Sections
+///
+/// # Description
+/// Split the parsed markdown into sections by the headings up to the max level. Each section
+/// hold its heading path, its lines and its raw source
+///
+/// # Arguments
+/// * `content` &str the source of the tokens
+/// * `tokens` &BTreeMap<usize, token::TextualToken>
+/// * `max_level` HeadingLevel
+///
+/// # Return
+/// Vec<Section>
+pub fn split_sections(content: &str, tokens: &BTreeMap<usize, token::TextualToken>, max_level: HeadingLevel) -> Vec<Section> {
+    section::split_sections(content, tokens, max_level)
+}
@@ -0,0 +1,203 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process;
+use xiaomarkdwn::{format_markdown, FormatStyle, OrderedNumbering, ParserOptions};
+
+// Usage of the command line
+const USAGE: &str = "Usage: xiaomarkdwn fmt [--check] [--write] [--bullet <-|*|+>] [--ordered <one|increment>] \
+[--emphasis <*|_>] [--fence <`|~>] [--width <n>] [files...]
+
+Format the markdown files. The content is read from the standard input when no file is given";
+
+/// Fmt Arguments
+///
+/// # Description
+/// Arguments of the fmt subcommand
+#[derive(Debug, Default)]
+struct FmtArguments {
+    // Exit with an error when a file isn't formatted
+    check: bool,
+    // Overwrite the files with the formatted content
+    write: bool,
+    style: FormatStyle,
+    files: Vec<String>
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("fmt") => {},
+        _ => exit_with_usage(None)
+    }
+
+    let arguments = match parse_fmt_arguments(&args[1..]) {
+        Ok(arguments) => arguments,
+        Err(message) => exit_with_usage(Some(&message))
+    };
+
+    process::exit(run_fmt(&arguments));
+}
+
+/// Parse Fmt Arguments
+///
+/// # Description
+/// Parse the arguments following the fmt subcommand
+///
+/// # Arguments
+/// * `args` &[String]
+///
+/// # Return
+/// Result<FmtArguments, String>
+fn parse_fmt_arguments(args: &[String]) -> Result<FmtArguments, String> {
+    let mut arguments = FmtArguments::default();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--check" => arguments.check = true,
+            "--write" => arguments.write = true,
+            "--bullet" => arguments.style.bullet = get_char_value(arg, iter.next())?,
+            "--emphasis" => arguments.style.emphasis = get_char_value(arg, iter.next())?,
+            "--fence" => arguments.style.fence = get_char_value(arg, iter.next())?,
+            "--ordered" => {
+                arguments.style.ordered_numbering = match iter.next().map(String::as_str) {
+                    Some("one") => OrderedNumbering::One,
+                    Some("increment") => OrderedNumbering::Increment,
+                    _ => return Err(format!("{} expects one or increment", arg))
+                };
+            },
+            "--width" => {
+                let width = iter.next()
+                    .and_then(|w| w.parse::<usize>().ok())
+                    .ok_or_else(|| format!("{} expects a number", arg))?;
+
+                arguments.style.wrap_width = Some(width);
+            },
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ => arguments.files.push(arg.clone())
+        }
+    }
+
+    if arguments.write && arguments.files.is_empty() {
+        return Err("--write expects at least one file".to_string());
+    }
+
+    Ok(arguments)
+}
+
+/// Get Char Value
+///
+/// # Description
+/// Get the single character value of an option
+///
+/// # Arguments
+/// * `option` &str
+/// * `value` Option<&String>
+///
+/// # Return
+/// Result<char, String>
+fn get_char_value(option: &str, value: Option<&String>) -> Result<char, String> {
+    let mut chars = value.map(|v| v.chars()).ok_or_else(|| format!("{} expects a character", option))?;
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!("{} expects a single character", option))
+    }
+}
+
+/// Run Fmt
+///
+/// # Description
+/// Format the files or the standard input. The formatted content is printed unless
+/// the files are checked or overwritten
+///
+/// # Arguments
+/// * `arguments` &FmtArguments
+///
+/// # Return
+/// i32 exit code
+fn run_fmt(arguments: &FmtArguments) -> i32 {
+    let options = ParserOptions::default();
+    let sources: Vec<Option<&String>> = if arguments.files.is_empty() {
+        vec![None]
+    } else {
+        arguments.files.iter().map(Some).collect()
+    };
+
+    let mut code = 0;
+    for source in sources {
+        let name = source.map_or("<stdin>", String::as_str);
+        let content = match read_source(source) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("{}: {}", name, err);
+                code = 2;
+                continue;
+            }
+        };
+
+        let formatted = match format_markdown(&content, &options, &arguments.style) {
+            Ok(formatted) => formatted,
+            Err(err) => {
+                eprintln!("{}: {}", name, err);
+                code = 2;
+                continue;
+            }
+        };
+
+        if arguments.check {
+            if formatted != content {
+                println!("{}", name);
+                code = code.max(1);
+            }
+        } else if let (true, Some(path)) = (arguments.write, source) {
+            if formatted != content {
+                if let Err(err) = fs::write(path, formatted) {
+                    eprintln!("{}: {}", name, err);
+                    code = 2;
+                }
+            }
+        } else {
+            print!("{}", formatted);
+        }
+    }
+
+    code
+}
+
+/// Read Source
+///
+/// # Description
+/// Read the file or the standard input when no file is given
+///
+/// # Arguments
+/// * `source` Option<&String>
+///
+/// # Return
+/// io::Result<String>
+fn read_source(source: Option<&String>) -> io::Result<String> {
+    match source {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)?;
+            Ok(content)
+        }
+    }
+}
+
+/// Exit With Usage
+///
+/// # Description
+/// Print the usage with an optional error message and exit
+///
+/// # Arguments
+/// * `message` Option<&str>
+fn exit_with_usage(message: Option<&str>) -> ! {
+    if let Some(message) = message {
+        eprintln!("{}\n", message);
+    }
+
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
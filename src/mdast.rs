@@ -0,0 +1,1498 @@
+use std::clone::Clone;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+use serde::{Deserialize, Deserializer, Serialize};
+use crate::error;
+use crate::options::ParserOptions;
+use crate::parser::code::{CodeOperator, get_info_string};
+use crate::parser::directive::{DirectiveKind, DirectiveMeta, format_directive};
+use crate::parser::front_matter::{FrontMatterKind, get_front_matter};
+use crate::parser::github::IssueRef;
+use crate::parser::heading::HeadingLevel;
+use crate::parser::inline::{InlineNode, get_inline_nodes};
+use crate::parser::table::TableAlignment;
+use crate::render::html::get_heading_number;
+use crate::render::markdown::{format_code_span, format_inline_math, format_link_title};
+use crate::section::get_line_offsets;
+use crate::token::{self, TextualToken};
+use crate::token::code::CodeToken;
+use crate::token::tag::{TagToken, TagOperator};
+
+// Characters of a text escaped when writing the markdown
+const ESCAPED_CHARACTERS: &str = "\\`*_[]<~$";
+// Indentation of the continuation paragraphs of a footnote definition
+const CONTINUATION_INDENT: &str = "    ";
+// Indentation of the items of a nested list
+const NESTED_LIST_INDENT: &str = "  ";
+// Fence of the innermost directive container
+const CONTAINER_FENCE: usize = 3;
+
+/// Point
+///
+/// # Description
+/// Place in the source. The line and the column start at 1
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Point {
+    pub line: usize,
+    pub column: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>
+}
+
+/// Position
+///
+/// # Description
+/// Range of the source covered by a node
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub start: Point,
+    pub end: Point
+}
+
+/// Mdast Node
+///
+/// # Description
+/// Node of a mdast tree as produced by unified / remark
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct MdastNode {
+    #[serde(flatten)]
+    pub kind: MdastKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Position>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<MdastData>
+}
+
+/// Mdast Data
+///
+/// # Description
+/// Extra information of a node, only the label of the directives is supported
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct MdastData {
+    // The paragraph is the label of its container directive
+    #[serde(rename = "directiveLabel", default)]
+    pub directive_label: bool
+}
+
+/// Mdast Kind
+///
+/// # Description
+/// Type of a mdast node with its fields. Types which aren't supported are read as `Unknown` and skipped
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MdastKind {
+    Root {
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    // Front matter of remark-frontmatter
+    Yaml {
+        value: String
+    },
+    Toml {
+        value: String
+    },
+    Heading {
+        depth: usize,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    Paragraph {
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    Blockquote {
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    List {
+        ordered: bool,
+        start: Option<usize>,
+        #[serde(default)]
+        spread: bool,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    ListItem {
+        #[serde(default)]
+        spread: bool,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    Code {
+        lang: Option<String>,
+        meta: Option<String>,
+        value: String
+    },
+    Math {
+        value: String
+    },
+    Html {
+        value: String
+    },
+    Table {
+        // left, right, center or null for each column
+        #[serde(default)]
+        align: Vec<Option<String>>,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    TableRow {
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    TableCell {
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    FootnoteDefinition {
+        identifier: String,
+        label: Option<String>,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    Text {
+        value: String
+    },
+    Emphasis {
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    Strong {
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    Delete {
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    InlineCode {
+        value: String
+    },
+    InlineMath {
+        value: String
+    },
+    Break,
+    Link {
+        url: String,
+        title: Option<String>,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    Image {
+        url: String,
+        title: Option<String>,
+        alt: Option<String>
+    },
+    FootnoteReference {
+        identifier: String,
+        label: Option<String>
+    },
+    // References are only read, they are written as the link of their definition
+    LinkReference {
+        identifier: String,
+        label: Option<String>,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    ImageReference {
+        identifier: String,
+        label: Option<String>,
+        alt: Option<String>
+    },
+    Definition {
+        identifier: String,
+        label: Option<String>,
+        url: String,
+        title: Option<String>
+    },
+    // Directives of remark-directive, the label of a container is its first paragraph
+    ContainerDirective {
+        name: String,
+        #[serde(default, deserialize_with = "deserialize_attributes")]
+        attributes: BTreeMap<String, String>,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    LeafDirective {
+        name: String,
+        #[serde(default, deserialize_with = "deserialize_attributes")]
+        attributes: BTreeMap<String, String>,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    TextDirective {
+        name: String,
+        #[serde(default, deserialize_with = "deserialize_attributes")]
+        attributes: BTreeMap<String, String>,
+        #[serde(default)]
+        children: Vec<MdastNode>
+    },
+    #[serde(other)]
+    Unknown
+}
+
+impl MdastNode {
+    /// New
+    ///
+    /// # Description
+    /// Create a node without position
+    ///
+    /// # Arguments
+    /// * `kind` MdastKind
+    ///
+    /// # Return
+    /// MdastNode
+    pub fn new(kind: MdastKind) -> Self {
+        MdastNode {
+            kind,
+            position: None,
+            data: None
+        }
+    }
+}
+
+/// Mdast Builder
+///
+/// Keep track of the blocks which are opened while walking through the lines
+struct MdastBuilder<'a> {
+    options: &'a ParserOptions,
+    lines: Vec<&'a str>,
+    offsets: Vec<Range<usize>>,
+    children: Vec<MdastNode>,
+    // Line index and content of the lines of the paragraph being built
+    paragraph: Vec<(usize, String)>,
+    blockquote: Vec<(usize, String)>,
+    // Line index, kind and content of the items of the list being built, nested items included
+    list: Vec<(usize, TagOperator, String)>,
+    // Line index of the fence, info string and lines of the code block being built
+    code: Option<(usize, String, Vec<String>)>,
+    // Line index, metas and parent blocks of the directive containers being built
+    directives: Vec<(usize, DirectiveMeta, Vec<MdastNode>)>
+}
+
+/// Get Mdast
+///
+/// # Description
+/// Convert the parsed markdown into a mdast tree. The blocks hold the position of their lines
+///
+/// # Arguments
+/// * `content` &str
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `code_tokens` &BTreeMap<usize, CodeToken>
+/// * `options` &ParserOptions
+///
+/// # Return
+/// MdastNode
+pub fn get_mdast(
+    content: &str,
+    tokens: &BTreeMap<usize, TextualToken>,
+    code_tokens: &BTreeMap<usize, CodeToken>,
+    options: &ParserOptions
+) -> MdastNode {
+    let mut builder = MdastBuilder {
+        options,
+        lines: content.lines().collect(),
+        offsets: get_line_offsets(content),
+        children: Vec::new(),
+        paragraph: Vec::new(),
+        blockquote: Vec::new(),
+        list: Vec::new(),
+        code: None,
+        directives: Vec::new()
+    };
+
+    if let Some(front_matter) = get_front_matter(content) {
+        let value = front_matter.raw.trim_end_matches('\n').to_string();
+        let kind = match front_matter.kind {
+            FrontMatterKind::Yaml => MdastKind::Yaml { value },
+            FrontMatterKind::Toml => MdastKind::Toml { value }
+        };
+
+        builder.push_node(kind, front_matter.start_line, front_matter.end_line);
+    }
+
+    let lines: BTreeSet<usize> = tokens.keys().chain(code_tokens.keys()).copied().collect();
+    // A ```math fence is exported as a math node instead of a code block
+    let mut in_math_fence = false;
+
+    for idx in lines {
+        let textual = tokens.get(&idx);
+        let code = code_tokens.get(&idx);
+
+        if let Some(tag) = textual.and_then(|t| t.tag.as_ref()).filter(|t| t.operator == TagOperator::DisplayMath) {
+            builder.add_tag(tag, idx);
+            in_math_fence = code.is_some_and(|c| c.operator == CodeOperator::BlockCodeStart);
+            continue;
+        }
+
+        if let Some(code) = code {
+            if in_math_fence {
+                in_math_fence = code.operator != CodeOperator::BlockCodeEnd;
+                continue;
+            }
+
+            builder.add_code(code, idx);
+            continue;
+        }
+
+        if let Some(textual) = textual {
+            builder.add_textual(textual, idx);
+        }
+    }
+
+    builder.close_blocks();
+    builder.close_code(None);
+    while !builder.directives.is_empty() {
+        builder.close_directive(builder.lines.len().saturating_sub(1));
+    }
+
+    let position = builder.get_position(0, builder.lines.len().saturating_sub(1));
+    MdastNode {
+        kind: MdastKind::Root { children: builder.children },
+        position,
+        data: None
+    }
+}
+
+impl<'a> MdastBuilder<'a> {
+    /// Add Code
+    ///
+    /// # Description
+    /// Gather the lines of a code block
+    ///
+    /// # Arguments
+    /// * `code` &CodeToken
+    /// * `idx` usize
+    fn add_code(&mut self, code: &CodeToken, idx: usize) {
+        let line = self.lines.get(idx).copied().unwrap_or_default();
+        match code.operator {
+            CodeOperator::BlockCodeStart => {
+                self.close_blocks();
+                let info = get_info_string(line).to_string();
+                self.code = Some((idx, info, Vec::new()));
+            },
+            CodeOperator::BlockCodeContent => {
+                if let Some((start, _, lines)) = self.code.as_mut() {
+                    // the indentation of the fence is removed from the content
+                    let fence_indent = get_indent(self.lines.get(*start).copied().unwrap_or_default());
+                    let indent = get_indent(line).min(fence_indent);
+                    lines.push(line[indent..].to_string());
+                }
+            },
+            CodeOperator::BlockCodeEnd => self.close_code(Some(idx))
+        }
+    }
+
+    /// Add Textual
+    ///
+    /// # Description
+    /// Add a textual token. Consecutive lines of text, list items and blockquotes are grouped
+    ///
+    /// # Arguments
+    /// * `textual` &TextualToken
+    /// * `idx` usize
+    fn add_textual(&mut self, textual: &TextualToken, idx: usize) {
+        if let Some(tag) = &textual.tag {
+            self.add_tag(tag, idx);
+            return;
+        }
+
+        let text = match &textual.text {
+            Some(text) => text,
+            None => return
+        };
+
+        if text.content.is_empty() {
+            self.close_blocks();
+            return;
+        }
+
+        self.close_list();
+        self.close_blockquote();
+        self.paragraph.push((idx, text.content.to_string()));
+    }
+
+    /// Add Tag
+    ///
+    /// # Description
+    /// Add a tag token
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    /// * `idx` usize
+    fn add_tag(&mut self, tag: &TagToken, idx: usize) {
+        match tag.operator {
+            TagOperator::Heading => {
+                self.close_blocks();
+                let level = tag.metas.as_ref().map_or(HeadingLevel::H1, |m| m.heading_kind.clone());
+                let children = self.get_inline(&[(idx, tag.content.to_string())]);
+                self.push_node(MdastKind::Heading { depth: get_heading_number(&level), children }, idx, idx);
+            },
+            TagOperator::UnorderedList | TagOperator::OrderedList => {
+                self.close_paragraph();
+                self.close_blockquote();
+                // an item of another kind only starts a new list at the indentation of the list
+                let indent = get_indent(self.lines.get(idx).copied().unwrap_or_default());
+                let other_list = self.list
+                    .first()
+                    .is_some_and(|(start, operator, _)| *operator != tag.operator && indent <= get_indent(self.lines[*start]));
+
+                if other_list {
+                    self.close_list();
+                }
+
+                self.list.push((idx, tag.operator.clone(), tag.content.to_string()));
+            },
+            TagOperator::BlockQuote => {
+                self.close_paragraph();
+                self.close_list();
+                self.blockquote.push((idx, tag.content.to_string()));
+            },
+            TagOperator::FootnoteDefinition => {
+                self.close_blocks();
+                let footnote = tag.metas.as_ref().and_then(|m| m.footnote.clone());
+                let label = footnote.as_ref().map(|f| f.label.clone()).unwrap_or_default();
+                let paragraphs = footnote.map(|f| f.paragraphs).filter(|p| !p.is_empty()).unwrap_or_else(|| vec![tag.content.to_string()]);
+                let children = paragraphs
+                    .iter()
+                    .map(|p| MdastNode::new(MdastKind::Paragraph { children: self.get_inline(&[(idx, p.clone())]) }))
+                    .collect();
+
+                let kind = MdastKind::FootnoteDefinition {
+                    identifier: label.to_lowercase(),
+                    label: Some(label),
+                    children
+                };
+
+                let end = self.get_block_end(idx);
+                self.push_node(kind, idx, end);
+            },
+            TagOperator::HtmlBlock => {
+                self.close_blocks();
+                // the lines of the block are part of its content
+                let end = self.get_block_end(idx).max(idx + tag.content.lines().count().saturating_sub(1));
+                self.push_node(MdastKind::Html { value: tag.content.to_string() }, idx, end);
+            },
+            TagOperator::ContainerDirective => {
+                self.close_blocks();
+                let directive = tag.metas.as_ref().and_then(|m| m.directive.clone()).unwrap_or_default();
+                let parent = std::mem::take(&mut self.children);
+                self.directives.push((idx, directive, parent));
+
+                if !tag.content.is_empty() {
+                    let children = self.get_inline(&[(idx, tag.content.to_string())]);
+                    self.push_node(MdastKind::Paragraph { children }, idx, idx);
+                    if let Some(label) = self.children.last_mut() {
+                        label.data = Some(MdastData { directive_label: true });
+                    }
+                }
+            },
+            TagOperator::ContainerDirectiveEnd => {
+                self.close_blocks();
+                self.close_code(None);
+                self.close_directive(idx);
+            },
+            TagOperator::LeafDirective => {
+                self.close_blocks();
+                let directive = tag.metas.as_ref().and_then(|m| m.directive.clone()).unwrap_or_default();
+                let kind = MdastKind::LeafDirective {
+                    name: directive.name,
+                    attributes: directive.attributes,
+                    children: self.get_inline(&[(idx, tag.content.to_string())])
+                };
+
+                self.push_node(kind, idx, idx);
+            },
+            TagOperator::Custom(ref node) => {
+                self.close_blocks();
+                let end = idx + tag.content.lines().count().saturating_sub(1);
+                self.push_node(MdastKind::Html { value: node.render_html() }, idx, end);
+            },
+            TagOperator::DisplayMath => {
+                self.close_blocks();
+                let end = self.get_block_end(idx);
+                self.push_node(MdastKind::Math { value: tag.content.trim().to_string() }, idx, end);
+            },
+            // mdast has no definition list, the terms and the definitions are exported as paragraphs
+            TagOperator::DefinitionList => {
+                self.close_blocks();
+                let items = tag.metas.as_ref().and_then(|m| m.definition_list.as_ref()).map(|l| l.items.clone());
+                for item in items.unwrap_or_default() {
+                    let term = self.get_inline(&[(idx, item.term)]);
+                    self.children.push(MdastNode::new(MdastKind::Paragraph { children: term }));
+
+                    for definition in item.definitions {
+                        let definition = self.get_inline(&[(idx, definition)]);
+                        self.children.push(MdastNode::new(MdastKind::Paragraph { children: definition }));
+                    }
+                }
+            },
+            TagOperator::Table => {
+                self.close_blocks();
+                let table = match tag.metas.as_ref().and_then(|m| m.table.clone()) {
+                    Some(table) => table,
+                    None => return
+                };
+
+                let align = table.alignments
+                    .iter()
+                    .map(|alignment| match alignment {
+                        TableAlignment::Left => Some(String::from("left")),
+                        TableAlignment::Center => Some(String::from("center")),
+                        TableAlignment::Right => Some(String::from("right")),
+                        TableAlignment::None => None
+                    })
+                    .collect();
+
+                // the delimiter row isn't part of the children
+                let end = idx + table.rows.len() + 1;
+                let children = std::iter::once(&table.header)
+                    .chain(&table.rows)
+                    .map(|row| {
+                        let cells = row
+                            .iter()
+                            .map(|cell| MdastNode::new(MdastKind::TableCell { children: self.get_inline(&[(idx, cell.clone())]) }))
+                            .collect();
+
+                        MdastNode::new(MdastKind::TableRow { children: cells })
+                    })
+                    .collect();
+
+                self.push_node(MdastKind::Table { align, children }, idx, end);
+            },
+            // only used to annotate the text
+            TagOperator::AbbreviationDefinition => {}
+        }
+    }
+
+    /// Get Inline
+    ///
+    /// # Description
+    /// Convert the lines of a block into phrasing nodes. The lines are separated by a line ending
+    ///
+    /// # Arguments
+    /// * `lines` &[(usize, String)]
+    ///
+    /// # Return
+    /// Vec<MdastNode>
+    fn get_inline(&self, lines: &[(usize, String)]) -> Vec<MdastNode> {
+        let mut children = Vec::new();
+        for (pos, (_, content)) in lines.iter().enumerate() {
+            if pos > 0 {
+                children.push(MdastNode::new(MdastKind::Text { value: String::from("\n") }));
+            }
+
+            children.extend(self.get_phrasing(&get_inline_nodes(content, self.options)));
+        }
+
+        merge_text(children)
+    }
+
+    /// Get Phrasing
+    ///
+    /// # Description
+    /// Convert the inline nodes into phrasing nodes
+    ///
+    /// # Arguments
+    /// * `nodes` &[InlineNode]
+    ///
+    /// # Return
+    /// Vec<MdastNode>
+    fn get_phrasing(&self, nodes: &[InlineNode]) -> Vec<MdastNode> {
+        nodes
+            .iter()
+            .map(|node| {
+                let kind = match node {
+                    InlineNode::Text(value) => MdastKind::Text { value: value.clone() },
+                    InlineNode::Strong(children) => MdastKind::Strong { children: self.get_phrasing(children) },
+                    InlineNode::Emphasis(children) => MdastKind::Emphasis { children: self.get_phrasing(children) },
+                    InlineNode::Strike(children) => MdastKind::Delete { children: self.get_phrasing(children) },
+                    InlineNode::Code(value) => MdastKind::InlineCode { value: value.clone() },
+                    InlineNode::Link { title, url, tooltip } => MdastKind::Link {
+                        url: url.clone(),
+                        title: tooltip.clone(),
+                        children: merge_text(self.get_phrasing(title))
+                    },
+                    InlineNode::Image { alt_text, url, tooltip } => MdastKind::Image {
+                        url: url.clone(),
+                        title: tooltip.clone(),
+                        alt: Some(alt_text.clone())
+                    },
+                    InlineNode::FootnoteRef(label) => MdastKind::FootnoteReference {
+                        identifier: label.to_lowercase(),
+                        label: Some(label.clone())
+                    },
+                    InlineNode::Html(value) => MdastKind::Html { value: value.clone() },
+                    // mdast has no custom node, the rendered html is kept instead
+                    InlineNode::TextDirective { directive, label } => MdastKind::TextDirective {
+                        name: directive.name.clone(),
+                        attributes: directive.attributes.clone(),
+                        children: merge_text(self.get_phrasing(label))
+                    },
+                    InlineNode::Custom(node) => MdastKind::Html { value: node.render_html() },
+                    InlineNode::InlineMath(value) => MdastKind::InlineMath { value: value.clone() },
+                    InlineNode::Emoji { unicode, .. } => MdastKind::Text { value: unicode.clone() },
+                    InlineNode::Mention(user) => self.get_reference_link(
+                        format!("@{}", user),
+                        self.options.github.as_ref().map(|github| github.get_mention_url(user))
+                    ),
+                    InlineNode::IssueRef { repository, number } => {
+                        let issue = IssueRef { repository: repository.clone(), number: *number, col: None };
+                        self.get_reference_link(
+                            format!("{}#{}", repository.as_deref().unwrap_or(""), number),
+                            self.options.github.as_ref().and_then(|github| github.get_issue_url(&issue))
+                        )
+                    }
+                };
+
+                MdastNode::new(kind)
+            })
+            .collect()
+    }
+
+    /// Get Reference Link
+    ///
+    /// # Description
+    /// Get a link to a mention or an issue, or a text when the url is unknown
+    ///
+    /// # Arguments
+    /// * `text` String
+    /// * `url` Option<String>
+    ///
+    /// # Return
+    /// MdastKind
+    fn get_reference_link(&self, text: String, url: Option<String>) -> MdastKind {
+        match url {
+            Some(url) => MdastKind::Link {
+                url,
+                title: None,
+                children: vec![MdastNode::new(MdastKind::Text { value: text })]
+            },
+            None => MdastKind::Text { value: text }
+        }
+    }
+
+    /// Get Position
+    ///
+    /// # Description
+    /// Get the position from the first non whitespace character of the start line to the end of the end line
+    ///
+    /// # Arguments
+    /// * `start` usize
+    /// * `end` usize
+    ///
+    /// # Return
+    /// Option<Position>
+    fn get_position(&self, start: usize, end: usize) -> Option<Position> {
+        let start_line = self.lines.get(start)?;
+        let end_line = self.lines.get(end)?;
+        let indent = get_indent(start_line);
+
+        Some(Position {
+            start: Point {
+                line: start + 1,
+                column: start_line[..indent].chars().count() + 1,
+                offset: Some(self.offsets[start].start + indent)
+            },
+            end: Point {
+                line: end + 1,
+                column: end_line.chars().count() + 1,
+                offset: Some(self.offsets[end].end)
+            }
+        })
+    }
+
+    /// Get Block End
+    ///
+    /// # Description
+    /// Get the last line of a block which may be continued on the following lines i.e html block,
+    /// footnote definition. The continuation lines aren't part of the tokens
+    ///
+    /// # Arguments
+    /// * `start` usize
+    ///
+    /// # Return
+    /// usize
+    fn get_block_end(&self, start: usize) -> usize {
+        let mut end = start;
+        while end + 1 < self.lines.len() && !self.lines[end + 1].trim().is_empty() && get_indent(self.lines[end + 1]) > 0 {
+            end += 1;
+        }
+
+        end
+    }
+
+    /// Push Node
+    ///
+    /// # Description
+    /// Push a block spanning from the start line to the end line
+    ///
+    /// # Arguments
+    /// * `kind` MdastKind
+    /// * `start` usize
+    /// * `end` usize
+    fn push_node(&mut self, kind: MdastKind, start: usize, end: usize) {
+        let position = self.get_position(start, end);
+        self.children.push(MdastNode { kind, position, data: None });
+    }
+
+    /// Close Blocks
+    ///
+    /// # Description
+    /// Close the paragraph, the list and the blockquote which are opened
+    fn close_blocks(&mut self) {
+        self.close_paragraph();
+        self.close_list();
+        self.close_blockquote();
+    }
+
+    /// Close Directive
+    ///
+    /// # Description
+    /// Push the innermost directive container into its parent
+    ///
+    /// # Arguments
+    /// * `end` usize line of the closing fence
+    fn close_directive(&mut self, end: usize) {
+        let (start, directive, parent) = match self.directives.pop() {
+            Some(container) => container,
+            None => return
+        };
+
+        let children = std::mem::replace(&mut self.children, parent);
+        let kind = MdastKind::ContainerDirective {
+            name: directive.name,
+            attributes: directive.attributes,
+            children
+        };
+
+        self.push_node(kind, start, end);
+    }
+
+    /// Close Paragraph
+    ///
+    /// # Description
+    /// Push the paragraph being built
+    fn close_paragraph(&mut self) {
+        let lines = std::mem::take(&mut self.paragraph);
+        if let (Some((start, _)), Some((end, _))) = (lines.first(), lines.last()) {
+            let children = self.get_inline(&lines);
+            self.push_node(MdastKind::Paragraph { children }, *start, *end);
+        }
+    }
+
+    /// Close List
+    ///
+    /// # Description
+    /// Push the list being built. Each item holds a paragraph
+    fn close_list(&mut self) {
+        let items = std::mem::take(&mut self.list);
+        let lists = self.get_lists(&items);
+        self.children.extend(lists);
+    }
+
+    /// Get Lists
+    ///
+    /// # Description
+    /// Build the lists of the items. The items indented deeper than the previous item are nested
+    /// within it and an item of another kind at the same indentation starts a new list
+    ///
+    /// # Arguments
+    /// * `items` &[(usize, TagOperator, String)]
+    ///
+    /// # Return
+    /// Vec<MdastNode>
+    fn get_lists(&self, items: &[(usize, TagOperator, String)]) -> Vec<MdastNode> {
+        let mut lists = Vec::new();
+        let mut pos = 0;
+
+        while let Some((start, operator, _)) = items.get(pos) {
+            let indent = get_indent(self.lines[*start]);
+            let mut children = Vec::new();
+
+            while let Some((idx, item_operator, content)) = items.get(pos) {
+                if get_indent(self.lines[*idx]) <= indent && item_operator != operator {
+                    break;
+                }
+
+                // the nested items end at the next item of the list
+                let nested_end = items[pos + 1..]
+                    .iter()
+                    .position(|(line, _, _)| get_indent(self.lines[*line]) <= indent)
+                    .map_or(items.len(), |end| pos + 1 + end);
+
+                let mut item_children = vec![
+                    MdastNode::new(MdastKind::Paragraph { children: self.get_inline(&[(*idx, content.clone())]) })
+                ];
+
+                item_children.extend(self.get_lists(&items[pos + 1..nested_end]));
+                children.push(MdastNode {
+                    kind: MdastKind::ListItem { spread: false, children: item_children },
+                    position: self.get_position(*idx, items[nested_end - 1].0),
+                    data: None
+                });
+
+                pos = nested_end;
+            }
+
+            let ordered = *operator == TagOperator::OrderedList;
+            let number = self.lines[*start]
+                .trim_start()
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect::<String>()
+                .parse::<usize>()
+                .ok();
+
+            let kind = MdastKind::List {
+                ordered,
+                start: if ordered { number.or(Some(1)) } else { None },
+                spread: false,
+                children
+            };
+
+            lists.push(MdastNode {
+                kind,
+                position: self.get_position(*start, items[pos - 1].0),
+                data: None
+            });
+        }
+
+        lists
+    }
+
+    /// Close Blockquote
+    ///
+    /// # Description
+    /// Push the blockquote being built with its lines as a paragraph
+    fn close_blockquote(&mut self) {
+        let lines = std::mem::take(&mut self.blockquote);
+        if let (Some((start, _)), Some((end, _))) = (lines.first(), lines.last()) {
+            let paragraph = MdastNode::new(MdastKind::Paragraph { children: self.get_inline(&lines) });
+            self.push_node(MdastKind::Blockquote { children: vec![paragraph] }, *start, *end);
+        }
+    }
+
+    /// Close Code
+    ///
+    /// # Description
+    /// Push the code block being built. The info string is split into the language and the meta
+    ///
+    /// # Arguments
+    /// * `end` Option<usize> line of the closing fence, None for a block left open
+    fn close_code(&mut self, end: Option<usize>) {
+        let (start, info, lines) = match self.code.take() {
+            Some(code) => code,
+            None => return
+        };
+
+        let (lang, meta) = match info.split_once(char::is_whitespace) {
+            Some((lang, meta)) => (lang.to_string(), Some(meta.trim().to_string())),
+            None => (info, None)
+        };
+
+        let kind = MdastKind::Code {
+            lang: Some(lang).filter(|l| !l.is_empty()),
+            meta: meta.filter(|m| !m.is_empty()),
+            value: lines.join("\n")
+        };
+
+        let end = end.unwrap_or(start + lines.len());
+        self.push_node(kind, start, end);
+    }
+}
+
+/// Get Markdown
+///
+/// # Description
+/// Write the mdast tree as markdown. The blocks are separated by a blank line and the references
+/// are written as the link of their definition
+///
+/// # Arguments
+/// * `node` &MdastNode
+///
+/// # Return
+/// String
+pub fn get_markdown(node: &MdastNode) -> String {
+    let mut nodes = vec![node.clone()];
+    let mut definitions = BTreeMap::new();
+    get_definitions(&mut nodes, &mut definitions);
+    resolve_references(&mut nodes, &definitions);
+
+    let mut markdown = join_blocks(&nodes).join("\n");
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+/// Get Block Lines
+///
+/// # Description
+/// Write a block as lines of markdown
+///
+/// # Arguments
+/// * `node` &MdastNode
+///
+/// # Return
+/// Vec<String>
+fn get_block_lines(node: &MdastNode) -> Vec<String> {
+    match &node.kind {
+        MdastKind::Root { children } => join_blocks(children),
+        MdastKind::Yaml { value } => vec![String::from("---"), value.clone(), String::from("---")],
+        MdastKind::Toml { value } => vec![String::from("+++"), value.clone(), String::from("+++")],
+        MdastKind::Heading { depth, children } => {
+            let text = get_phrasing_markdown(children).replace('\n', " ");
+            vec![format!("{} {}", "#".repeat((*depth).clamp(1, 6)), text)]
+        },
+        MdastKind::Paragraph { children } => get_phrasing_markdown(children)
+            .split('\n')
+            .map(escape_line_start)
+            .collect(),
+        MdastKind::Blockquote { children } => children
+            .iter()
+            .flat_map(get_block_lines)
+            .filter(|line| !line.is_empty())
+            .map(|line| format!("> {}", line))
+            .collect(),
+        MdastKind::List { ordered, start, children, .. } => get_list_lines(*ordered, start.unwrap_or(1), children),
+        MdastKind::ListItem { children, .. } => get_list_lines(false, 1, &[MdastNode::new(MdastKind::ListItem {
+            spread: false,
+            children: children.clone()
+        })]),
+        MdastKind::Code { lang, meta, value } => {
+            let fence = if value.contains("```") { "~~~" } else { "```" };
+            let info = [lang.as_deref(), meta.as_deref()]
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<&str>>()
+                .join(" ");
+
+            let mut lines = vec![format!("{}{}", fence, info)];
+            lines.extend(value.lines().map(String::from));
+            lines.push(fence.to_string());
+            lines
+        },
+        MdastKind::Math { value } => {
+            let mut lines = vec![String::from("$$")];
+            lines.extend(value.lines().map(String::from));
+            lines.push(String::from("$$"));
+            lines
+        },
+        MdastKind::Html { value } => value.lines().map(String::from).collect(),
+        MdastKind::Table { align, children } => get_table_lines(align, children),
+        MdastKind::FootnoteDefinition { identifier, label, children } => {
+            let label = label.as_ref().unwrap_or(identifier);
+            let mut lines = Vec::new();
+            for (pos, child) in children.iter().enumerate() {
+                if pos > 0 {
+                    lines.push(String::new());
+                }
+
+                for line in get_block_lines(child) {
+                    if lines.is_empty() {
+                        lines.push(format!("[^{}]: {}", label, line));
+                    } else if line.is_empty() {
+                        lines.push(line);
+                    } else {
+                        lines.push(format!("{}{}", CONTINUATION_INDENT, line));
+                    }
+                }
+            }
+
+            if lines.is_empty() {
+                lines.push(format!("[^{}]:", label));
+            }
+
+            lines
+        },
+        MdastKind::ContainerDirective { name, attributes, children } => {
+            let (label, body) = match children.first() {
+                Some(first) if first.data.as_ref().is_some_and(|d| d.directive_label) => (Some(first), &children[1..]),
+                _ => (None, children.as_slice())
+            };
+
+            // a nested container needs a shorter fence than its parent
+            let fence = CONTAINER_FENCE + get_directive_depth(body);
+            let directive = DirectiveMeta {
+                kind: DirectiveKind::Container,
+                name: name.clone(),
+                label: label.map(|l| get_phrasing_markdown(get_children(l)).replace('\n', " ")),
+                attributes: attributes.clone(),
+                fence
+            };
+
+            let mut lines = vec![format_directive(&directive)];
+            lines.extend(join_blocks(body));
+            lines.push(":".repeat(fence));
+            lines
+        },
+        MdastKind::LeafDirective { name, attributes, children } => {
+            let directive = DirectiveMeta {
+                kind: DirectiveKind::Leaf,
+                name: name.clone(),
+                label: Some(get_phrasing_markdown(children).replace('\n', " ")).filter(|l| !l.is_empty()),
+                attributes: attributes.clone(),
+                fence: 2
+            };
+
+            vec![format_directive(&directive)]
+        },
+        MdastKind::Definition { identifier, label, url, title } => {
+            vec![format!("[{}]: {}{}", label.as_ref().unwrap_or(identifier), url, format_link_title(title))]
+        },
+        MdastKind::TableRow { .. } | MdastKind::TableCell { .. } => get_table_lines(&[], std::slice::from_ref(node)),
+        MdastKind::Unknown => Vec::new(),
+        // phrasing content outside of a paragraph
+        _ => get_phrasing_markdown(std::slice::from_ref(node))
+            .split('\n')
+            .map(escape_line_start)
+            .collect()
+    }
+}
+
+/// Get Definitions
+///
+/// # Description
+/// Gather the url and the title of the definitions of the tree by identifier
+///
+/// # Arguments
+/// * `nodes` &mut [MdastNode]
+/// * `definitions` &mut BTreeMap<String, (String, Option<String>)>
+fn get_definitions(nodes: &mut [MdastNode], definitions: &mut BTreeMap<String, (String, Option<String>)>) {
+    for node in nodes {
+        if let MdastKind::Definition { identifier, url, title, .. } = &node.kind {
+            // the first definition of an identifier wins
+            definitions
+                .entry(identifier.to_lowercase())
+                .or_insert_with(|| (url.clone(), title.clone()));
+        }
+
+        if let Some(children) = get_node_children_mut(&mut node.kind) {
+            get_definitions(children, definitions);
+        }
+    }
+}
+
+/// Resolve References
+///
+/// # Description
+/// Replace the references by the link or the image of their definition. A reference without
+/// definition is replaced by its children, or its alternative text for an image
+///
+/// # Arguments
+/// * `nodes` &mut Vec<MdastNode>
+/// * `definitions` &BTreeMap<String, (String, Option<String>)>
+fn resolve_references(nodes: &mut Vec<MdastNode>, definitions: &BTreeMap<String, (String, Option<String>)>) {
+    for mut node in std::mem::take(nodes) {
+        let definition = match &node.kind {
+            MdastKind::LinkReference { identifier, .. } | MdastKind::ImageReference { identifier, .. } => {
+                definitions.get(&identifier.to_lowercase()).cloned()
+            },
+            _ => None
+        };
+
+        node.kind = match (node.kind, definition) {
+            (MdastKind::LinkReference { children, .. }, Some((url, title))) => MdastKind::Link { url, title, children },
+            (MdastKind::LinkReference { mut children, .. }, None) => {
+                resolve_references(&mut children, definitions);
+                nodes.extend(children);
+                continue;
+            },
+            (MdastKind::ImageReference { alt, .. }, Some((url, title))) => MdastKind::Image { url, title, alt },
+            (MdastKind::ImageReference { alt, .. }, None) => MdastKind::Text { value: alt.unwrap_or_default() },
+            (kind, _) => kind
+        };
+
+        if let Some(children) = get_node_children_mut(&mut node.kind) {
+            resolve_references(children, definitions);
+        }
+
+        nodes.push(node);
+    }
+}
+
+/// Get Node Children Mut
+///
+/// # Description
+/// Get the children of a node which can hold some
+///
+/// # Arguments
+/// * `kind` &mut MdastKind
+///
+/// # Return
+/// Option<&mut Vec<MdastNode>>
+fn get_node_children_mut(kind: &mut MdastKind) -> Option<&mut Vec<MdastNode>> {
+    match kind {
+        MdastKind::Root { children }
+        | MdastKind::Heading { children, .. }
+        | MdastKind::Paragraph { children }
+        | MdastKind::Blockquote { children }
+        | MdastKind::List { children, .. }
+        | MdastKind::ListItem { children, .. }
+        | MdastKind::Table { children, .. }
+        | MdastKind::TableRow { children }
+        | MdastKind::TableCell { children }
+        | MdastKind::FootnoteDefinition { children, .. }
+        | MdastKind::Emphasis { children }
+        | MdastKind::Strong { children }
+        | MdastKind::Delete { children }
+        | MdastKind::Link { children, .. }
+        | MdastKind::LinkReference { children, .. }
+        | MdastKind::ContainerDirective { children, .. }
+        | MdastKind::LeafDirective { children, .. }
+        | MdastKind::TextDirective { children, .. } => Some(children),
+        _ => None
+    }
+}
+
+/// Deserialize Attributes
+///
+/// # Description
+/// Read the attributes of a directive. remark-directive may set an attribute to null, it is read
+/// as an empty value
+///
+/// # Arguments
+/// * `deserializer` D
+///
+/// # Return
+/// Result<BTreeMap<String, String>, D::Error>
+fn deserialize_attributes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error> {
+    let attributes = Option::<BTreeMap<String, Option<String>>>::deserialize(deserializer)?;
+
+    Ok(attributes
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| (key, value.unwrap_or_default()))
+        .collect())
+}
+
+/// Join Blocks
+///
+/// # Description
+/// Write the blocks separated by a blank line. Consecutive phrasing nodes are written as a paragraph
+///
+/// # Arguments
+/// * `children` &[MdastNode]
+///
+/// # Return
+/// Vec<String>
+fn join_blocks(children: &[MdastNode]) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for child in children {
+        let block = get_block_lines(child);
+        if block.is_empty() {
+            continue;
+        }
+
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+
+        lines.extend(block);
+    }
+
+    lines
+}
+
+/// Get List Lines
+///
+/// # Description
+/// Write the items of a list. The blocks of an item are joined on the line of the item and the
+/// nested lists follow the item, indented deeper than its marker
+///
+/// # Arguments
+/// * `ordered` bool
+/// * `start` usize
+/// * `children` &[MdastNode]
+///
+/// # Return
+/// Vec<String>
+fn get_list_lines(ordered: bool, start: usize, children: &[MdastNode]) -> Vec<String> {
+    let mut lines = Vec::new();
+    // ordered markers only support a single digit
+    let single_digit = start + children.len() <= 10;
+
+    for (pos, item) in children.iter().enumerate() {
+        let item_children = match &item.kind {
+            MdastKind::ListItem { children, .. } => children.as_slice(),
+            _ => std::slice::from_ref(item)
+        };
+
+        let marker = match (ordered, single_digit) {
+            (false, _) => String::from("-."),
+            (true, true) => format!("{}.", start.max(1) + pos),
+            (true, false) => String::from("1.")
+        };
+
+        let mut text = Vec::new();
+        let mut nested = Vec::new();
+        for child in item_children {
+            match child.kind {
+                MdastKind::List { .. } => nested.extend(
+                    get_block_lines(child)
+                        .into_iter()
+                        .filter(|l| !l.is_empty())
+                        .map(|l| format!("{}{}", NESTED_LIST_INDENT, l))
+                ),
+                _ => text.extend(get_block_lines(child).into_iter().filter(|l| !l.is_empty()))
+            }
+        }
+
+        lines.push(format!("{} {}", marker, text.join(" ")).trim_end().to_string());
+        lines.extend(nested);
+    }
+
+    lines
+}
+
+/// Get Table Lines
+///
+/// # Description
+/// Write the rows of a table. The delimiter row follows the first row
+///
+/// # Arguments
+/// * `align` &[Option<String>]
+/// * `rows` &[MdastNode]
+///
+/// # Return
+/// Vec<String>
+fn get_table_lines(align: &[Option<String>], rows: &[MdastNode]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (pos, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = match &row.kind {
+            MdastKind::TableRow { children } => children
+                .iter()
+                .map(|cell| match &cell.kind {
+                    MdastKind::TableCell { children } => get_phrasing_markdown(children).replace('|', "\\|"),
+                    _ => get_phrasing_markdown(std::slice::from_ref(cell))
+                })
+                .collect(),
+            MdastKind::TableCell { children } => vec![get_phrasing_markdown(children)],
+            _ => continue
+        };
+
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if pos == 0 {
+            let delimiters: Vec<&str> = (0..cells.len())
+                .map(|col| match align.get(col).and_then(|a| a.as_deref()) {
+                    Some("left") => ":---",
+                    Some("right") => "---:",
+                    Some("center") => ":---:",
+                    _ => "---"
+                })
+                .collect();
+
+            lines.push(format!("| {} |", delimiters.join(" | ")));
+        }
+    }
+
+    lines
+}
+
+/// Get Phrasing Markdown
+///
+/// # Description
+/// Write the phrasing nodes as inline markdown
+///
+/// # Arguments
+/// * `nodes` &[MdastNode]
+///
+/// # Return
+/// String
+fn get_phrasing_markdown(nodes: &[MdastNode]) -> String {
+    nodes
+        .iter()
+        .map(|node| match &node.kind {
+            MdastKind::Text { value } => escape_text(value),
+            MdastKind::Emphasis { children } => format!("*{}*", get_phrasing_markdown(children)),
+            MdastKind::Strong { children } => format!("**{}**", get_phrasing_markdown(children)),
+            MdastKind::Delete { children } => format!("~~{}~~", get_phrasing_markdown(children)),
+            MdastKind::InlineCode { value } => format_code_span(value),
+            MdastKind::InlineMath { value } => format_inline_math(value),
+            MdastKind::Break => String::from("\n"),
+            MdastKind::Link { url, title, children } => {
+                format!("[{}]({}{})", get_phrasing_markdown(children), url, format_link_title(title))
+            },
+            MdastKind::Image { url, title, alt } => {
+                format!("![{}]({}{})", escape_text(alt.as_deref().unwrap_or("")), url, format_link_title(title))
+            },
+            MdastKind::FootnoteReference { identifier, label } => format!("[^{}]", label.as_ref().unwrap_or(identifier)),
+            MdastKind::Html { value } => value.clone(),
+            MdastKind::TextDirective { name, attributes, children } => format_directive(&DirectiveMeta {
+                kind: DirectiveKind::Text,
+                name: name.clone(),
+                label: Some(get_phrasing_markdown(children)),
+                attributes: attributes.clone(),
+                fence: 1
+            }),
+            _ => String::new()
+        })
+        .collect()
+}
+
+/// Get Children
+///
+/// # Description
+/// Get the children of a paragraph
+///
+/// # Arguments
+/// * `node` &MdastNode
+///
+/// # Return
+/// &[MdastNode]
+fn get_children(node: &MdastNode) -> &[MdastNode] {
+    match &node.kind {
+        MdastKind::Paragraph { children } => children,
+        _ => std::slice::from_ref(node)
+    }
+}
+
+/// Get Directive Depth
+///
+/// # Description
+/// Get the number of nested directive containers within the blocks
+///
+/// # Arguments
+/// * `children` &[MdastNode]
+///
+/// # Return
+/// usize
+fn get_directive_depth(children: &[MdastNode]) -> usize {
+    children
+        .iter()
+        .map(|child| match &child.kind {
+            MdastKind::ContainerDirective { children, .. } => 1 + get_directive_depth(children),
+            _ => 0
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Escape Text
+///
+/// # Description
+/// Escape the characters of a text which could be parsed as markup
+///
+/// # Arguments
+/// * `text` &str
+///
+/// # Return
+/// String
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if ESCAPED_CHARACTERS.contains(c) {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// Escape Line Start
+///
+/// # Description
+/// Escape the start of a line of text which would be parsed as a block i.e heading, list, blockquote
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// String
+fn escape_line_start(line: &str) -> String {
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 && line[digits..].starts_with('.') {
+        return format!("{}\\{}", &line[..digits], &line[digits..]);
+    }
+
+    let is_block = line.starts_with(['#', '>', '+'])
+        || line.starts_with("-.")
+        || line.starts_with(": ");
+
+    if is_block {
+        return format!("\\{}", line);
+    }
+
+    line.to_string()
+}
+
+/// Merge Text
+///
+/// # Description
+/// Merge the adjacent text nodes
+///
+/// # Arguments
+/// * `nodes` Vec<MdastNode>
+///
+/// # Return
+/// Vec<MdastNode>
+fn merge_text(nodes: Vec<MdastNode>) -> Vec<MdastNode> {
+    let mut merged: Vec<MdastNode> = Vec::new();
+    for node in nodes {
+        if let (Some(MdastNode { kind: MdastKind::Text { value: last }, .. }), MdastKind::Text { value }) = (merged.last_mut(), &node.kind) {
+            last.push_str(value);
+            continue;
+        }
+
+        merged.push(node);
+    }
+
+    merged
+}
+
+/// Get Indent
+///
+/// # Description
+/// Return the number of leading whitespace of a line
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// usize
+fn get_indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Parse Mdast Json
+///
+/// # Description
+/// Read a mdast tree from json
+///
+/// # Arguments
+/// * `json` &str
+///
+/// # Return
+/// Result<MdastNode, error::ParserError>
+pub fn parse_mdast_json(json: &str) -> Result<MdastNode, error::ParserError> {
+    serde_json::from_str(json).map_err(|err| error::ParserError {
+        message: format!("{}: {}", error::MDAST_INVALID, err)
+    })
+}
+
+/// Get Mdast Json
+///
+/// # Description
+/// Parse the markdown and serialize its mdast tree to json
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<String, error::ParserError>
+pub fn get_mdast_json(content: &str, options: &ParserOptions) -> Result<String, error::ParserError> {
+    let tokens = token::get_textual_tokens_with_options(content, options)?;
+    let code_tokens = token::get_code_tokens(content)?;
+    let mdast = get_mdast(content, &tokens, &code_tokens, options);
+
+    serde_json::to_string(&mdast).map_err(|err| error::ParserError {
+        message: format!("{}: {}", error::JSON_SERIALIZE, err)
+    })
+}
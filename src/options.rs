@@ -0,0 +1,321 @@
+use std::default::Default;
+use std::clone::Clone;
+use std::sync::Arc;
+use crate::parser::github::GithubOptions;
+use crate::parser::extension::{BlockParserExt, InlineParserExt};
+use crate::parser::slug::Slugger;
+
+/// Parser Options
+///
+/// # Description
+/// Options used to configure the syntaxes handled by the parser. The options are built either with
+/// the struct fields, the chainable setters or one of the `commonmark` / `gfm` presets
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    // Drop the html blocks and the inline html instead of preserving them
+    pub drop_html: bool,
+    // Parse the ~~strikethrough~~
+    pub strikethrough: bool,
+    // Parse the [^label] footnote references and the [^label]: definitions
+    pub footnotes: bool,
+    // Parse the | a | b | tables
+    pub tables: bool,
+    // Parse the :::container, ::leaf and :text directives
+    pub directives: bool,
+    // Parse the inline $math$ and the display $$math$$ / ```math blocks
+    pub math: bool,
+    // Replace the "quotes", the -- / --- dashes and the ... ellipsis of the text by their typographic characters
+    pub smart_punctuation: bool,
+    // Parse the :emoji: shortcodes, the @mentions and the #123 issue references
+    pub github: Option<GithubOptions>,
+    // Slugger used for the heading anchors. Default to the GitHub one
+    pub slugger: Option<Arc<dyn Slugger>>,
+    // Custom block syntaxes, tried in order before the built-in blocks
+    pub block_parsers: Vec<Arc<dyn BlockParserExt>>,
+    // Custom inline syntaxes, tried in order before the built-in inline syntaxes
+    pub inline_parsers: Vec<Arc<dyn InlineParserExt>>,
+    // Parse the `Term` / `: definition` lists. Ignored without the `definition_list` feature
+    pub definition_list: bool,
+    // Parse the `*[HTML]: Hyper Text Markup Language` abbreviations. Ignored without the `abbreviation` feature
+    pub abbreviation: bool
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            drop_html: false,
+            strikethrough: true,
+            footnotes: true,
+            tables: false,
+            directives: false,
+            math: false,
+            smart_punctuation: false,
+            github: None,
+            slugger: None,
+            block_parsers: Vec::new(),
+            inline_parsers: Vec::new(),
+            definition_list: false,
+            abbreviation: false
+        }
+    }
+}
+
+impl ParserOptions {
+    /// New
+    ///
+    /// # Description
+    /// Create the default options: the strikethrough and the footnotes are enabled, the other
+    /// extensions are opt-in
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn new() -> Self {
+        ParserOptions::default()
+    }
+
+    /// Commonmark
+    ///
+    /// # Description
+    /// Create the options of the CommonMark dialect, every extension is disabled
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn commonmark() -> Self {
+        ParserOptions::new()
+            .strikethrough(false)
+            .footnotes(false)
+    }
+
+    /// Gfm
+    ///
+    /// # Description
+    /// Create the options of the GitHub Flavored Markdown dialect: CommonMark with the tables, the
+    /// strikethrough and the footnotes
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn gfm() -> Self {
+        ParserOptions::commonmark()
+            .tables(true)
+            .strikethrough(true)
+            .footnotes(true)
+    }
+
+    /// Drop Html
+    ///
+    /// # Description
+    /// Drop the html blocks and the inline html
+    ///
+    /// # Arguments
+    /// * `enabled` bool
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn drop_html(mut self, enabled: bool) -> Self {
+        self.drop_html = enabled;
+        self
+    }
+
+    /// Strikethrough
+    ///
+    /// # Description
+    /// Enable the ~~strikethrough~~
+    ///
+    /// # Arguments
+    /// * `enabled` bool
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn strikethrough(mut self, enabled: bool) -> Self {
+        self.strikethrough = enabled;
+        self
+    }
+
+    /// Footnotes
+    ///
+    /// # Description
+    /// Enable the footnote references and definitions
+    ///
+    /// # Arguments
+    /// * `enabled` bool
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn footnotes(mut self, enabled: bool) -> Self {
+        self.footnotes = enabled;
+        self
+    }
+
+    /// Tables
+    ///
+    /// # Description
+    /// Enable the tables
+    ///
+    /// # Arguments
+    /// * `enabled` bool
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn tables(mut self, enabled: bool) -> Self {
+        self.tables = enabled;
+        self
+    }
+
+    /// Directives
+    ///
+    /// # Description
+    /// Enable the container, leaf and text directives
+    ///
+    /// # Arguments
+    /// * `enabled` bool
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn directives(mut self, enabled: bool) -> Self {
+        self.directives = enabled;
+        self
+    }
+
+    /// Math
+    ///
+    /// # Description
+    /// Enable the inline and the display math
+    ///
+    /// # Arguments
+    /// * `enabled` bool
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn math(mut self, enabled: bool) -> Self {
+        self.math = enabled;
+        self
+    }
+
+    /// Smart Punctuation
+    ///
+    /// # Description
+    /// Enable the typographic quotes, dashes and ellipsis within the text. The code spans, the code
+    /// blocks and the urls are kept as is
+    ///
+    /// # Arguments
+    /// * `enabled` bool
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn smart_punctuation(mut self, enabled: bool) -> Self {
+        self.smart_punctuation = enabled;
+        self
+    }
+
+    /// Github
+    ///
+    /// # Description
+    /// Enable the GitHub extensions, None disable them
+    ///
+    /// # Arguments
+    /// * `github` Option<GithubOptions>
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn github(mut self, github: Option<GithubOptions>) -> Self {
+        self.github = github;
+        self
+    }
+
+    /// Slugger
+    ///
+    /// # Description
+    /// Set the slugger of the heading anchors
+    ///
+    /// # Arguments
+    /// * `slugger` Arc<dyn Slugger>
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn slugger(mut self, slugger: Arc<dyn Slugger>) -> Self {
+        self.slugger = Some(slugger);
+        self
+    }
+
+    /// Block Parser
+    ///
+    /// # Description
+    /// Register a custom block syntax
+    ///
+    /// # Arguments
+    /// * `parser` Arc<dyn BlockParserExt>
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn block_parser(mut self, parser: Arc<dyn BlockParserExt>) -> Self {
+        self.block_parsers.push(parser);
+        self
+    }
+
+    /// Inline Parser
+    ///
+    /// # Description
+    /// Register a custom inline syntax
+    ///
+    /// # Arguments
+    /// * `parser` Arc<dyn InlineParserExt>
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn inline_parser(mut self, parser: Arc<dyn InlineParserExt>) -> Self {
+        self.inline_parsers.push(parser);
+        self
+    }
+
+    /// Definition List
+    ///
+    /// # Description
+    /// Enable the definition lists, ignored without the `definition_list` feature
+    ///
+    /// # Arguments
+    /// * `enabled` bool
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn definition_list(mut self, enabled: bool) -> Self {
+        self.definition_list = enabled;
+        self
+    }
+
+    /// Abbreviation
+    ///
+    /// # Description
+    /// Enable the abbreviations, ignored without the `abbreviation` feature
+    ///
+    /// # Arguments
+    /// * `enabled` bool
+    ///
+    /// # Return
+    /// ParserOptions
+    pub fn abbreviation(mut self, enabled: bool) -> Self {
+        self.abbreviation = enabled;
+        self
+    }
+
+    /// Has Definition List
+    ///
+    /// # Description
+    /// Check whether the definition lists are enabled. Always false without the `definition_list` feature
+    ///
+    /// # Return
+    /// bool
+    pub(crate) fn has_definition_list(&self) -> bool {
+        cfg!(feature = "definition_list") && self.definition_list
+    }
+
+    /// Has Abbreviation
+    ///
+    /// # Description
+    /// Check whether the abbreviations are enabled. Always false without the `abbreviation` feature
+    ///
+    /// # Return
+    /// bool
+    pub(crate) fn has_abbreviation(&self) -> bool {
+        cfg!(feature = "abbreviation") && self.abbreviation
+    }
+}
@@ -0,0 +1,208 @@
+use std::borrow::Cow;
+use std::clone::Clone;
+use std::collections::BTreeMap;
+use regex::Regex;
+use lazy_static::lazy_static;
+use crate::token::TextualToken;
+use crate::token::tag::{TagToken, TagOperator, TagMeta};
+use super::{html, inline};
+use super::text_style::mask_code_spans;
+
+lazy_static!{
+    static ref ABBREVIATION_DEF_RE: Regex = Regex::new(r"^\*\[([^\]]+)\]:\s*(.*)$").unwrap();
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbbreviationMeta {
+    pub abbr: String,
+    pub title: String
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Abbreviation {
+    pub abbr: String,
+    pub title: String,
+    pub col: Option<usize>
+}
+
+/// Get Abbreviation Definition
+///
+/// # Description
+/// Get the abbreviation definition i.e `*[HTML]: Hyper Text Markup Language` of a line
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// Option<TagToken>
+pub fn get_abbreviation_definition(line: &str) -> Option<TagToken<'_>> {
+    let captures = ABBREVIATION_DEF_RE.captures(line)?;
+    let abbr = captures[1].trim();
+    let title = captures.get(2).map_or("", |m| m.as_str()).trim();
+    if abbr.is_empty() {
+        return None;
+    }
+
+    Some(
+        TagToken {
+            operator: TagOperator::AbbreviationDefinition,
+            content: Cow::Borrowed(title),
+            metas: Some(
+                TagMeta {
+                    abbreviation: Some(AbbreviationMeta {
+                        abbr: abbr.to_string(),
+                        title: title.to_string()
+                    }),
+                    ..Default::default()
+                }
+            ),
+            ..Default::default()
+        }
+    )
+}
+
+/// Get Abbreviations
+///
+/// # Description
+/// Get the occurences of the defined abbreviations in the content. Only whole words are matched
+/// and the longest abbreviation win i.e HTML5 over HTML. The inline code, the raw html and the
+/// link destinations are skipped
+///
+/// # Arguments
+/// * `content` &str
+/// * `definitions` &[AbbreviationMeta]
+///
+/// # Return
+/// Option<Vec<Abbreviation>>
+pub fn get_abbreviations(content: &str, definitions: &[AbbreviationMeta]) -> Option<Vec<Abbreviation>> {
+    let mut sorted: Vec<&AbbreviationMeta> = definitions.iter().collect();
+    sorted.sort_by_key(|def| std::cmp::Reverse(def.abbr.len()));
+
+    let masked = mask_markup(content);
+    let mut abbreviations = Vec::new();
+    let mut idx = 0;
+
+    while idx < masked.len() {
+        let matched = if is_word_boundary(masked[..idx].chars().last()) {
+            sorted.iter().find(|def| {
+                masked[idx..].starts_with(def.abbr.as_str())
+                    && is_word_boundary(masked[idx + def.abbr.len()..].chars().next())
+            })
+        } else {
+            None
+        };
+
+        match matched {
+            Some(def) => {
+                abbreviations.push(Abbreviation {
+                    abbr: def.abbr.clone(),
+                    title: def.title.clone(),
+                    col: Some(idx)
+                });
+                idx += def.abbr.len();
+            },
+            None => idx += masked[idx..].chars().next().map_or(1, |c| c.len_utf8())
+        }
+    }
+
+    if abbreviations.is_empty() {
+        return None;
+    }
+
+    Some(abbreviations)
+}
+
+/// Get Abbreviation Definitions
+///
+/// # Description
+/// Collect the abbreviation definitions of the document. The last definition of an abbreviation win
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+///
+/// # Return
+/// Vec<AbbreviationMeta>
+pub fn get_abbreviation_definitions(tokens: &BTreeMap<usize, TextualToken>) -> Vec<AbbreviationMeta> {
+    let mut definitions: Vec<AbbreviationMeta> = Vec::new();
+    let metas = tokens
+        .values()
+        .filter_map(|t| t.tag.as_ref())
+        .filter_map(|t| t.metas.as_ref())
+        .filter_map(|m| m.abbreviation.as_ref());
+
+    for meta in metas {
+        definitions.retain(|def| def.abbr != meta.abbr);
+        definitions.push(meta.clone());
+    }
+
+    definitions
+}
+
+/// Set Abbreviations
+///
+/// # Description
+/// Annotate the text tokens with the abbreviations defined anywhere in the document
+///
+/// # Arguments
+/// * `tokens` &mut BTreeMap<usize, TextualToken>
+pub fn set_abbreviations(tokens: &mut BTreeMap<usize, TextualToken>) {
+    let definitions = get_abbreviation_definitions(tokens);
+    if definitions.is_empty() {
+        return;
+    }
+
+    for text in tokens.values_mut().filter_map(|t| t.text.as_mut()) {
+        if let Some(metas) = text.metas.as_mut() {
+            metas.abbreviations = get_abbreviations(&text.content, &definitions);
+        }
+    }
+}
+
+/// Mask Markup
+///
+/// # Description
+/// Replace the inline code, the raw html and the link destinations by whitespaces. The length of
+/// the content is kept so that the column of the abbreviations aren't shifted
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// String
+fn mask_markup(content: &str) -> String {
+    let mut masked = mask_code_spans(content);
+    let mut ranges: Vec<(usize, usize)> = html::get_inline_html(&masked)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|html| html.col.map(|col| (col, col + html.word.len())))
+        .collect();
+
+    for (idx, _) in masked.match_indices('[') {
+        if let Some((label, _, len)) = inline::match_link(&masked[idx..]) {
+            // the label is kept, the destination starts after the ](
+            ranges.push((idx + label.len() + 3, idx + len - 1));
+        }
+    }
+
+    for (start, end) in ranges {
+        masked.replace_range(start..end, &" ".repeat(end - start));
+    }
+
+    masked
+}
+
+/// Is Word Boundary
+///
+/// # Description
+/// Check that the character surrounding an abbreviation isn't part of a word
+///
+/// # Arguments
+/// * `c` Option<char>
+///
+/// # Return
+/// bool
+fn is_word_boundary(c: Option<char>) -> bool {
+    !c.is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
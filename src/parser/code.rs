@@ -1,29 +1,42 @@
 use std::default::Default;
 use std::clone::Clone;
-use lazy_static::lazy_static;
-use regex::Regex;
 use crate::token::code::{CodeToken};
 
-lazy_static!{
-    static ref BLOCK_CODE: Regex = Regex::new(r"(```[a-z]*)").unwrap();
+// Minimum length of a fence
+const FENCE_MIN_LENGTH: usize = 3;
+// Maximum indentation of a closing fence relative to the opening one
+const FENCE_MAX_INDENT: usize = 3;
+
+/// Code Fence
+///
+/// Fence opening a code block i.e ```` or ~~~
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodeFence {
+    pub character: char,
+    pub length: usize,
+    // Indentation of the fence
+    pub indent: usize
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum CodeOperator {
+    #[default]
     BlockCodeStart,
     BlockCodeContent,
     BlockCodeEnd
 }
 
-impl Default for CodeOperator {
-    fn default() -> Self { CodeOperator::BlockCodeStart }
-}
-
 /// Get Block Code Operator
 ///
 /// # Description
-/// Return a block of code token
-/// 
+/// Return the operator of a code block line. A block is opened by a line starting with a fence of
+/// at least three ` or ~. It is closed by a fence of the same character, at least as long as the
+/// opening one and indented by at most three spaces more i.e a ``` doesn't close a ````
+///
 /// # Arguments
 /// * `&str` content
 /// * `&Option<Token>` previous_token
@@ -31,34 +44,77 @@ impl Default for CodeOperator {
 /// # Return
 /// Option<Token>
 pub fn get_block_code_operator(content: &str, previous_token: &Option<CodeToken>) -> Option<CodeOperator> {
-    let matches = BLOCK_CODE.is_match(content);
+    let opening = previous_token
+        .as_ref()
+        .filter(|token| token.operator != CodeOperator::BlockCodeEnd)
+        .and_then(|token| token.fence.as_ref());
 
-    if matches && previous_token.is_none() {
-        return Some(CodeOperator::BlockCodeStart);
+    match opening {
+        Some(fence) if is_closing_fence(content, fence) => Some(CodeOperator::BlockCodeEnd),
+        Some(_) => Some(CodeOperator::BlockCodeContent),
+        None => get_fence(content).map(|_| CodeOperator::BlockCodeStart)
     }
+}
 
-    if let Some(ptoken) = previous_token {
-        // Match if we detect a new block code
-        if (ptoken.operator == CodeOperator::BlockCodeContent || ptoken.operator == CodeOperator::BlockCodeStart) && !matches {
-            return Some(CodeOperator::BlockCodeContent);
-        }
+/// Get Fence
+///
+/// # Description
+/// Get the fence opening a code block
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<CodeFence>
+pub fn get_fence(content: &str) -> Option<CodeFence> {
+    let (fence, rest) = get_fence_run(content)?;
+    // the info string of a backtick fence can't contain a backtick
+    if fence.character == '`' && rest.contains('`') {
+        return None;
+    }
 
-        if matches && ptoken.operator == CodeOperator::BlockCodeContent {
-            return Some(CodeOperator::BlockCodeEnd);
-        }
+    Some(fence)
+}
 
-        if matches {
-            return Some(CodeOperator::BlockCodeStart);
-        }
-    }
+/// Get Info String
+///
+/// # Description
+/// Get the info string following the opening fence of a code block. Only the characters of the
+/// fence are trimmed i.e the info string of ~~~ ``` is ```
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// &str
+pub fn get_info_string(content: &str) -> &str {
+    get_fence_run(content).map_or("", |(_, rest)| rest.trim())
+}
 
-    None
+/// Is Closing Fence
+///
+/// # Description
+/// Check whether the line closes the code block opened by the fence
+///
+/// # Arguments
+/// * `content` &str
+/// * `opening` &CodeFence
+///
+/// # Return
+/// bool
+pub fn is_closing_fence(content: &str, opening: &CodeFence) -> bool {
+    get_fence_run(content).is_some_and(|(fence, rest)| {
+        fence.character == opening.character
+            && fence.length >= opening.length
+            && fence.indent <= opening.indent + FENCE_MAX_INDENT
+            && rest.trim().is_empty()
+    })
 }
 
 /// Is Code
 ///
 /// # Description
-/// Return if the content is a code line
+/// Return if the content is a code fence
 ///
 /// # Argument
 /// * `content` &str
@@ -66,5 +122,33 @@ pub fn get_block_code_operator(content: &str, previous_token: &Option<CodeToken>
 /// # Return
 /// bool
 pub fn is_code(content: &str) -> bool {
-    BLOCK_CODE.is_match(content)
-} 
\ No newline at end of file
+    get_fence(content).is_some()
+}
+
+/// Get Fence Run
+///
+/// # Description
+/// Get the run of ` or ~ starting the line after its indentation
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<(CodeFence, &str)> the fence and the rest of the line
+fn get_fence_run(content: &str) -> Option<(CodeFence, &str)> {
+    let body = content.trim_start();
+    let character = body.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let rest = body.trim_start_matches(character);
+    let length = body.len() - rest.len();
+    if length < FENCE_MIN_LENGTH {
+        return None;
+    }
+
+    let fence = CodeFence {
+        character,
+        length,
+        indent: content.len() - body.len()
+    };
+
+    Some((fence, rest))
+}
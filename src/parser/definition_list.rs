@@ -0,0 +1,146 @@
+use std::borrow::Cow;
+use std::clone::Clone;
+use crate::token::tag::{TagToken, TagOperator, TagMeta};
+
+// Marker of a definition i.e `: definition`
+const DEFINITION_MARKER: char = ':';
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefinitionItem {
+    pub term: String,
+    pub definitions: Vec<String>
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefinitionListMeta {
+    pub items: Vec<DefinitionItem>
+}
+
+/// Get Definition
+///
+/// # Description
+/// Get the definition of a line i.e `: definition`. The marker must be followed by a whitespace
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// Option<&str>
+pub fn get_definition(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix(DEFINITION_MARKER)?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let definition = rest.trim();
+    if definition.is_empty() {
+        return None;
+    }
+
+    Some(definition)
+}
+
+/// Get Definition List Token
+///
+/// # Description
+/// Create a definition list starting with the term and its first definition
+///
+/// # Arguments
+/// * `term` &str
+/// * `definition` &str
+///
+/// # Return
+/// TagToken<'static>
+pub fn get_definition_list_token(term: &str, definition: &str) -> TagToken<'static> {
+    TagToken {
+        operator: TagOperator::DefinitionList,
+        content: Cow::Owned(format!("{}\n{} {}", term, DEFINITION_MARKER, definition)),
+        metas: Some(
+            TagMeta {
+                definition_list: Some(DefinitionListMeta {
+                    items: vec![DefinitionItem {
+                        term: term.to_string(),
+                        definitions: vec![definition.to_string()]
+                    }]
+                }),
+                ..Default::default()
+            }
+        ),
+        ..Default::default()
+    }
+}
+
+/// Append Term
+///
+/// # Description
+/// Append a new term and its first definition to the definition list
+///
+/// # Arguments
+/// * `token` &mut TagToken
+/// * `term` &str
+/// * `definition` &str
+pub fn append_term(token: &mut TagToken, term: &str, definition: &str) {
+    token.content.to_mut().push_str(&format!("\n{}\n{} {}", term, DEFINITION_MARKER, definition));
+
+    if let Some(list) = get_list_meta(token) {
+        list.items.push(DefinitionItem {
+            term: term.to_string(),
+            definitions: vec![definition.to_string()]
+        });
+    }
+}
+
+/// Append Definition
+///
+/// # Description
+/// Append a definition to the last term of the definition list
+///
+/// # Arguments
+/// * `token` &mut TagToken
+/// * `definition` &str
+pub fn append_definition(token: &mut TagToken, definition: &str) {
+    token.content.to_mut().push_str(&format!("\n{} {}", DEFINITION_MARKER, definition));
+
+    if let Some(item) = get_list_meta(token).and_then(|l| l.items.last_mut()) {
+        item.definitions.push(definition.to_string());
+    }
+}
+
+/// Append Definition Line
+///
+/// # Description
+/// Append an indented continuation line to the last definition
+///
+/// # Arguments
+/// * `token` &mut TagToken
+/// * `line` &str
+pub fn append_definition_line(token: &mut TagToken, line: &str) {
+    let line = line.trim();
+    token.content.to_mut().push('\n');
+    token.content.to_mut().push_str(line);
+
+    let definition = get_list_meta(token)
+        .and_then(|l| l.items.last_mut())
+        .and_then(|i| i.definitions.last_mut());
+
+    if let Some(definition) = definition {
+        definition.push('\n');
+        definition.push_str(line);
+    }
+}
+
+/// Get List Meta
+///
+/// # Description
+/// Get the definition list meta of the token
+///
+/// # Arguments
+/// * `token` &mut TagToken
+///
+/// # Return
+/// Option<&mut DefinitionListMeta>
+fn get_list_meta<'t>(token: &'t mut TagToken) -> Option<&'t mut DefinitionListMeta> {
+    token.metas.as_mut().and_then(|m| m.definition_list.as_mut())
+}
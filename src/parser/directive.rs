@@ -0,0 +1,434 @@
+use std::borrow::Cow;
+use std::clone::Clone;
+use std::collections::BTreeMap;
+use crate::token::tag::{TagToken, TagOperator, TagMeta};
+use super::inline::find_closing;
+
+// Marker of the directives i.e :::tip / ::video / :abbr
+const DIRECTIVE_MARKER: char = ':';
+// Minimum number of colons of a container fence
+const MIN_CONTAINER_FENCE: usize = 3;
+// Number of colons of a leaf directive
+const LEAF_FENCE: usize = 2;
+
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DirectiveKind {
+    // :::name ... ::: wrapping blocks
+    #[default]
+    Container,
+    // ::name on its own line
+    Leaf,
+    // :name within a text
+    Text
+}
+
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectiveMeta {
+    pub kind: DirectiveKind,
+    pub name: String,
+    pub label: Option<String>,
+    // The {#id} and {.class} shortcuts are stored as the id and class attributes
+    pub attributes: BTreeMap<String, String>,
+    // Number of colons of the fence of a container
+    pub fence: usize
+}
+
+/// Get Container Start
+///
+/// # Description
+/// Get the opening fence of a container i.e `:::tip[Title]{#id}`. The label can also follow the
+/// name after a space as in `::: tip Title`
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// Option<(DirectiveMeta, &str)> the directive and its label
+pub fn get_container_start(line: &str) -> Option<(DirectiveMeta, &str)> {
+    let line = line.trim();
+    let fence = get_fence_length(line);
+    if fence < MIN_CONTAINER_FENCE {
+        return None;
+    }
+
+    let rest = line[fence..].trim_start();
+    let (name, rest) = split_name(rest)?;
+
+    // ::: tip Title {.wide}
+    let (label, attributes) = match rest.chars().next() {
+        Some('[') | Some('{') | None => parse_label_and_attributes(rest)?,
+        Some(c) if c.is_whitespace() => {
+            let rest = rest.trim();
+            match rest.strip_suffix('}').and_then(|r| r.rfind('{').map(|start| (start, r))) {
+                Some((start, attributes)) => (rest[..start].trim(), parse_attributes(&attributes[start + 1..])?),
+                None => (rest, BTreeMap::new())
+            }
+        },
+        _ => return None
+    };
+
+    let meta = DirectiveMeta {
+        kind: DirectiveKind::Container,
+        name: name.to_string(),
+        label: Some(label.to_string()).filter(|l| !l.is_empty()),
+        attributes,
+        fence
+    };
+
+    Some((meta, label))
+}
+
+/// Get Container End
+///
+/// # Description
+/// Get the number of colons of a closing fence i.e `:::`
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// Option<usize>
+pub fn get_container_end(line: &str) -> Option<usize> {
+    let line = line.trim();
+    let fence = get_fence_length(line);
+    if fence < MIN_CONTAINER_FENCE || fence != line.len() {
+        return None;
+    }
+
+    Some(fence)
+}
+
+/// Get Leaf Directive
+///
+/// # Description
+/// Get a leaf directive i.e `::youtube[Video]{id=123}` standing on its own line
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// Option<(DirectiveMeta, &str)> the directive and its label
+pub fn get_leaf_directive(line: &str) -> Option<(DirectiveMeta, &str)> {
+    let line = line.trim();
+    if get_fence_length(line) != LEAF_FENCE {
+        return None;
+    }
+
+    let (name, rest) = split_name(&line[LEAF_FENCE..])?;
+    let (label, attributes) = parse_label_and_attributes(rest)?;
+
+    let meta = DirectiveMeta {
+        kind: DirectiveKind::Leaf,
+        name: name.to_string(),
+        label: Some(label.to_string()).filter(|l| !l.is_empty()),
+        attributes,
+        fence: LEAF_FENCE
+    };
+
+    Some((meta, label))
+}
+
+/// Get Text Directive At
+///
+/// # Description
+/// Match a text directive i.e `:abbr[HTML]{title="..."}` at the index of the content. The name must be
+/// followed by a label or attributes so that the colons of the text aren't mistaken for a directive
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+///
+/// # Return
+/// Option<(DirectiveMeta, usize)> the directive and the length of the match
+pub fn get_text_directive_at(content: &str, idx: usize) -> Option<(DirectiveMeta, usize)> {
+    let previous = content[..idx].chars().next_back();
+    if previous.is_some_and(|c| c.is_alphanumeric() || c == DIRECTIVE_MARKER) {
+        return None;
+    }
+
+    let rest = content[idx..].strip_prefix(DIRECTIVE_MARKER)?;
+    let (name, after_name) = split_name(rest)?;
+    if !after_name.starts_with(['[', '{']) {
+        return None;
+    }
+
+    let mut end = 0;
+    let mut label = None;
+    if after_name.starts_with('[') {
+        let closing = find_closing(after_name, '[', ']')?;
+        label = Some(after_name[1..closing].to_string());
+        end = closing + 1;
+    }
+
+    let mut attributes = BTreeMap::new();
+    if after_name[end..].starts_with('{') {
+        let closing = after_name[end..].find('}')?;
+        attributes = parse_attributes(&after_name[end + 1..end + closing])?;
+        end += closing + 1;
+    }
+
+    let meta = DirectiveMeta {
+        kind: DirectiveKind::Text,
+        name: name.to_string(),
+        label,
+        attributes,
+        fence: 1
+    };
+
+    Some((meta, 1 + name.len() + end))
+}
+
+/// Get Directive Token
+///
+/// # Description
+/// Create the token of a container or a leaf directive, the content of the token is the label
+///
+/// # Arguments
+/// * `operator` TagOperator
+/// * `meta` DirectiveMeta
+/// * `label` &str
+///
+/// # Return
+/// TagToken
+pub fn get_directive_token(operator: TagOperator, meta: DirectiveMeta, label: &str) -> TagToken<'_> {
+    TagToken {
+        operator,
+        content: Cow::Borrowed(label),
+        metas: Some(
+            TagMeta {
+                directive: Some(meta),
+                ..Default::default()
+            }
+        ),
+        ..Default::default()
+    }
+}
+
+/// Format Directive
+///
+/// # Description
+/// Write the directive back in the generic syntax i.e `:::name[label]{key="value"}`
+///
+/// # Arguments
+/// * `meta` &DirectiveMeta
+///
+/// # Return
+/// String
+pub fn format_directive(meta: &DirectiveMeta) -> String {
+    let mut directive = DIRECTIVE_MARKER.to_string().repeat(meta.fence.max(1));
+    directive.push_str(&meta.name);
+
+    if let Some(label) = &meta.label {
+        // a trailing backslash would escape the closing bracket
+        let trailing = label.len() - label.trim_end_matches('\\').len();
+        let escape = if trailing % 2 == 1 { "\\" } else { "" };
+        directive.push_str(&format!("[{}{}]", label, escape));
+    }
+
+    if !meta.attributes.is_empty() {
+        let mut attributes = Vec::new();
+        let mut others = Vec::new();
+
+        for (key, value) in &meta.attributes {
+            let is_word = !value.is_empty() && get_word_length(value) == value.len();
+            match key.as_str() {
+                "id" if is_word => attributes.insert(0, format!("#{}", value)),
+                "class" if value.split_whitespace().all(|c| get_word_length(c) == c.len()) => {
+                    attributes.extend(value.split_whitespace().map(|class| format!(".{}", class)));
+                },
+                _ if value.contains('"') => others.push(format!("{}='{}'", key, value)),
+                _ => others.push(format!("{}=\"{}\"", key, value))
+            }
+        }
+
+        attributes.extend(others);
+        directive.push_str(&format!("{{{}}}", attributes.join(" ")));
+    }
+
+    directive
+}
+
+/// Get Fence Length
+///
+/// # Description
+/// Count the leading colons of the line
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// usize
+fn get_fence_length(line: &str) -> usize {
+    line.len() - line.trim_start_matches(DIRECTIVE_MARKER).len()
+}
+
+/// Split Name
+///
+/// # Description
+/// Split the name of the directive from the rest of the content. A name starts with a letter and
+/// contains letters, digits, - and _
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<(&str, &str)>
+fn split_name(content: &str) -> Option<(&str, &str)> {
+    if !content.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let end = content
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(content.len());
+
+    Some(content.split_at(end))
+}
+
+/// Parse Label And Attributes
+///
+/// # Description
+/// Parse the optional `[label]` and `{attributes}` ending a directive
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<(&str, BTreeMap<String, String>)>
+fn parse_label_and_attributes(content: &str) -> Option<(&str, BTreeMap<String, String>)> {
+    let mut rest = content.trim_end();
+    let mut label = "";
+
+    if rest.starts_with('[') {
+        let closing = find_closing(rest, '[', ']')?;
+        label = &rest[1..closing];
+        rest = &rest[closing + 1..];
+    }
+
+    let attributes = match rest.strip_prefix('{').and_then(|r| r.strip_suffix('}')) {
+        Some(attributes) => parse_attributes(attributes)?,
+        None if rest.is_empty() => BTreeMap::new(),
+        None => return None
+    };
+
+    Some((label, attributes))
+}
+
+/// Parse Attributes
+///
+/// # Description
+/// Parse the attributes of a directive i.e `#id .class key=value key="quoted value" flag`
+///
+/// # Arguments
+/// * `content` &str the attributes without the braces
+///
+/// # Return
+/// Option<BTreeMap<String, String>>
+fn parse_attributes(content: &str) -> Option<BTreeMap<String, String>> {
+    let mut attributes: BTreeMap<String, String> = BTreeMap::new();
+    let mut rest = content.trim_start();
+
+    while !rest.is_empty() {
+        let (key, value, len) = match rest.chars().next()? {
+            '#' => {
+                let len = get_word_length(&rest[1..]);
+                if len == 0 {
+                    return None;
+                }
+
+                ("id".to_string(), rest[1..1 + len].to_string(), len + 1)
+            },
+            '.' => {
+                let len = get_word_length(&rest[1..]);
+                if len == 0 {
+                    return None;
+                }
+
+                let class = match attributes.get("class") {
+                    Some(classes) => format!("{} {}", classes, &rest[1..1 + len]),
+                    None => rest[1..1 + len].to_string()
+                };
+
+                ("class".to_string(), class, len + 1)
+            },
+            _ => {
+                let key_len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':'))
+                    .unwrap_or(rest.len());
+
+                let key = &rest[..key_len];
+                match rest[key_len..].strip_prefix('=') {
+                    Some(value) => {
+                        let (value, value_len) = parse_attribute_value(value)?;
+                        (key.to_string(), value, key_len + 1 + value_len)
+                    },
+                    None => (key.to_string(), String::new(), key_len)
+                }
+            }
+        };
+
+        if key.is_empty() {
+            return None;
+        }
+
+        attributes.insert(key, value);
+        rest = &rest[len..];
+
+        // the attributes are separated by whitespaces
+        let trimmed = rest.trim_start();
+        if trimmed.len() == rest.len() && !rest.is_empty() {
+            return None;
+        }
+
+        rest = trimmed;
+    }
+
+    Some(attributes)
+}
+
+/// Parse Attribute Value
+///
+/// # Description
+/// Parse a quoted or an unquoted attribute value
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<(String, usize)> the value and its length within the content
+fn parse_attribute_value(content: &str) -> Option<(String, usize)> {
+    let quote = content.chars().next().filter(|c| *c == '"' || *c == '\'');
+    match quote {
+        Some(quote) => {
+            let end = content[1..].find(quote)?;
+            Some((content[1..1 + end].to_string(), end + 2))
+        },
+        None => {
+            let len = get_word_length(content);
+            if len == 0 {
+                return None;
+            }
+
+            Some((content[..len].to_string(), len))
+        }
+    }
+}
+
+/// Get Word Length
+///
+/// # Description
+/// Get the length of the content until the next whitespace, quote or brace
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// usize
+fn get_word_length(content: &str) -> usize {
+    content
+        .find(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '{' || c == '}' || c == '=')
+        .unwrap_or(content.len())
+}
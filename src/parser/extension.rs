@@ -0,0 +1,224 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::sync::Arc;
+use crate::token::tag::{TagToken, TagOperator};
+
+/// Block Continuation
+///
+/// # Description
+/// Whether a line extends the custom block being parsed
+#[derive(Debug, PartialEq, Clone)]
+pub enum BlockContinuation {
+    // The line is part of the block, the following lines are given to the block
+    Append,
+    // The line is the last line of the block
+    Close,
+    // The line isn't part of the block and is parsed as usual
+    Stop
+}
+
+/// Custom Node Clone
+///
+/// # Description
+/// Object safe helpers of the custom nodes, implemented for every `CustomNode` which is `Clone`
+pub trait CustomNodeClone {
+    /// Clone Box
+    ///
+    /// # Description
+    /// Clone the node behind the box
+    ///
+    /// # Return
+    /// Box<dyn CustomNode>
+    fn clone_box(&self) -> Box<dyn CustomNode>;
+
+    /// As Any
+    ///
+    /// # Description
+    /// Used to downcast the node to its concrete type
+    ///
+    /// # Return
+    /// &dyn Any
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: CustomNode + Clone + 'static> CustomNodeClone for T {
+    fn clone_box(&self) -> Box<dyn CustomNode> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Custom Node
+///
+/// # Description
+/// Node returned by a `BlockParserExt` or an `InlineParserExt`. The node is carried by the tokens
+/// as a `TagOperator::Custom` or an `InlineNode::Custom` and renders itself
+pub trait CustomNode: CustomNodeClone + Debug + Send + Sync {
+    /// Name
+    ///
+    /// # Description
+    /// Name of the syntax i.e `shortcode`
+    ///
+    /// # Return
+    /// &str
+    fn name(&self) -> &str;
+
+    /// Source
+    ///
+    /// # Description
+    /// Markdown the node has been parsed from, used to format the markdown back
+    ///
+    /// # Return
+    /// &str
+    fn source(&self) -> &str;
+
+    /// Render Html
+    ///
+    /// # Description
+    /// Render the node in html
+    ///
+    /// # Return
+    /// String
+    fn render_html(&self) -> String;
+
+    /// Render Text
+    ///
+    /// # Description
+    /// Render the node in plain text. The source is kept by default
+    ///
+    /// # Return
+    /// String
+    fn render_text(&self) -> String {
+        self.source().to_string()
+    }
+
+    /// Push Line
+    ///
+    /// # Description
+    /// Give the line following a custom block to the block. A block ends at its first line by default
+    ///
+    /// # Arguments
+    /// * `line` &str
+    ///
+    /// # Return
+    /// BlockContinuation
+    fn push_line(&mut self, _line: &str) -> BlockContinuation {
+        BlockContinuation::Stop
+    }
+}
+
+impl Clone for Box<dyn CustomNode> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+// Two nodes are the same when they are parsed from the same source by the same syntax
+impl PartialEq for dyn CustomNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name() && self.source() == other.source()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for dyn CustomNode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut node = serializer.serialize_struct("CustomNode", 2)?;
+        node.serialize_field("name", self.name())?;
+        node.serialize_field("source", self.source())?;
+        node.end()
+    }
+}
+
+/// Block Parser Ext
+///
+/// # Description
+/// Custom block syntax registered through the `block_parser` option. The extensions are tried in order
+/// before the built-in blocks, the lines within a code block are never given to them
+pub trait BlockParserExt: Debug + Send + Sync {
+    /// Parse Block
+    ///
+    /// # Description
+    /// Parse the line starting a block. The following lines are given to `CustomNode::push_line`
+    ///
+    /// # Arguments
+    /// * `idx` usize index of the line
+    /// * `line` &str
+    ///
+    /// # Return
+    /// Option<Box<dyn CustomNode>>
+    fn parse_block(&self, idx: usize, line: &str) -> Option<Box<dyn CustomNode>>;
+}
+
+/// Inline Parser Ext
+///
+/// # Description
+/// Custom inline syntax registered through the `inline_parser` option. The extensions are tried in order
+/// at each position of the text before the built-in syntaxes, code spans are never given to them
+pub trait InlineParserExt: Debug + Send + Sync {
+    /// Parse Inline
+    ///
+    /// # Description
+    /// Parse the node starting at the cursor
+    ///
+    /// # Arguments
+    /// * `content` &str content of the line
+    /// * `cursor` usize byte index within the content
+    ///
+    /// # Return
+    /// Option<(Box<dyn CustomNode>, usize)> the node and the length of the match in bytes
+    fn parse_inline(&self, content: &str, cursor: usize) -> Option<(Box<dyn CustomNode>, usize)>;
+}
+
+/// Get Custom Block Token
+///
+/// # Description
+/// Create a custom block from the first extension parsing the line
+///
+/// # Arguments
+/// * `idx` usize
+/// * `line` &str
+/// * `parsers` &[Arc<dyn BlockParserExt>]
+///
+/// # Return
+/// Option<TagToken>
+pub fn get_custom_block_token<'a>(idx: usize, line: &'a str, parsers: &[Arc<dyn BlockParserExt>]) -> Option<TagToken<'a>> {
+    let node = parsers.iter().find_map(|parser| parser.parse_block(idx, line))?;
+
+    Some(TagToken {
+        operator: TagOperator::Custom(node),
+        content: Cow::Borrowed(line.trim()),
+        ..Default::default()
+    })
+}
+
+/// Append Custom Line
+///
+/// # Description
+/// Give the line to the custom block and append it to the content of the block when it's part of it
+///
+/// # Arguments
+/// * `token` &mut TagToken
+/// * `line` &str
+///
+/// # Return
+/// BlockContinuation
+pub fn append_custom_line(token: &mut TagToken, line: &str) -> BlockContinuation {
+    let continuation = match &mut token.operator {
+        TagOperator::Custom(node) => node.push_line(line),
+        _ => BlockContinuation::Stop
+    };
+
+    if continuation != BlockContinuation::Stop {
+        token.content.to_mut().push('\n');
+        token.content.to_mut().push_str(line.trim());
+    }
+
+    continuation
+}
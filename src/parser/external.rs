@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::clone::Clone;
 use regex::Regex;
 use lazy_static::lazy_static;
@@ -10,15 +11,51 @@ lazy_static!{
 }
 
 #[derive(Debug, Clone)]
-pub struct LinkMeta {
-    pub title: String,
-    pub url: String
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkMeta<'a> {
+    pub title: Cow<'a, str>,
+    pub url: Cow<'a, str>,
+    pub rel: Option<String>
 }
 
 #[derive(Debug, Clone)]
-pub struct ImageMeta {
-    pub alt_text: String,
-    pub url: String
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageMeta<'a> {
+    pub alt_text: Cow<'a, str>,
+    pub url: Cow<'a, str>
+}
+
+impl LinkMeta<'_> {
+    /// Into Owned
+    ///
+    /// # Description
+    /// Copy the borrowed title and url
+    ///
+    /// # Return
+    /// LinkMeta<'static>
+    pub fn into_owned(self) -> LinkMeta<'static> {
+        LinkMeta {
+            title: Cow::Owned(self.title.into_owned()),
+            url: Cow::Owned(self.url.into_owned()),
+            rel: self.rel
+        }
+    }
+}
+
+impl ImageMeta<'_> {
+    /// Into Owned
+    ///
+    /// # Description
+    /// Copy the borrowed alt text and url
+    ///
+    /// # Return
+    /// ImageMeta<'static>
+    pub fn into_owned(self) -> ImageMeta<'static> {
+        ImageMeta {
+            alt_text: Cow::Owned(self.alt_text.into_owned()),
+            url: Cow::Owned(self.url.into_owned())
+        }
+    }
 }
 
 
@@ -33,7 +70,7 @@ pub struct ImageMeta {
 ///
 /// # Return
 /// * `Option<Vec<LinkMeta>>`
-pub fn get_link_metas(content: &str, images: &Option<Vec<ImageMeta>>) -> Option<Vec<LinkMeta>> {
+pub fn get_link_metas<'a>(content: &'a str, images: &Option<Vec<ImageMeta>>) -> Option<Vec<LinkMeta<'a>>> {
     let captures = LINK_RE.captures_iter(content);
     let mut imgs = &Vec::new();
     
@@ -46,10 +83,11 @@ pub fn get_link_metas(content: &str, images: &Option<Vec<ImageMeta>>) -> Option<
             let title = link.get(1).unwrap().as_str();
             let url = link.get(2).unwrap().as_str();
 
-            if is_not_image(&imgs, title, url) {
+            if is_not_image(imgs, title, url) {
                 return Some(LinkMeta {
-                    title: title.to_string(),
-                    url: url.to_string()
+                    title: Cow::Borrowed(title),
+                    url: Cow::Borrowed(url),
+                    rel: None
                 });
             }
 
@@ -74,17 +112,14 @@ pub fn get_link_metas(content: &str, images: &Option<Vec<ImageMeta>>) -> Option<
 ///
 /// # Return
 /// Option<Vec<ImageMeta>>
-pub fn get_image_metas(content: &str) -> Option<Vec<ImageMeta>> {
+pub fn get_image_metas(content: &str) -> Option<Vec<ImageMeta<'_>>> {
     let captures = IMG_RE.captures_iter(content);
 
     let images: Vec<ImageMeta> = captures
         .map(|img| {
-            let alt_text = img.get(1).unwrap().as_str().to_string();
-            let url = img.get(2).unwrap().as_str().to_string();
-
             ImageMeta {
-                alt_text,
-                url
+                alt_text: Cow::Borrowed(img.get(1).unwrap().as_str()),
+                url: Cow::Borrowed(img.get(2).unwrap().as_str())
             }
         })
         .collect();
@@ -121,4 +156,23 @@ fn is_not_image(images: &[ImageMeta], link_title: &str, link_url: &str) -> bool
     }
 
     false
-}
\ No newline at end of file
+}
+
+/// Is Within Url
+///
+/// # Description
+/// Check whether the index is within a bare url i.e https://example.com/a--b or www.example.com.
+/// The destination of a markdown link is a word starting with the url as well i.e [a](https://example.com)
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+///
+/// # Return
+/// bool
+pub fn is_within_url(content: &str, idx: usize) -> bool {
+    let start = content[..idx].rfind(char::is_whitespace).map_or(0, |pos| pos + 1);
+    let word = &content[start..idx];
+
+    word.contains("://") || word.starts_with("www.")
+}
@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+use std::clone::Clone;
+use regex::Regex;
+use lazy_static::lazy_static;
+use crate::token::tag::{TagToken, TagOperator, TagMeta};
+use super::text_style::mask_code_spans;
+
+// Creating static variable to match footnote reference [^label]
+// and footnote definition [^label]: text
+lazy_static!{
+    static ref FOOTNOTE_REF_RE: Regex = Regex::new(r"\[\^([^\]\s]+)\]").unwrap();
+    static ref FOOTNOTE_REF_START: Regex = Regex::new(r"^\[\^([^\]\s]+)\]").unwrap();
+    static ref FOOTNOTE_DEF_RE: Regex = Regex::new(r"^\[\^([^\]\s]+)\]:\s*(.*)$").unwrap();
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteRef {
+    pub label: String,
+    pub col: Option<usize>,
+    pub number: Option<usize>
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteMeta {
+    pub label: String,
+    pub number: Option<usize>,
+    pub paragraphs: Vec<String>
+}
+
+/// Get Footnote Refs
+///
+/// # Description
+/// Get the footnote references i.e `[^1]` from the content of a line. The inline code and the escaped
+/// references i.e `\[^1]` are skipped
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<Vec<FootnoteRef>>
+pub fn get_footnote_refs(content: &str) -> Option<Vec<FootnoteRef>> {
+    let masked = mask_code_spans(content);
+    let refs: Vec<FootnoteRef> = FOOTNOTE_REF_RE
+        .captures_iter(&masked)
+        .filter(|c| !c.get(0).is_some_and(|m| masked[..m.start()].ends_with('\\')))
+        .map(|c| {
+            let label = c.get(1).unwrap().as_str().to_string();
+            let col = c.get(0).map(|m| m.start());
+
+            FootnoteRef {
+                label,
+                col,
+                number: None
+            }
+        })
+        .collect();
+
+    if refs.is_empty() {
+        return None;
+    }
+
+    Some(refs)
+}
+
+/// Get Footnote Ref At
+///
+/// # Description
+/// Match a footnote reference at the start of the content
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<(String, usize)> the label and the length of the match
+pub fn get_footnote_ref_at(content: &str) -> Option<(String, usize)> {
+    FOOTNOTE_REF_START
+        .captures(content)
+        .map(|c| (c[1].to_string(), c[0].len()))
+}
+
+/// Get Footnote Definition
+///
+/// # Description
+/// Get a footnote definition tag token i.e `[^1]: note text`
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<TagToken>
+pub fn get_footnote_definition(content: &str) -> Option<TagToken<'_>> {
+    let captures = FOOTNOTE_DEF_RE.captures(content)?;
+    let label = captures.get(1).unwrap().as_str().to_string();
+    let text = captures.get(2).map_or("", |v| v.as_str()).trim();
+
+    Some(
+        TagToken {
+            operator: TagOperator::FootnoteDefinition,
+            content: Cow::Borrowed(text),
+            metas: Some(
+                TagMeta {
+                    footnote: Some(FootnoteMeta {
+                        label,
+                        number: None,
+                        paragraphs: vec![text.to_string()]
+                    }),
+                    ..Default::default()
+                }
+            ),
+            ..Default::default()
+        }
+    )
+}
@@ -0,0 +1,148 @@
+use std::clone::Clone;
+use std::ops::Range;
+#[cfg(feature = "front_matter")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "front_matter")]
+use crate::error;
+
+// Delimiter of a YAML front matter
+const YAML_DELIMITER: &str = "---";
+// Alternative closing delimiter of a YAML front matter
+const YAML_END_DELIMITER: &str = "...";
+// Delimiter of a TOML front matter
+const TOML_DELIMITER: &str = "+++";
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FrontMatterKind {
+    Yaml,
+    Toml
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrontMatter {
+    pub kind: FrontMatterKind,
+    pub raw: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub span: Range<usize>
+}
+
+impl FrontMatter {
+    /// Contains Line
+    ///
+    /// # Description
+    /// Return if the line, delimiters included, is part of the front matter
+    ///
+    /// # Arguments
+    /// * `line` usize
+    ///
+    /// # Return
+    /// bool
+    pub fn contains_line(&self, line: usize) -> bool {
+        line >= self.start_line && line <= self.end_line
+    }
+
+    /// Deserialize
+    ///
+    /// # Description
+    /// Deserialize the raw front matter into a user provided type
+    ///
+    /// # Return
+    /// Result<T, error::ParserError>
+    #[cfg(feature = "front_matter")]
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, error::ParserError> {
+        let res = match self.kind {
+            FrontMatterKind::Yaml => serde_yaml::from_str(&self.raw).map_err(|err| err.to_string()),
+            FrontMatterKind::Toml => toml::from_str(&self.raw).map_err(|err| err.to_string())
+        };
+
+        res.map_err(|err| error::ParserError {
+            message: format!("{}: {}", error::FRONT_MATTER_DESERIALIZE, err)
+        })
+    }
+}
+
+/// Get Front Matter
+///
+/// # Description
+/// Detect a YAML (---) or TOML (+++) front matter at the start of the content. The opening delimiter
+/// must be the first line, a --- further down is a thematic break
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<FrontMatter>
+pub fn get_front_matter(content: &str) -> Option<FrontMatter> {
+    let mut offset = 0;
+    let mut opening: Option<(FrontMatterKind, usize, usize)> = None;
+    let mut raw = String::new();
+
+    for (idx, line) in content.split_inclusive('\n').enumerate() {
+        let trimmed = line.trim();
+
+        match &opening {
+            None => {
+                let kind = match trimmed {
+                    YAML_DELIMITER => FrontMatterKind::Yaml,
+                    TOML_DELIMITER => FrontMatterKind::Toml,
+                    _ => return None
+                };
+
+                opening = Some((kind, idx, offset));
+            },
+            Some((kind, start_line, start)) => {
+                if is_closing_delimiter(kind, trimmed) {
+                    return Some(FrontMatter {
+                        kind: kind.clone(),
+                        raw,
+                        start_line: *start_line,
+                        end_line: idx,
+                        span: *start..offset + line.len()
+                    });
+                }
+
+                raw.push_str(line);
+            }
+        }
+
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Is Opening Delimiter
+///
+/// # Description
+/// Check whether the trimmed line may open a front matter
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// bool
+pub fn is_opening_delimiter(line: &str) -> bool {
+    line == YAML_DELIMITER || line == TOML_DELIMITER
+}
+
+/// Is Closing Delimiter
+///
+/// # Description
+/// Check whether the line close the front matter
+///
+/// # Arguments
+/// * `kind` &FrontMatterKind
+/// * `line` &str
+///
+/// # Return
+/// bool
+fn is_closing_delimiter(kind: &FrontMatterKind, line: &str) -> bool {
+    match kind {
+        FrontMatterKind::Yaml => line == YAML_DELIMITER || line == YAML_END_DELIMITER,
+        FrontMatterKind::Toml => line == TOML_DELIMITER
+    }
+}
@@ -0,0 +1,329 @@
+use std::default::Default;
+use std::clone::Clone;
+use std::ops::Range;
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::emoji_table::EMOJI_SHORTCODES;
+use super::external::is_within_url;
+use super::inline::match_link;
+use super::text_style::mask_code_spans;
+
+// Default url of a mention
+const MENTION_URL: &str = "https://github.com/{user}";
+// Default url of an issue reference
+const ISSUE_URL: &str = "https://github.com/{repository}/issues/{number}";
+
+lazy_static!{
+    static ref EMOJI_RE: Regex = Regex::new(r"^:([a-z0-9_+\-]+):").unwrap();
+    static ref MENTION_RE: Regex = Regex::new(r"^@([A-Za-z0-9](?:[A-Za-z0-9-]{0,38})(?:/[A-Za-z0-9_.-]+)?)").unwrap();
+    static ref ISSUE_RE: Regex = Regex::new(r"^(?:([A-Za-z0-9-]+/[A-Za-z0-9_.-]+))?#([0-9]+)\b").unwrap();
+}
+
+/// Github Options
+///
+/// # Description
+/// Url templates used to render the mentions and the issue references. {user}, {repository}
+/// and {number} are replaced by the value of the reference
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GithubOptions {
+    pub mention_url: String,
+    pub issue_url: String,
+    // Repository of the references which doesn't specify one i.e #123
+    pub repository: Option<String>
+}
+
+impl Default for GithubOptions {
+    fn default() -> Self {
+        GithubOptions {
+            mention_url: MENTION_URL.to_string(),
+            issue_url: ISSUE_URL.to_string(),
+            repository: None
+        }
+    }
+}
+
+impl GithubOptions {
+    /// Get Mention Url
+    ///
+    /// # Description
+    /// Get the url of a mention
+    ///
+    /// # Arguments
+    /// * `user` &str
+    ///
+    /// # Return
+    /// String
+    pub fn get_mention_url(&self, user: &str) -> String {
+        self.mention_url.replace("{user}", user)
+    }
+
+    /// Get Issue Url
+    ///
+    /// # Description
+    /// Get the url of an issue reference. None is returned if the repository is unknown
+    ///
+    /// # Arguments
+    /// * `issue` &IssueRef
+    ///
+    /// # Return
+    /// Option<String>
+    pub fn get_issue_url(&self, issue: &IssueRef) -> Option<String> {
+        let repository = issue.repository.as_ref().or(self.repository.as_ref())?;
+
+        Some(
+            self.issue_url
+                .replace("{repository}", repository)
+                .replace("{number}", &issue.number.to_string())
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Emoji {
+    pub shortcode: String,
+    pub unicode: String,
+    pub col: Option<usize>
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mention {
+    pub user: String,
+    pub col: Option<usize>
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IssueRef {
+    pub repository: Option<String>,
+    pub number: usize,
+    pub col: Option<usize>
+}
+
+/// Github Metas
+#[derive(Debug, Default)]
+pub struct GithubMetas {
+    pub emojis: Option<Vec<Emoji>>,
+    pub mentions: Option<Vec<Mention>>,
+    pub issue_refs: Option<Vec<IssueRef>>
+}
+
+/// Get Github Metas
+///
+/// # Description
+/// Get the emoji shortcodes, the mentions and the issue references of a line.
+/// The inline code and the urls are skipped, as well as the mentions and the issue references
+/// within the label of a link
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// GithubMetas
+pub fn get_github_metas(content: &str) -> GithubMetas {
+    let masked = mask_code_spans(content);
+    let labels = get_link_labels(&masked);
+    let mut emojis = Vec::new();
+    let mut mentions = Vec::new();
+    let mut issue_refs = Vec::new();
+
+    let mut idx = 0;
+    while idx < masked.len() {
+        if let Some((emoji, len)) = get_emoji_at(&masked, idx) {
+            emojis.push(emoji);
+            idx += len;
+        } else if labels.iter().any(|label| label.contains(&idx)) {
+            idx += masked[idx..].chars().next().map_or(1, |c| c.len_utf8());
+        } else if let Some((mention, len)) = get_mention_at(&masked, idx) {
+            mentions.push(mention);
+            idx += len;
+        } else if let Some((issue, len)) = get_issue_ref_at(&masked, idx) {
+            issue_refs.push(issue);
+            idx += len;
+        } else {
+            idx += masked[idx..].chars().next().map_or(1, |c| c.len_utf8());
+        }
+    }
+
+    GithubMetas {
+        emojis: to_option(emojis),
+        mentions: to_option(mentions),
+        issue_refs: to_option(issue_refs)
+    }
+}
+
+/// Get Link Labels
+///
+/// # Description
+/// Get the byte range of the label of each link of the content i.e `[label](url)`
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Vec<Range<usize>>
+fn get_link_labels(content: &str) -> Vec<Range<usize>> {
+    let mut labels = Vec::new();
+    let mut idx = 0;
+
+    while let Some(pos) = content[idx..].find('[') {
+        let start = idx + pos;
+        idx = start + 1;
+
+        if content[..start].ends_with('\\') {
+            continue;
+        }
+
+        if let Some((label, _, len)) = match_link(&content[start..]) {
+            labels.push(start + 1..start + 1 + label.len());
+            idx = start + len;
+        }
+    }
+
+    labels
+}
+
+/// Get Emoji At
+///
+/// # Description
+/// Match an emoji shortcode i.e :rocket: at the index of the content. Unknown shortcodes are skipped
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+///
+/// # Return
+/// Option<(Emoji, usize)> the emoji and the length of the match
+pub fn get_emoji_at(content: &str, idx: usize) -> Option<(Emoji, usize)> {
+    let captures = EMOJI_RE.captures(&content[idx..])?;
+    let shortcode = &captures[1];
+    let unicode = get_emoji_unicode(shortcode)?;
+
+    Some((
+        Emoji {
+            shortcode: shortcode.to_string(),
+            unicode: unicode.to_string(),
+            col: Some(idx)
+        },
+        captures[0].len()
+    ))
+}
+
+/// Get Mention At
+///
+/// # Description
+/// Match a mention i.e @user or @org/team at the index of the content. A @ within a word
+/// i.e an email or within an url i.e https://medium.com/@user isn't a mention
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+///
+/// # Return
+/// Option<(Mention, usize)>
+pub fn get_mention_at(content: &str, idx: usize) -> Option<(Mention, usize)> {
+    if !is_boundary(content, idx) || is_within_url(content, idx) {
+        return None;
+    }
+
+    let captures = MENTION_RE.captures(&content[idx..])?;
+
+    Some((
+        Mention {
+            user: captures[1].to_string(),
+            col: Some(idx)
+        },
+        captures[0].len()
+    ))
+}
+
+/// Get Issue Ref At
+///
+/// # Description
+/// Match an issue reference i.e #123 or org/repo#123 at the index of the content. The pattern
+/// is only tried on the words containing a #, the references within an url are skipped
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+///
+/// # Return
+/// Option<(IssueRef, usize)>
+pub fn get_issue_ref_at(content: &str, idx: usize) -> Option<(IssueRef, usize)> {
+    if !is_boundary(content, idx) || content[..idx].ends_with(['/', '&']) {
+        return None;
+    }
+
+    let rest = &content[idx..];
+    let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    if !rest[..word_end].contains('#') || is_within_url(content, idx) {
+        return None;
+    }
+
+    let captures = ISSUE_RE.captures(rest)?;
+    let number = captures[2].parse::<usize>().ok()?;
+
+    Some((
+        IssueRef {
+            repository: captures.get(1).map(|r| r.as_str().to_string()),
+            number,
+            col: Some(idx)
+        },
+        captures[0].len()
+    ))
+}
+
+/// Get Emoji Unicode
+///
+/// # Description
+/// Lookup the unicode of a shortcode in the bundled table
+///
+/// # Arguments
+/// * `shortcode` &str
+///
+/// # Return
+/// Option<&str>
+pub fn get_emoji_unicode(shortcode: &str) -> Option<&'static str> {
+    EMOJI_SHORTCODES
+        .binary_search_by(|(code, _)| (*code).cmp(shortcode))
+        .ok()
+        .map(|idx| EMOJI_SHORTCODES[idx].1)
+}
+
+/// Is Boundary
+///
+/// # Description
+/// Check that the index isn't preceded by a word character
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+///
+/// # Return
+/// bool
+fn is_boundary(content: &str, idx: usize) -> bool {
+    !content[..idx]
+        .chars()
+        .last()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '@' || c == '#')
+}
+
+/// To Option
+///
+/// # Description
+/// Return None for an empty vector
+///
+/// # Arguments
+/// * `vec` Vec<T>
+///
+/// # Return
+/// Option<Vec<T>>
+fn to_option<T>(vec: Vec<T>) -> Option<Vec<T>> {
+    if vec.is_empty() {
+        return None;
+    }
+
+    Some(vec)
+}
@@ -1,9 +1,18 @@
 use std::default::Default;
 use std::clone::Clone;
+use regex::Regex;
+use lazy_static::lazy_static;
+
+lazy_static!{
+    static ref CUSTOM_ID: Regex = Regex::new(r"\s*\{#([A-Za-z0-9_:.-]+)\}\s*$").unwrap();
+}
 
 /// Heading LEvel
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum HeadingLevel {
+    #[default]
     H1,
     H2,
     H3,
@@ -12,27 +21,61 @@ pub enum HeadingLevel {
     H6
 }
 
-impl Default for HeadingLevel {
-    fn default() -> Self { HeadingLevel::H1 }
-}
-
 /// Get Heading Depth
 ///
 /// # Description
-/// Get the heading depth by counting the number of #
+/// Get the heading depth by counting the number of leading #
 pub fn get_heading_depth(line: &str) -> HeadingLevel {
-    let res = line.rfind('#');
-    if let Some(idx) = res {
-        return match idx {
-            0 => HeadingLevel::H1,
-            1 => HeadingLevel::H2,
-            2 => HeadingLevel::H3,
-            3 => HeadingLevel::H4,
-            4 => HeadingLevel::H5,
-            5 => HeadingLevel::H6,
-            _ => HeadingLevel::H2
-        };
+    let depth = line.len() - line.trim_start_matches('#').len();
+
+    match depth {
+        0 | 1 => HeadingLevel::H1,
+        2 => HeadingLevel::H2,
+        3 => HeadingLevel::H3,
+        4 => HeadingLevel::H4,
+        5 => HeadingLevel::H5,
+        6 => HeadingLevel::H6,
+        _ => HeadingLevel::H2
+    }
+}
+
+/// Get Heading Content
+///
+/// # Description
+/// Remove the opening # and the optional closing # sequence of a heading. Unlike the other tags
+/// the style characters i.e `**bold**` are kept
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// &str
+pub fn get_heading_content(line: &str) -> &str {
+    let content = line.trim_start_matches('#').trim();
+    let without_closing = content.trim_end_matches('#');
+    if without_closing.is_empty() || without_closing.ends_with(char::is_whitespace) {
+        return without_closing.trim();
     }
 
-    HeadingLevel::H1
-}
\ No newline at end of file
+    content
+}
+
+/// Get Custom Id
+///
+/// # Description
+/// Split the explicit `{#custom-id}` attribute from the end of a heading
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// (&str, Option<String>) the heading without the attribute and the id
+pub fn get_custom_id(line: &str) -> (&str, Option<String>) {
+    match CUSTOM_ID.captures(line) {
+        Some(captures) => {
+            let start = captures.get(0).map_or(line.len(), |m| m.start());
+            (&line[..start], Some(captures[1].to_string()))
+        },
+        None => (line, None)
+    }
+}
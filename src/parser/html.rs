@@ -0,0 +1,228 @@
+use std::borrow::Cow;
+use std::clone::Clone;
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::text_style::TextOption;
+use crate::token::tag::{TagToken, TagOperator, TagMeta};
+
+// Open tag, closing tag, comment, processing instruction, CDATA and declaration
+// See: https://spec.commonmark.org/0.31.2/#raw-html
+const INLINE_HTML_PATTERN: &str = concat!(
+    r#"(<[A-Za-z][A-Za-z0-9-]*(\s+[A-Za-z_:][A-Za-z0-9_.:-]*(\s*=\s*([^\s"'=<>`]+|'[^']*'|"[^"]*"))?)*\s*/?>"#,
+    r"|</[A-Za-z][A-Za-z0-9-]*\s*>",
+    r"|<!---?>|<!--.*?-->",
+    r"|<\?.*?\?>",
+    r"|<!\[CDATA\[.*?\]\]>",
+    r"|<![A-Za-z][^>]*>)"
+);
+
+// Creating static variable to match the seven kinds of html block of the CommonMark spec
+// See: https://spec.commonmark.org/0.31.2/#html-blocks
+lazy_static!{
+    static ref RAW_TEXT_START: Regex = Regex::new(r"(?i)^<(pre|script|style|textarea)(\s|>|$)").unwrap();
+    static ref RAW_TEXT_END: Regex = Regex::new(r"(?i)</(pre|script|style|textarea)>").unwrap();
+    static ref DECLARATION_START: Regex = Regex::new(r"^<![A-Za-z]").unwrap();
+    static ref BLOCK_TAG_START: Regex = Regex::new(concat!(
+        r"(?i)^</?(address|article|aside|base|basefont|blockquote|body|caption|center|col|colgroup|dd|details|dialog|",
+        r"dir|div|dl|dt|fieldset|figcaption|figure|footer|form|frame|frameset|h1|h2|h3|h4|h5|h6|head|header|hr|html|",
+        r"iframe|legend|li|link|main|menu|menuitem|nav|noframes|ol|optgroup|option|p|param|search|section|summary|",
+        r"table|tbody|td|tfoot|th|thead|title|tr|track|ul)(\s|/?>|$)"
+    )).unwrap();
+    static ref COMPLETE_TAG: Regex = Regex::new(concat!(
+        r#"^(<[A-Za-z][A-Za-z0-9-]*(\s+[A-Za-z_:][A-Za-z0-9_.:-]*(\s*=\s*([^\s"'=<>`]+|'[^']*'|"[^"]*"))?)*\s*/?>"#,
+        r"|</[A-Za-z][A-Za-z0-9-]*\s*>)\s*$"
+    )).unwrap();
+    static ref INLINE_HTML: Regex = Regex::new(INLINE_HTML_PATTERN).unwrap();
+    static ref INLINE_HTML_START: Regex = Regex::new(&format!("^{}", INLINE_HTML_PATTERN)).unwrap();
+}
+
+/// Html Block Kind
+///
+/// Follow the order of the html block kinds of the CommonMark spec
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum HtmlBlockKind {
+    // <script>, <pre>, <style>, <textarea>
+    RawText,
+    // <!-- -->
+    Comment,
+    // <? ?>
+    ProcessingInstruction,
+    // <!DOCTYPE >
+    Declaration,
+    // <![CDATA[ ]]>
+    CData,
+    // <div>, <details>, <summary>...
+    BlockTag,
+    // Any other complete tag alone on its line
+    CompleteTag
+}
+
+/// Get Html Block Kind
+///
+/// # Description
+/// Return the kind of html block started by the line if any. A complete tag can't interrupt
+/// a paragraph
+///
+/// # Arguments
+/// * `line` &str
+/// * `in_paragraph` bool
+///
+/// # Return
+/// Option<HtmlBlockKind>
+pub fn get_html_block_kind(line: &str, in_paragraph: bool) -> Option<HtmlBlockKind> {
+    if !line.starts_with('<') {
+        return None;
+    }
+
+    if RAW_TEXT_START.is_match(line) {
+        return Some(HtmlBlockKind::RawText);
+    }
+
+    if line.starts_with("<!--") {
+        return Some(HtmlBlockKind::Comment);
+    }
+
+    if line.starts_with("<?") {
+        return Some(HtmlBlockKind::ProcessingInstruction);
+    }
+
+    if DECLARATION_START.is_match(line) {
+        return Some(HtmlBlockKind::Declaration);
+    }
+
+    if line.starts_with("<![CDATA[") {
+        return Some(HtmlBlockKind::CData);
+    }
+
+    if BLOCK_TAG_START.is_match(line) {
+        return Some(HtmlBlockKind::BlockTag);
+    }
+
+    if !in_paragraph && COMPLETE_TAG.is_match(line) {
+        return Some(HtmlBlockKind::CompleteTag);
+    }
+
+    None
+}
+
+/// Is Html Block End
+///
+/// # Description
+/// Check whether the line end the html block. Block and complete tags are ended by a blank line
+/// which is not part of the block
+///
+/// # Arguments
+/// * `kind` &HtmlBlockKind
+/// * `line` &str
+///
+/// # Return
+/// bool
+pub fn is_html_block_end(kind: &HtmlBlockKind, line: &str) -> bool {
+    match kind {
+        HtmlBlockKind::RawText => RAW_TEXT_END.is_match(line),
+        HtmlBlockKind::Comment => line.contains("-->"),
+        HtmlBlockKind::ProcessingInstruction => line.contains("?>"),
+        HtmlBlockKind::Declaration => line.contains('>'),
+        HtmlBlockKind::CData => line.contains("]]>"),
+        HtmlBlockKind::BlockTag | HtmlBlockKind::CompleteTag => line.trim().is_empty()
+    }
+}
+
+/// Get Inline Html
+///
+/// # Description
+/// Get the raw inline html i.e `<kbd>`, `</kbd>`, `<br>`, `<!-- comment -->` of a line
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<Vec<TextOption>>
+pub fn get_inline_html(content: &str) -> Option<Vec<TextOption<'_>>> {
+    let html: Vec<TextOption> = INLINE_HTML
+        .find_iter(content)
+        .map(|m| TextOption {
+            word: Cow::Borrowed(m.as_str()),
+            col: Some(m.start())
+        })
+        .collect();
+
+    if html.is_empty() {
+        return None;
+    }
+
+    Some(html)
+}
+
+/// Get Inline Html At
+///
+/// # Description
+/// Match a raw inline html at the start of the content
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<&str>
+pub fn get_inline_html_at(content: &str) -> Option<&str> {
+    INLINE_HTML_START.find(content).map(|m| m.as_str())
+}
+
+/// Strip Inline Html
+///
+/// # Description
+/// Remove the raw inline html of a line
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// String
+pub fn strip_inline_html(content: &str) -> String {
+    INLINE_HTML.replace_all(content, "").to_string()
+}
+
+/// Get Html Block Token
+///
+/// # Description
+/// Get the tag token of the line which start an html block
+///
+/// # Arguments
+/// * `line` &str
+/// * `kind` HtmlBlockKind
+///
+/// # Return
+/// TagToken
+pub fn get_html_block_token(line: &str, kind: HtmlBlockKind) -> TagToken<'_> {
+    TagToken {
+        operator: TagOperator::HtmlBlock,
+        content: Cow::Borrowed(line),
+        metas: Some(
+            TagMeta {
+                html_kind: Some(kind),
+                ..Default::default()
+            }
+        ),
+        ..Default::default()
+    }
+}
+
+/// Append Html Line
+///
+/// # Description
+/// Append a line to an html block. The indentation of the block is removed in order to keep
+/// the html verbatim
+///
+/// # Arguments
+/// * `token` &mut TagToken
+/// * `line` &str
+/// * `indent` usize
+pub fn append_html_line(token: &mut TagToken, line: &str, indent: usize) {
+    let leading = line.len() - line.trim_start().len();
+
+    let content = token.content.to_mut();
+    content.push('\n');
+    content.push_str(line[leading.min(indent)..].trim_end());
+}
@@ -0,0 +1,552 @@
+use std::clone::Clone;
+use std::ops::Range;
+use crate::options::ParserOptions;
+use super::{footnote, github, html, math, typography};
+use super::directive::{self, DirectiveMeta};
+use super::extension::CustomNode;
+
+/// Inline Node
+///
+/// Node of the inline content of a line in document order. Unlike the TextMetas,
+/// the nodes keep the nesting of the styles i.e `**bold _italic_**`
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value", rename_all = "snake_case"))]
+pub enum InlineNode {
+    Text(String),
+    Strong(Vec<InlineNode>),
+    Emphasis(Vec<InlineNode>),
+    Strike(Vec<InlineNode>),
+    Code(String),
+    Link {
+        title: Vec<InlineNode>,
+        url: String,
+        // Title of the destination i.e [label](url "title")
+        #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+        tooltip: Option<String>
+    },
+    Image {
+        alt_text: String,
+        url: String,
+        #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+        tooltip: Option<String>
+    },
+    FootnoteRef(String),
+    Html(String),
+    InlineMath(String),
+    Emoji {
+        shortcode: String,
+        unicode: String
+    },
+    Mention(String),
+    IssueRef {
+        repository: Option<String>,
+        number: usize
+    },
+    // :name[label]{attrs}
+    TextDirective {
+        directive: DirectiveMeta,
+        label: Vec<InlineNode>
+    },
+    // Node parsed by an InlineParserExt
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+    Custom(Box<dyn CustomNode>)
+}
+
+/// Get Inline Nodes
+///
+/// # Description
+/// Parse the content of a line into inline nodes
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Vec<InlineNode>
+pub fn get_inline_nodes(content: &str, options: &ParserOptions) -> Vec<InlineNode> {
+    let mut nodes = Vec::new();
+    let mut text = String::new();
+    let mut idx = 0;
+
+    while idx < content.len() {
+        if let Some((node, len)) = match_node(content, idx, options) {
+            match node {
+                InlineNode::Text(t) => text.push_str(&t),
+                _ => {
+                    flush_text(&mut nodes, &mut text);
+                    nodes.push(node);
+                }
+            }
+
+            idx += len;
+            continue;
+        }
+
+        let c = content[idx..].chars().next().unwrap();
+        text.push(c);
+        idx += c.len_utf8();
+    }
+
+    flush_text(&mut nodes, &mut text);
+    nodes
+}
+
+/// Get Inline Spans
+///
+/// # Description
+/// Parse the content of a line into inline nodes along with the byte range of their source. Unlike
+/// `get_inline_nodes` every byte of the content belongs to a node, the consecutive texts are merged
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Vec<(InlineNode, Range<usize>)>
+pub fn get_inline_spans(content: &str, options: &ParserOptions) -> Vec<(InlineNode, Range<usize>)> {
+    let mut spans: Vec<(InlineNode, Range<usize>)> = Vec::new();
+    let mut idx = 0;
+
+    while idx < content.len() {
+        let (node, len) = match_node(content, idx, options).unwrap_or_else(|| {
+            let c = content[idx..].chars().next().unwrap();
+            (InlineNode::Text(c.to_string()), c.len_utf8())
+        });
+
+        match (spans.last_mut(), node) {
+            (Some((InlineNode::Text(previous), span)), InlineNode::Text(text)) => {
+                previous.push_str(&text);
+                span.end = idx + len;
+            },
+            (_, node) => spans.push((node, idx..idx + len))
+        }
+
+        idx += len;
+    }
+
+    spans
+}
+
+/// Get Plain Text
+///
+/// # Description
+/// Get the text of the nodes without any markup. The links keep their title and the images their alt text
+///
+/// # Arguments
+/// * `nodes` &[InlineNode]
+///
+/// # Return
+/// String
+pub fn get_plain_text(nodes: &[InlineNode]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            InlineNode::Text(text) | InlineNode::Code(text) | InlineNode::InlineMath(text) => text.clone(),
+            InlineNode::Strong(children)
+            | InlineNode::Emphasis(children)
+            | InlineNode::Strike(children)
+            | InlineNode::Link { title: children, .. } => get_plain_text(children),
+            InlineNode::Image { alt_text, .. } => alt_text.clone(),
+            InlineNode::Emoji { unicode, .. } => unicode.clone(),
+            InlineNode::Mention(user) => format!("@{}", user),
+            InlineNode::IssueRef { repository, number } => format!("{}#{}", repository.as_deref().unwrap_or(""), number),
+            InlineNode::TextDirective { label, .. } => get_plain_text(label),
+            InlineNode::Custom(node) => node.render_text(),
+            InlineNode::FootnoteRef(_) | InlineNode::Html(_) => String::new()
+        })
+        .collect()
+}
+
+/// Match Node
+///
+/// # Description
+/// Match an inline node starting at the index of the content
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Option<(InlineNode, usize)> the node and the length of the match
+fn match_node(content: &str, idx: usize, options: &ParserOptions) -> Option<(InlineNode, usize)> {
+    let rest = &content[idx..];
+    let first = rest.chars().next()?;
+
+    let custom = options.inline_parsers
+        .iter()
+        .find_map(|parser| parser.parse_inline(content, idx))
+        // the match must end on a character of the content
+        .filter(|(_, len)| *len > 0 && content.is_char_boundary(idx + len));
+
+    if let Some((node, len)) = custom {
+        return Some((InlineNode::Custom(node), len));
+    }
+
+    match first {
+        '\\' => {
+            let escaped = rest[1..].chars().next().filter(|c| c.is_ascii_punctuation())?;
+            Some((InlineNode::Text(escaped.to_string()), 2))
+        },
+        '`' => Some(match_code(rest)),
+        '$' if options.math => math::get_inline_math_at(content, idx)
+            .map(|(word, len)| (InlineNode::InlineMath(word.to_string()), len)),
+        '<' => {
+            let html = html::get_inline_html_at(rest)?;
+            if options.drop_html {
+                return Some((InlineNode::Text(String::new()), html.len()));
+            }
+
+            Some((InlineNode::Html(html.to_string()), html.len()))
+        },
+        '!' if rest[1..].starts_with('[') => {
+            let (label, url, len) = match_link(&rest[1..])?;
+            let tooltip = get_link_title(&rest[1..=len]);
+            Some((InlineNode::Image { alt_text: label.to_string(), url, tooltip }, len + 1))
+        },
+        '[' => {
+            if let Some((label, len)) = footnote::get_footnote_ref_at(rest).filter(|_| options.footnotes) {
+                return Some((InlineNode::FootnoteRef(label), len));
+            }
+
+            let (label, url, len) = match_link(rest)?;
+            let tooltip = get_link_title(&rest[..len]);
+            let title = unlink_references(get_inline_nodes(label, options));
+            Some((InlineNode::Link { title, url, tooltip }, len))
+        },
+        '~' if options.strikethrough && rest.starts_with("~~") => {
+            let end = rest[2..].find("~~")?;
+            if end == 0 {
+                return None;
+            }
+
+            Some((InlineNode::Strike(get_inline_nodes(&rest[2..2 + end], options)), end + 4))
+        },
+        '*' | '_' => match_emphasis(content, idx, first, options),
+        '"' | '\'' | '-' | '.' if options.smart_punctuation => typography::get_smart_punctuation_at(content, idx)
+            .map(|(punctuation, len)| (InlineNode::Text(punctuation), len)),
+        ':' => {
+            if let Some((directive, len)) = directive::get_text_directive_at(content, idx).filter(|_| options.directives) {
+                let label = get_inline_nodes(directive.label.as_deref().unwrap_or_default(), options);
+                return Some((InlineNode::TextDirective { directive, label }, len));
+            }
+
+            options.github.as_ref()?;
+            github::get_emoji_at(content, idx)
+                .map(|(emoji, len)| (InlineNode::Emoji { shortcode: emoji.shortcode, unicode: emoji.unicode }, len))
+        },
+        '@' if options.github.is_some() => github::get_mention_at(content, idx)
+            .map(|(mention, len)| (InlineNode::Mention(mention.user), len)),
+        c if options.github.is_some() && (c == '#' || c.is_ascii_alphanumeric()) => github::get_issue_ref_at(content, idx)
+            .map(|(issue, len)| (InlineNode::IssueRef { repository: issue.repository, number: issue.number }, len)),
+        _ => None
+    }
+}
+
+/// Unlink References
+///
+/// # Description
+/// Turn the mentions and the issue references of a link label back into text, a link can't be
+/// nested within an other link
+///
+/// # Arguments
+/// * `nodes` Vec<InlineNode>
+///
+/// # Return
+/// Vec<InlineNode>
+fn unlink_references(nodes: Vec<InlineNode>) -> Vec<InlineNode> {
+    let mut unlinked: Vec<InlineNode> = Vec::new();
+
+    for node in nodes {
+        let node = match node {
+            InlineNode::Mention(_) | InlineNode::IssueRef { .. } => InlineNode::Text(get_plain_text(&[node])),
+            InlineNode::Strong(children) => InlineNode::Strong(unlink_references(children)),
+            InlineNode::Emphasis(children) => InlineNode::Emphasis(unlink_references(children)),
+            InlineNode::Strike(children) => InlineNode::Strike(unlink_references(children)),
+            node => node
+        };
+
+        match (unlinked.last_mut(), node) {
+            (Some(InlineNode::Text(previous)), InlineNode::Text(text)) => previous.push_str(&text),
+            (_, node) => unlinked.push(node)
+        }
+    }
+
+    unlinked
+}
+
+/// Match Code
+///
+/// # Description
+/// Match a code span. An unmatched backtick run is kept as text
+///
+/// # Arguments
+/// * `rest` &str
+///
+/// # Return
+/// (InlineNode, usize)
+fn match_code(rest: &str) -> (InlineNode, usize) {
+    let run = rest.len() - rest.trim_start_matches('`').len();
+    let delimiter = &rest[..run];
+    let mut search = run;
+
+    while let Some(pos) = rest[search..].find(delimiter) {
+        let start = search + pos;
+        let closing = rest[start..].len() - rest[start..].trim_start_matches('`').len();
+        if closing == run {
+            let code = &rest[run..start];
+            // strip one space on both side i.e `` `code` ``
+            let code = if code.len() > 2 && code.starts_with(' ') && code.ends_with(' ') {
+                &code[1..code.len() - 1]
+            } else {
+                code
+            };
+
+            return (InlineNode::Code(code.to_string()), start + run);
+        }
+
+        search = start + closing;
+    }
+
+    (InlineNode::Text(delimiter.to_string()), run)
+}
+
+/// Match Link
+///
+/// # Description
+/// Match a link `[label](url "title")`. The label may contain nested brackets. The destination
+/// lies between the `(` following the label and the last character of the match
+///
+/// # Arguments
+/// * `rest` &str
+///
+/// # Return
+/// Option<(&str, String, usize)> the label, the url and the length of the match
+pub fn match_link(rest: &str) -> Option<(&str, String, usize)> {
+    let label_end = find_closing(rest, '[', ']')?;
+    if !rest[label_end + 1..].starts_with('(') {
+        return None;
+    }
+
+    let dest_start = label_end + 1;
+    let dest_end = dest_start + find_closing(&rest[dest_start..], '(', ')')?;
+    let destination = rest[dest_start + 1..dest_end].trim();
+    let url = destination
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string();
+
+    Some((&rest[1..label_end], url, dest_end + 1))
+}
+
+/// Get Link Title
+///
+/// # Description
+/// Get the title following the url of a matched link i.e `"title"`, `'title'` or `(title)`
+///
+/// # Arguments
+/// * `link` &str the link returned by `match_link`
+///
+/// # Return
+/// Option<String>
+fn get_link_title(link: &str) -> Option<String> {
+    let label_end = find_closing(link, '[', ']')?;
+    let destination = link.get(label_end + 2..link.len() - 1)?.trim();
+    let url_end = match destination.strip_prefix('<') {
+        Some(rest) => rest.find('>')? + 2,
+        None => destination.find(char::is_whitespace)?
+    };
+
+    let title = destination[url_end..].trim();
+    let quoted = title.len() >= 2 && matches!(
+        (title.chars().next(), title.chars().last()),
+        (Some('"'), Some('"')) | (Some('\''), Some('\'')) | (Some('('), Some(')'))
+    );
+
+    if !quoted {
+        return None;
+    }
+
+    let mut unescaped = String::with_capacity(title.len());
+    let mut chars = title[1..title.len() - 1].chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(next) if c == '\\' && next.is_ascii_punctuation() => {},
+            _ => unescaped.push(c)
+        }
+    }
+
+    Some(unescaped)
+}
+
+/// Find Closing
+///
+/// # Description
+/// Find the index of the closing character matching the opening character at the start of the content
+///
+/// # Arguments
+/// * `content` &str
+/// * `open` char
+/// * `close` char
+///
+/// # Return
+/// Option<usize>
+pub fn find_closing(content: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+
+    for (idx, c) in content.char_indices() {
+        match c {
+            '\\' if !escaped => {
+                escaped = true;
+                continue;
+            },
+            c if c == open && !escaped => depth += 1,
+            c if c == close && !escaped => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            },
+            _ => {}
+        }
+
+        escaped = false;
+    }
+
+    None
+}
+
+/// Match Emphasis
+///
+/// # Description
+/// Match a strong or an emphasis delimited by * or _. An _ within a word i.e snake_case
+/// doesn't open nor close an emphasis
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+/// * `delimiter` char
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Option<(InlineNode, usize)>
+fn match_emphasis(content: &str, idx: usize, delimiter: char, options: &ParserOptions) -> Option<(InlineNode, usize)> {
+    let run = get_run_length(&content[idx..], delimiter);
+    let before = content[..idx].chars().last();
+    let after = content[idx + run..].chars().next();
+
+    // left flanking
+    if after.is_none_or(char::is_whitespace) {
+        return None;
+    }
+
+    if delimiter == '_' && before.is_some_and(char::is_alphanumeric) {
+        return None;
+    }
+
+    let size = run.min(3);
+    let start = idx + size;
+    let end = find_closing_delimiter(content, start, delimiter, size, options)?;
+    let children = get_inline_nodes(&content[start..end], options);
+    let node = match size {
+        1 => InlineNode::Emphasis(children),
+        2 => InlineNode::Strong(children),
+        _ => InlineNode::Strong(vec![InlineNode::Emphasis(children)])
+    };
+
+    Some((node, end + size - idx))
+}
+
+/// Find Closing Delimiter
+///
+/// # Description
+/// Find the closing delimiter run of the same size. Code spans, inline math and escaped punctuations
+/// are skipped
+///
+/// # Arguments
+/// * `content` &str
+/// * `from` usize
+/// * `delimiter` char
+/// * `size` usize
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Option<usize>
+fn find_closing_delimiter(content: &str, from: usize, delimiter: char, size: usize, options: &ParserOptions) -> Option<usize> {
+    let mut idx = from;
+
+    while idx < content.len() {
+        let rest = &content[idx..];
+        if rest.starts_with('`') {
+            let (_, len) = match_code(rest);
+            idx += len;
+            continue;
+        }
+
+        if let Some((_, len)) = math::get_inline_math_at(content, idx).filter(|_| options.math) {
+            idx += len;
+            continue;
+        }
+
+        // an escaped delimiter i.e \* doesn't close the emphasis
+        if let Some(escaped) = rest.strip_prefix('\\').and_then(|r| r.chars().next()).filter(char::is_ascii_punctuation) {
+            idx += 1 + escaped.len_utf8();
+            continue;
+        }
+
+        if rest.starts_with(delimiter) {
+            let run = get_run_length(rest, delimiter);
+            let before = content[..idx].chars().last();
+            let after = content[idx + run..].chars().next();
+            // right flanking
+            let flanking = idx > from
+                && before.is_some_and(|c| !c.is_whitespace())
+                && (delimiter != '_' || !after.is_some_and(char::is_alphanumeric));
+
+            if flanking && run >= size && (size > 1 || run != 2) {
+                return Some(idx);
+            }
+
+            idx += run;
+            continue;
+        }
+
+        idx += rest.chars().next().map_or(1, |c| c.len_utf8());
+    }
+
+    None
+}
+
+/// Get Run Length
+///
+/// # Description
+/// Count the number of consecutive delimiter at the start of the content
+///
+/// # Arguments
+/// * `content` &str
+/// * `delimiter` char
+///
+/// # Return
+/// usize
+fn get_run_length(content: &str, delimiter: char) -> usize {
+    content.len() - content.trim_start_matches(delimiter).len()
+}
+
+/// Flush Text
+///
+/// # Description
+/// Push the pending text as a text node
+///
+/// # Arguments
+/// * `nodes` &mut Vec<InlineNode>
+/// * `text` &mut String
+fn flush_text(nodes: &mut Vec<InlineNode>, text: &mut String) {
+    if !text.is_empty() {
+        nodes.push(InlineNode::Text(std::mem::take(text)));
+    }
+}
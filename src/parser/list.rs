@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use lazy_static::lazy_static;
 use regex::Regex;
 use crate::token::tag::{TagToken, TagOperator};
@@ -18,7 +19,7 @@ lazy_static!{
 ///
 /// # Return
 /// Option<TagToken>
-pub fn get_any_list(content: &str) -> Option<TagToken> {
+pub fn get_any_list(content: &str) -> Option<TagToken<'_>> {
     let unordered_list = get_unordered_list_token(content);
     if unordered_list.is_some() {
         return unordered_list;
@@ -42,21 +43,21 @@ pub fn get_any_list(content: &str) -> Option<TagToken> {
 ///
 /// # Return
 /// Option<TagToken>
-fn get_ordered_list_token(content: &str) -> Option<TagToken> {
+fn get_ordered_list_token(content: &str) -> Option<TagToken<'_>> {
     let has = ORDERED_LIST.is_match(content);
     if !has {
         return None;
     }
 
     let trimmed_content = content
-        .trim_start_matches(|c: char| c.is_numeric() && c == '.')
-        .trim()
-        .to_string();
+        .trim_start_matches(|c: char| c.is_numeric())
+        .trim_start_matches('.')
+        .trim();
 
     Some(
         TagToken {
             operator: TagOperator::OrderedList,
-            content: trimmed_content,
+            content: Cow::Borrowed(trimmed_content),
             ..Default::default()
         }
     )
@@ -72,18 +73,24 @@ fn get_ordered_list_token(content: &str) -> Option<TagToken> {
 ///
 /// # Return
 /// Option<TagToken>
-fn get_unordered_list_token(content: &str) -> Option<TagToken> {
-    let has = UNORDED_LIST.is_match(content);
+fn get_unordered_list_token(content: &str) -> Option<TagToken<'_>> {
+    // only the first alternative of the pattern is anchored, the list has to start with its marker
+    let has = UNORDED_LIST.find(content).is_some_and(|marker| marker.start() == 0);
     if !has {
         return None;
     }
 
-    let trimmed = UNORDED_LIST.replace_all(content, "");
+    // the marker is borrowed out of the line unless other markers are removed from the content
+    let mut markers = UNORDED_LIST.find_iter(content);
+    let trimmed = match (markers.next(), markers.next()) {
+        (Some(marker), None) if marker.start() == 0 => Cow::Borrowed(content[marker.end()..].trim()),
+        _ => Cow::Owned(UNORDED_LIST.replace_all(content, "").trim().to_string())
+    };
 
     Some(
         TagToken {
             operator: TagOperator::UnorderedList,
-            content: trimmed.as_ref().trim().to_string(),
+            content: trimmed,
             ..Default::default()
         }
     )
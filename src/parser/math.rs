@@ -0,0 +1,258 @@
+use std::borrow::Cow;
+use std::clone::Clone;
+use super::text_style::{TextOption, mask_code_spans, strip_style};
+use crate::token::tag::{TagToken, TagOperator};
+use crate::options::ParserOptions;
+
+// Delimiter of an inline math i.e $x$
+const INLINE_MATH: char = '$';
+// Delimiter of a display math block i.e $$x$$
+const DISPLAY_MATH: &str = "$$";
+// Info string of a math fenced code block
+const MATH_FENCE: &str = "```math";
+// Closing of a math fenced code block
+const FENCE_END: &str = "```";
+
+/// Math Delimiter
+#[derive(Debug, PartialEq, Clone)]
+pub enum MathDelimiter {
+    // $$ ... $$
+    Dollar,
+    // ```math ... ```
+    Fence
+}
+
+/// Get Inline Math
+///
+/// # Description
+/// Get the inline math i.e `$x_1$` or `$$x_1$$` of a line. The opening $ must be followed by a non space
+/// character and the closing $ must be preceded by a non space character and not followed by a digit.
+/// The dollars within the inline code are skipped
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Option<Vec<TextOption>>
+pub fn get_inline_math(content: &str) -> Option<Vec<TextOption<'_>>> {
+    let masked = mask_code_spans(content);
+    let mut maths = Vec::new();
+    let mut idx = 0;
+
+    while idx < masked.len() {
+        match get_inline_math_at(&masked, idx) {
+            Some((word, len)) => {
+                let delimiter = (len - word.len()) / 2;
+                maths.push(TextOption {
+                    word: Cow::Borrowed(&content[idx + delimiter..idx + delimiter + word.len()]),
+                    col: Some(idx)
+                });
+                idx += len;
+            },
+            None => idx += masked[idx..].chars().next().map_or(1, |c| c.len_utf8())
+        }
+    }
+
+    if maths.is_empty() {
+        return None;
+    }
+
+    Some(maths)
+}
+
+/// Get Inline Math At
+///
+/// # Description
+/// Match an inline math starting at the index of the content. A $$x$$ within a text is an inline math
+/// as well
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+///
+/// # Return
+/// Option<(&str, usize)> the math content and the length of the match
+pub fn get_inline_math_at(content: &str, idx: usize) -> Option<(&str, usize)> {
+    let rest = &content[idx..];
+    if !rest.starts_with(INLINE_MATH) {
+        return None;
+    }
+
+    // escaped dollar or closing of a display math
+    if content[..idx].ends_with('\\') || content[..idx].ends_with(INLINE_MATH) {
+        return None;
+    }
+
+    if let Some(inner) = rest.strip_prefix(DISPLAY_MATH) {
+        let pos = inner.find(DISPLAY_MATH)?;
+        let word = &inner[..pos];
+        if word.trim().is_empty() {
+            return None;
+        }
+
+        return Some((word, pos + DISPLAY_MATH.len() * 2));
+    }
+
+    let inner = &rest[1..];
+    if inner.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    for (pos, c) in inner.char_indices() {
+        if c != INLINE_MATH || pos == 0 {
+            continue;
+        }
+
+        let word = &inner[..pos];
+        let next = inner[pos + 1..].chars().next();
+        if word.ends_with(char::is_whitespace) || word.ends_with('\\') || next.is_some_and(|n| n.is_ascii_digit()) {
+            continue;
+        }
+
+        return Some((word, pos + 2));
+    }
+
+    None
+}
+
+/// Mask Math
+///
+/// # Description
+/// Replace the inline math by whitespaces in order to exempt them from the emphasis processing.
+/// The length of the content is kept so that the column of the other metas aren't shifted
+///
+/// # Arguments
+/// * `content` &str
+/// * `maths` &[TextOption]
+///
+/// # Return
+/// String
+pub fn mask_math(content: &str, maths: &[TextOption]) -> String {
+    let mut masked = content.to_string();
+    for math in maths {
+        if let Some(col) = math.col {
+            let len = get_math_len(content, col, &math.word);
+            masked.replace_range(col..col + len, &" ".repeat(len));
+        }
+    }
+
+    masked
+}
+
+/// Sanitize Math Content
+///
+/// # Description
+/// Sanitize the content while keeping the inline math untouched
+///
+/// # Arguments
+/// * `content` &str
+/// * `maths` &[TextOption]
+/// * `options` &ParserOptions
+///
+/// # Return
+/// String
+pub fn sanitize_math_content(content: &str, maths: &[TextOption], options: &ParserOptions) -> String {
+    let mut sanitized = String::new();
+    let mut start = 0;
+
+    for math in maths {
+        if let Some(col) = math.col {
+            let end = col + get_math_len(content, col, &math.word);
+            sanitized.push_str(&strip_style(&content[start..col], options));
+            sanitized.push_str(&content[col..end]);
+            start = end;
+        }
+    }
+
+    sanitized.push_str(&strip_style(&content[start..], options));
+    sanitized.trim().to_string()
+}
+
+/// Get Math Len
+///
+/// # Description
+/// Get the length of an inline math along with its $ or $$ delimiters
+///
+/// # Arguments
+/// * `content` &str
+/// * `col` usize
+/// * `word` &str
+///
+/// # Return
+/// usize
+fn get_math_len(content: &str, col: usize, word: &str) -> usize {
+    if content[col..].starts_with(DISPLAY_MATH) {
+        return word.len() + DISPLAY_MATH.len() * 2;
+    }
+
+    word.len() + 2
+}
+
+/// Get Display Math Start
+///
+/// # Description
+/// Get the display math tag token started by the line i.e $$ or ```math. A line whose first closing
+/// $$ isn't at its end doesn't start a block
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// Option<(TagToken, MathDelimiter, bool)> the token, the delimiter and whether the block is already closed
+pub fn get_display_math_start(line: &str) -> Option<(TagToken<'_>, MathDelimiter, bool)> {
+    let (content, delimiter, closed) = if line == MATH_FENCE {
+        ("", MathDelimiter::Fence, false)
+    } else if let Some(rest) = line.strip_prefix(DISPLAY_MATH) {
+        match rest.find(DISPLAY_MATH) {
+            Some(pos) if pos + DISPLAY_MATH.len() == rest.len() => (rest[..pos].trim(), MathDelimiter::Dollar, true),
+            // $$a$$ followed by text is an inline math within a paragraph
+            Some(_) => return None,
+            None => (rest.trim(), MathDelimiter::Dollar, false)
+        }
+    } else {
+        return None;
+    };
+
+    let token = TagToken {
+        operator: TagOperator::DisplayMath,
+        content: Cow::Borrowed(content),
+        ..Default::default()
+    };
+
+    Some((token, delimiter, closed))
+}
+
+/// Append Display Math Line
+///
+/// # Description
+/// Append a line to a display math block
+///
+/// # Arguments
+/// * `token` &mut TagToken
+/// * `line` &str
+/// * `delimiter` &MathDelimiter
+///
+/// # Return
+/// bool whether the line close the block
+pub fn append_display_math_line(token: &mut TagToken, line: &str, delimiter: &MathDelimiter) -> bool {
+    let line = line.trim();
+    let (content, closed) = match delimiter {
+        MathDelimiter::Fence if line == FENCE_END => ("", true),
+        MathDelimiter::Fence => (line, false),
+        MathDelimiter::Dollar => match line.strip_suffix(DISPLAY_MATH) {
+            Some(inner) => (inner.trim(), true),
+            None => (line, false)
+        }
+    };
+
+    if !content.is_empty() {
+        let block = token.content.to_mut();
+        if !block.is_empty() {
+            block.push('\n');
+        }
+
+        block.push_str(content);
+    }
+
+    closed
+}
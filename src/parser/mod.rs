@@ -7,12 +7,21 @@
 /// # Parser
 /// First we need to define the main token that we need to use in order to get the basic match
 /// Matchers that we'll do for each line:
-/// - # -> heading 
+/// - # -> heading (with an optional {#custom-id})
 /// - --- / *** / ___ -> horizontal rules
 /// - * / - / + -> unordered list
 /// - <number>. -> ordered list
-/// - ``` -> code
+/// - ``` / ~~~ -> code
 /// - > -> blockquotes
+/// - [^label]: -> footnote definition
+/// - <tag> / <!-- --> -> html block
+/// - $$ / ```math -> display math (opt-in)
+/// - | a | b | followed by | --- | --- | -> table (opt-in)
+/// - :::name ... ::: / ::name -> container and leaf directives (opt-in)
+/// - : definition -> definition list (opt-in, `definition_list` feature)
+/// - *[abbr]: title -> abbreviation (opt-in, `abbreviation` feature)
+///
+/// A leading --- (YAML) or +++ (TOML) front matter is detected and excluded from the body
 ///
 /// Other token are gonna be treat as a content marker and will later be process by a content lexer
 /// The content parser will have to match the following regex for each line
@@ -21,9 +30,31 @@
 /// - ~~...~~ -> strikethrought
 /// - []() -> link
 /// - ![]() -> image
+/// - [^label] -> footnote reference
+/// - <tag> -> inline html
+/// - $...$ -> inline math (opt-in)
+/// - :name[label]{attrs} -> text directive (opt-in)
+/// - :emoji: / @user / #123 -> github extensions (opt-in)
+/// - "quotes" / -- / --- / ... -> typographic quotes, dashes and ellipsis (opt-in)
+///
+/// Custom block and inline syntaxes can be registered with the `BlockParserExt` / `InlineParserExt` extensions
+pub mod abbreviation;
 pub mod code;
+pub mod definition_list;
+pub mod directive;
+pub mod emoji_table;
+pub mod extension;
 pub mod external;
+pub mod footnote;
+pub mod front_matter;
+pub mod github;
 pub mod heading;
+pub mod html;
+pub mod inline;
 pub mod list;
+pub mod math;
 pub mod operator;
+pub mod slug;
+pub mod table;
 pub mod text_style;
+pub mod typography;
@@ -2,7 +2,6 @@
 ///
 /// # Description
 /// List of operator available in Markdown
-
 pub mod bytes {
     // Operator -> #
     pub const HEADING: u8 = 35;
@@ -24,8 +23,8 @@ pub mod bytes {
     /// * `content` &str
     ///
     /// # Return
-    /// String
-    pub fn trim_matches_content(content: &str) -> String {
+    /// &str
+    pub fn trim_matches_content(content: &str) -> &str {
         let matches: &[char] = &[
             HEADING as char,
             UNORDERED_MUL as char,
@@ -35,7 +34,7 @@ pub mod bytes {
         ];
 
 
-        content.trim_matches(matches).trim().to_string()
+        content.trim_matches(matches).trim()
     }
 
 }
@@ -0,0 +1,149 @@
+use std::fmt::Debug;
+use std::collections::{BTreeMap, HashMap};
+use crate::token::TextualToken;
+use crate::token::tag::TagOperator;
+use crate::options::ParserOptions;
+use super::inline::{get_inline_nodes, get_plain_text};
+
+/// Slugger
+///
+/// # Description
+/// Generate the anchor of the headings. The default implementation follow GitHub, other flavours
+/// can be plugged through the `slugger` field of the ParserOptions
+pub trait Slugger: Debug + Send + Sync {
+    /// Slugify
+    ///
+    /// # Description
+    /// Turn the plain text of a heading into a slug
+    ///
+    /// # Arguments
+    /// * `text` &str
+    ///
+    /// # Return
+    /// String
+    fn slugify(&self, text: &str) -> String;
+
+    /// Suffix
+    ///
+    /// # Description
+    /// Build the slug of the nth duplicate of a slug i.e `intro-1`
+    ///
+    /// # Arguments
+    /// * `slug` &str
+    /// * `count` usize
+    ///
+    /// # Return
+    /// String
+    fn suffix(&self, slug: &str, count: usize) -> String {
+        format!("{}-{}", slug, count)
+    }
+}
+
+/// Github Slugger
+///
+/// Lowercase, strip the punctuation and replace each space by a `-`
+#[derive(Debug, Default, Clone)]
+pub struct GithubSlugger;
+
+impl Slugger for GithubSlugger {
+    fn slugify(&self, text: &str) -> String {
+        text.to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-' || *c == ' ')
+            .map(|c| if c == ' ' { '-' } else { c })
+            .collect()
+    }
+}
+
+/// Gitlab Slugger
+///
+/// Same as GitHub except that consecutive `-` are squeezed into one
+#[derive(Debug, Default, Clone)]
+pub struct GitlabSlugger;
+
+impl Slugger for GitlabSlugger {
+    fn slugify(&self, text: &str) -> String {
+        let slug = GithubSlugger.slugify(text.trim());
+        let mut squeezed = String::with_capacity(slug.len());
+
+        for c in slug.chars() {
+            if c == '-' && squeezed.ends_with('-') {
+                continue;
+            }
+
+            squeezed.push(c);
+        }
+
+        squeezed
+    }
+}
+
+/// MdBook Slugger
+///
+/// Keep the alphanumeric characters, `_` and `-`, and replace every whitespace by a `-`
+#[derive(Debug, Default, Clone)]
+pub struct MdBookSlugger;
+
+impl Slugger for MdBookSlugger {
+    fn slugify(&self, text: &str) -> String {
+        text.trim()
+            .to_lowercase()
+            .chars()
+            .filter_map(|c| match c {
+                c if c.is_alphanumeric() || c == '_' || c == '-' => Some(c),
+                c if c.is_whitespace() => Some('-'),
+                _ => None
+            })
+            .collect()
+    }
+}
+
+/// Set Heading Slugs
+///
+/// # Description
+/// Set the slug of every heading of the document. Explicit `{#custom-id}` are kept as is and
+/// reserved, the generated slugs are made unique by appending `-1`, `-2`...
+///
+/// # Arguments
+/// * `tokens` &mut BTreeMap<usize, TextualToken>
+/// * `options` &ParserOptions
+pub fn set_heading_slugs(tokens: &mut BTreeMap<usize, TextualToken>, options: &ParserOptions) {
+    let slugger: &dyn Slugger = options.slugger.as_deref().unwrap_or(&GithubSlugger);
+    // the anchors don't depend on the typography of the heading
+    let options = &ParserOptions { smart_punctuation: false, ..options.clone() };
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    let ids = tokens
+        .values()
+        .filter_map(|t| t.tag.as_ref())
+        .filter(|t| t.operator == TagOperator::Heading)
+        .filter_map(|t| t.metas.as_ref().and_then(|m| m.id.clone()));
+
+    // the explicit ids are reserved
+    for id in ids {
+        occurrences.insert(id, 0);
+    }
+
+    for tag in tokens.values_mut().filter_map(|t| t.tag.as_mut()) {
+        if tag.operator != TagOperator::Heading {
+            continue;
+        }
+
+        let text = get_plain_text(&get_inline_nodes(&tag.content, options));
+        let metas = tag.metas.get_or_insert_with(Default::default);
+        if let Some(id) = metas.id.as_ref() {
+            metas.slug = Some(id.clone());
+            continue;
+        }
+
+        let base = slugger.slugify(&text);
+        let mut slug = base.clone();
+        while occurrences.contains_key(&slug) {
+            let count = occurrences.entry(base.clone()).or_insert(0);
+            *count += 1;
+            slug = slugger.suffix(&base, *count);
+        }
+
+        occurrences.insert(slug.clone(), 0);
+        metas.slug = Some(slug);
+    }
+}
@@ -0,0 +1,152 @@
+use std::borrow::Cow;
+use std::clone::Clone;
+use crate::token::tag::{TagToken, TagOperator, TagMeta};
+
+// Separator of the cells of a row
+const CELL_SEPARATOR: char = '|';
+
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TableAlignment {
+    #[default]
+    None,
+    Left,
+    Center,
+    Right
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableMeta {
+    pub alignments: Vec<TableAlignment>,
+    pub header: Vec<String>,
+    // Rows of the body, each row has as many cells as the header
+    pub rows: Vec<Vec<String>>
+}
+
+/// Get Delimiter Row
+///
+/// # Description
+/// Get the alignment of the columns from the delimiter row of a table i.e `| :--- | :---: | ---: |`.
+/// The row must contain a pipe in order not to be mistaken for a horizontal rule
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// Option<Vec<TableAlignment>>
+pub fn get_delimiter_row(line: &str) -> Option<Vec<TableAlignment>> {
+    if !line.contains(CELL_SEPARATOR) {
+        return None;
+    }
+
+    split_row(line)
+        .iter()
+        .map(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+                return None;
+            }
+
+            Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => TableAlignment::Center,
+                (true, false) => TableAlignment::Left,
+                (false, true) => TableAlignment::Right,
+                (false, false) => TableAlignment::None
+            })
+        })
+        .collect()
+}
+
+/// Split Row
+///
+/// # Description
+/// Split a row into its trimmed cells. The leading and the trailing pipes are optional and the
+/// escaped pipes `\|` are part of the cell
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// Vec<String>
+pub fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix(CELL_SEPARATOR).unwrap_or(line);
+    let line = match line.strip_suffix(CELL_SEPARATOR) {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => line
+    };
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&CELL_SEPARATOR) => {
+                cell.push(CELL_SEPARATOR);
+                chars.next();
+            },
+            CELL_SEPARATOR => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c)
+        }
+    }
+
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+/// Get Table Token
+///
+/// # Description
+/// Create a table from its header row when the header has as many cells as the delimiter row
+///
+/// # Arguments
+/// * `header` &str
+/// * `delimiter` &str
+/// * `alignments` Vec<TableAlignment>
+///
+/// # Return
+/// Option<TagToken<'static>>
+pub fn get_table_token(header: &str, delimiter: &str, alignments: Vec<TableAlignment>) -> Option<TagToken<'static>> {
+    let cells = split_row(header);
+    if cells.len() != alignments.len() {
+        return None;
+    }
+
+    Some(TagToken {
+        operator: TagOperator::Table,
+        content: Cow::Owned(format!("{}\n{}", header.trim(), delimiter.trim())),
+        metas: Some(
+            TagMeta {
+                table: Some(TableMeta {
+                    alignments,
+                    header: cells,
+                    rows: Vec::new()
+                }),
+                ..Default::default()
+            }
+        ),
+        ..Default::default()
+    })
+}
+
+/// Append Table Row
+///
+/// # Description
+/// Append a row to the body of the table. The excess cells are dropped and the missing ones are empty
+///
+/// # Arguments
+/// * `token` &mut TagToken
+/// * `line` &str
+pub fn append_table_row(token: &mut TagToken, line: &str) {
+    token.content.to_mut().push('\n');
+    token.content.to_mut().push_str(line.trim());
+
+    if let Some(table) = token.metas.as_mut().and_then(|m| m.table.as_mut()) {
+        let mut cells = split_row(line);
+        cells.resize(table.alignments.len(), String::new());
+        table.rows.push(cells);
+    }
+}
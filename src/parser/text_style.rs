@@ -1,7 +1,9 @@
+use std::borrow::Cow;
 use std::clone::Clone;
 use regex::Regex;
 use lazy_static::lazy_static;
-use super::external;
+use super::{abbreviation, external, footnote, github, html, math};
+use crate::options::ParserOptions;
 use super::operator::pattern;
 
 lazy_static!{
@@ -14,20 +16,74 @@ lazy_static!{
 }
 
 #[derive(Debug, Clone)]
-pub struct TextOption {
-    pub word: String,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextOption<'a> {
+    pub word: Cow<'a, str>,
     pub col: Option<usize>
 }
 
 /// TextMetas
 #[derive(Debug, Clone)]
-pub struct TextMetas {
-    pub images: Option<Vec<external::ImageMeta>>,
-    pub links: Option<Vec<external::LinkMeta>>,
-    pub bold: Option<Vec<TextOption>>,
-    pub italic: Option<Vec<TextOption>>,
-    pub strike: Option<Vec<TextOption>>,
-    pub inline_code: Option<Vec<TextOption>>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextMetas<'a> {
+    pub images: Option<Vec<external::ImageMeta<'a>>>,
+    pub links: Option<Vec<external::LinkMeta<'a>>>,
+    pub bold: Option<Vec<TextOption<'a>>>,
+    pub italic: Option<Vec<TextOption<'a>>>,
+    pub strike: Option<Vec<TextOption<'a>>>,
+    pub inline_code: Option<Vec<TextOption<'a>>>,
+    pub footnotes: Option<Vec<footnote::FootnoteRef>>,
+    pub html: Option<Vec<TextOption<'a>>>,
+    pub inline_math: Option<Vec<TextOption<'a>>>,
+    pub emojis: Option<Vec<github::Emoji>>,
+    pub mentions: Option<Vec<github::Mention>>,
+    pub issue_refs: Option<Vec<github::IssueRef>>,
+    pub abbreviations: Option<Vec<abbreviation::Abbreviation>>
+}
+
+impl TextOption<'_> {
+    /// Into Owned
+    ///
+    /// # Description
+    /// Copy the borrowed word
+    ///
+    /// # Return
+    /// TextOption<'static>
+    pub fn into_owned(self) -> TextOption<'static> {
+        TextOption {
+            word: Cow::Owned(self.word.into_owned()),
+            col: self.col
+        }
+    }
+}
+
+impl TextMetas<'_> {
+    /// Into Owned
+    ///
+    /// # Description
+    /// Copy the borrowed words and urls in order to keep the metas beyond the lifetime of the markdown
+    ///
+    /// # Return
+    /// TextMetas<'static>
+    pub fn into_owned(self) -> TextMetas<'static> {
+        let options = |options: Option<Vec<TextOption>>| options.map(|o| o.into_iter().map(TextOption::into_owned).collect());
+
+        TextMetas {
+            images: self.images.map(|images| images.into_iter().map(external::ImageMeta::into_owned).collect()),
+            links: self.links.map(|links| links.into_iter().map(external::LinkMeta::into_owned).collect()),
+            bold: options(self.bold),
+            italic: options(self.italic),
+            strike: options(self.strike),
+            inline_code: options(self.inline_code),
+            footnotes: self.footnotes,
+            html: options(self.html),
+            inline_math: options(self.inline_math),
+            emojis: self.emojis,
+            mentions: self.mentions,
+            issue_refs: self.issue_refs,
+            abbreviations: self.abbreviations
+        }
+    }
 }
 
 /// Get Test Metas
@@ -36,53 +92,226 @@ pub struct TextMetas {
 /// Get token for text object
 ///
 /// # Arguments
-/// * TextMetas
-pub fn get_text_metas(content: &str) -> Option<TextMetas> {
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Option<TextMetas>
+pub fn get_text_metas<'a>(content: &'a str, options: &ParserOptions) -> Option<TextMetas<'a>> {
+    // math are exempt from the processing of the other metas
+    let inline_math = if options.math {
+        math::get_inline_math(content)
+    } else {
+        None
+    };
+
+    match inline_math {
+        // the metas of the masked content can't borrow the line
+        Some(maths) => {
+            let masked = math::mask_math(content, &maths);
+            let mut metas = get_style_metas(&masked, Some(maths), options).into_owned();
+            let styles = metas.bold.iter_mut().chain(metas.italic.iter_mut()).chain(metas.strike.iter_mut());
+            for option in styles.flatten() {
+                unmask_word(content, &masked, option);
+            }
+
+            Some(metas)
+        },
+        None => Some(get_style_metas(content, None, options))
+    }
+}
+
+/// Unmask Word
+///
+/// # Description
+/// Restore the inline math of a style whose word was read from the masked content i.e *a $b$*
+///
+/// # Arguments
+/// * `content` &str
+/// * `masked` &str
+/// * `option` &mut TextOption
+fn unmask_word(content: &str, masked: &str, option: &mut TextOption) {
+    let Some(col) = option.col else {
+        return;
+    };
+
+    if let Some(start) = masked[col..].find(option.word.as_ref()).map(|pos| col + pos) {
+        option.word = Cow::Owned(content[start..start + option.word.len()].to_string());
+    }
+}
+
+/// Get Style Metas
+///
+/// # Description
+/// Get the metas of a content whose inline math are already masked
+///
+/// # Arguments
+/// * `content` &str
+/// * `inline_math` Option<Vec<TextOption>>
+/// * `options` &ParserOptions
+///
+/// # Return
+/// TextMetas
+fn get_style_metas<'a>(content: &'a str, inline_math: Option<Vec<TextOption<'a>>>, options: &ParserOptions) -> TextMetas<'a> {
     // get images token
     let images = external::get_image_metas(content);
     let links = external::get_link_metas(content, &images);
-    let strike = get_kind_content(content, pattern::STRIKE, &STRIKE_RE);
+    let strike = if options.strikethrough {
+        get_kind_content(content, pattern::STRIKE, &STRIKE_RE)
+    } else {
+        None
+    };
+
     let bold_star = get_kind_content(content, pattern::BOLD_STAR, &BOLD_ST_RE);
     let bold_under = get_kind_content(content, pattern::BOLD_UNDER, &BOLD_UN_RE);
     let italic_star = get_kind_content(content, pattern::ITALIC_STAR, &ITALIC_RE);
     let italic_under = get_kind_content(content, pattern::ITALIC_UN, &ITALIC_UN_RE);
     let inline_code = get_kind_content(content, pattern::CODE_PATTERN, &INLINE_CODE);
+    let footnotes = if options.footnotes {
+        footnote::get_footnote_refs(content)
+    } else {
+        None
+    };
+
+    let html = if options.drop_html {
+        None
+    } else {
+        html::get_inline_html(content)
+    };
+
+    let github::GithubMetas { emojis, mentions, issue_refs } = match options.github {
+        Some(_) => github::get_github_metas(content),
+        None => github::GithubMetas::default()
+    };
 
     let bold = merge_option_vec::<TextOption>(bold_star, bold_under);
     let italic = merge_option_vec(italic_star, italic_under);
 
-    Some(TextMetas {
+    TextMetas {
         images,
         links,
         bold,
         italic,
         strike,
-        inline_code
-    })
-
+        inline_code,
+        footnotes,
+        html,
+        inline_math,
+        emojis,
+        mentions,
+        issue_refs,
+        // set once the whole document is parsed
+        abbreviations: None
+    }
 }
 
 /// Sanitize Content
 ///
 /// # Description
-/// Clean the content of any markdown style character
+/// Clean the content of any markdown style character. The strike characters are kept when the
+/// strikethrough is disabled
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Cow<str> borrowing the content when there isn't any style character
+pub fn sanitze_content<'a>(content: &'a str, options: &ParserOptions) -> Cow<'a, str> {
+    if !content.contains(['*', '_', '~', '`']) {
+        return Cow::Borrowed(content.trim());
+    }
+
+    Cow::Owned(strip_style(content, options).trim().to_string())
+}
+
+/// Mask Code Spans
+///
+/// # Description
+/// Replace the inline code by whitespaces. The length of the content is kept so that the column
+/// of the metas aren't shifted. A code span is closed by a backtick run of the same length i.e ``a`b``
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// String
+pub fn mask_code_spans(content: &str) -> String {
+    let mut masked = String::with_capacity(content.len());
+    let mut idx = 0;
+
+    while let Some(pos) = content[idx..].find('`') {
+        let start = idx + pos;
+        let run = content[start..].len() - content[start..].trim_start_matches('`').len();
+        masked.push_str(&content[idx..start]);
+
+        match find_closing_run(content, start + run, run) {
+            Some(end) => {
+                masked.push_str(&" ".repeat(end + run - start));
+                idx = end + run;
+            },
+            None => {
+                masked.push_str(&content[start..start + run]);
+                idx = start + run;
+            }
+        }
+    }
+
+    masked.push_str(&content[idx..]);
+    masked
+}
+
+/// Find Closing Run
+///
+/// # Description
+/// Find the backtick run of the given length closing a code span
+///
+/// # Arguments
+/// * `content` &str
+/// * `from` usize
+/// * `run` usize
+///
+/// # Return
+/// Option<usize>
+fn find_closing_run(content: &str, from: usize, run: usize) -> Option<usize> {
+    let mut idx = from;
+
+    while let Some(pos) = content[idx..].find('`') {
+        let start = idx + pos;
+        let closing = content[start..].len() - content[start..].trim_start_matches('`').len();
+        if closing == run {
+            return Some(start);
+        }
+
+        idx = start + closing;
+    }
+
+    None
+}
+
+/// Strip Style
+///
+/// # Description
+/// Remove the markdown style characters without trimming the content
 ///
 /// # Arguments
 /// * `content` &str
+/// * `options` &ParserOptions
 ///
 /// # Return
 /// String
-pub fn sanitze_content(content: &str) -> String {
+pub fn strip_style(content: &str, options: &ParserOptions) -> String {
     let wobold = content.replace(pattern::BOLD_STAR, "");
     let woboldun = wobold.replace(pattern::BOLD_UNDER, "");
-    let wostrike = woboldun.replace(pattern::STRIKE, "");
+    let wostrike = if options.strikethrough {
+        woboldun.replace(pattern::STRIKE, "")
+    } else {
+        woboldun
+    };
     let woitalic = wostrike.replace(pattern::ITALIC_STAR, "");
     let woitalicun = woitalic.replace(pattern::ITALIC_UN, "");
-    let wocode = woitalicun.replace(pattern::CODE_PATTERN, "");
 
-    wocode
-        .trim()
-        .to_string()
+    woitalicun.replace(pattern::CODE_PATTERN, "")
 }
 
 /// Merge Option Vec
@@ -120,7 +349,7 @@ fn merge_option_vec<T>(a: Option<Vec<T>>, b: Option<Vec<T>>) -> Option<Vec<T>> {
 ///
 /// # Return
 /// Option<Vec<TextOption>>
-fn get_kind_content(content: &str, pattern: &str, re: &Regex) -> Option<Vec<TextOption>> {
+fn get_kind_content<'a>(content: &'a str, pattern: &str, re: &Regex) -> Option<Vec<TextOption<'a>>> {
     let captures = re.captures_iter(content);
     let k: Vec<TextOption> = captures
         .filter_map(|c| {
@@ -131,7 +360,7 @@ fn get_kind_content(content: &str, pattern: &str, re: &Regex) -> Option<Vec<Text
             }
 
             Some(TextOption {
-                word: word.to_string(),
+                word: Cow::Borrowed(word),
                 col: get_indices(pattern, word, content)    
             })
         })
@@ -0,0 +1,104 @@
+use super::external::is_within_url;
+
+// Typographic characters of the smart punctuation
+const LEFT_DOUBLE_QUOTE: char = '“';
+const RIGHT_DOUBLE_QUOTE: char = '”';
+const LEFT_SINGLE_QUOTE: char = '‘';
+const RIGHT_SINGLE_QUOTE: char = '’';
+const EN_DASH: char = '–';
+const EM_DASH: char = '—';
+const ELLIPSIS: char = '…';
+// Characters after which a quote opens i.e ("quoted")
+const OPENING_CONTEXT: &[char] = &['(', '[', '{', '<', EN_DASH, EM_DASH, LEFT_DOUBLE_QUOTE, LEFT_SINGLE_QUOTE];
+
+/// Get Smart Punctuation At
+///
+/// # Description
+/// Match a straight quote, a -- / --- dash or a ... ellipsis at the index of the content and return its
+/// typographic replacement. A quote opens when it follows a whitespace, an opening punctuation or an
+/// opening quote and is followed by a non whitespace character, it closes otherwise so that the apostrophes i.e don't
+/// are curled as closing quotes. The hyphens, dots and apostrophes of a bare url are kept as is
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+///
+/// # Return
+/// Option<(String, usize)> the replacement and the length of the match
+pub fn get_smart_punctuation_at(content: &str, idx: usize) -> Option<(String, usize)> {
+    let rest = &content[idx..];
+    let first = rest.chars().next()?;
+    // a double quote can't be part of an url i.e "https://example.com"
+    if !matches!(first, '"' | '\'' | '-' | '.') || (first != '"' && is_within_url(content, idx)) {
+        return None;
+    }
+
+    match first {
+        '"' | '\'' => {
+            let quote = match (first, is_opening_quote(content, idx)) {
+                ('"', true) => LEFT_DOUBLE_QUOTE,
+                ('"', false) => RIGHT_DOUBLE_QUOTE,
+                (_, true) => LEFT_SINGLE_QUOTE,
+                (_, false) => RIGHT_SINGLE_QUOTE
+            };
+
+            Some((quote.to_string(), 1))
+        },
+        '-' => {
+            let run = rest.len() - rest.trim_start_matches('-').len();
+            if run < 2 {
+                return None;
+            }
+
+            Some((get_dashes(run), run))
+        },
+        _ => rest.starts_with("...").then(|| (ELLIPSIS.to_string(), 3))
+    }
+}
+
+/// Is Opening Quote
+///
+/// # Description
+/// Check whether the quote at the index opens. A quote following an other quote opens along with
+/// it i.e "'nested'"
+///
+/// # Arguments
+/// * `content` &str
+/// * `idx` usize
+///
+/// # Return
+/// bool
+fn is_opening_quote(content: &str, idx: usize) -> bool {
+    if !content[idx + 1..].starts_with(|c: char| !c.is_whitespace()) {
+        return false;
+    }
+
+    match content[..idx].chars().next_back() {
+        Some(quote @ ('"' | '\'')) => is_opening_quote(content, idx - quote.len_utf8()),
+        before => before.is_none_or(|c| c.is_whitespace() || OPENING_CONTEXT.contains(&c))
+    }
+}
+
+/// Get Dashes
+///
+/// # Description
+/// Convert a run of hyphens into em and en dashes. A run is made of em dashes when its length is a
+/// multiple of 3, of en dashes when it's a multiple of 2, otherwise of em dashes followed by en dashes
+///
+/// # Arguments
+/// * `run` usize number of hyphens, at least 2
+///
+/// # Return
+/// String
+fn get_dashes(run: usize) -> String {
+    let (em, en) = match (run % 3, run % 2) {
+        (0, _) => (run / 3, 0),
+        (_, 0) => (0, run / 2),
+        (2, _) => (run / 3, 1),
+        _ => ((run - 4) / 3, 2)
+    };
+
+    let mut dashes = EM_DASH.to_string().repeat(em);
+    dashes.push_str(&EN_DASH.to_string().repeat(en));
+    dashes
+}
@@ -0,0 +1,618 @@
+use std::clone::Clone;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+use crate::error;
+use crate::options::ParserOptions;
+use crate::parser::code::{CodeOperator, get_info_string};
+use crate::parser::heading::HeadingLevel;
+use crate::parser::inline::{get_inline_nodes, get_plain_text, match_link};
+use crate::parser::text_style::mask_code_spans;
+use crate::section::{get_line_offsets, get_section_end};
+use crate::token::{self, TextualToken};
+use crate::token::code::CodeToken;
+use crate::token::tag::{TagToken, TagOperator};
+use crate::token::text::TextToken;
+use crate::visit::get_code_blocks;
+
+/// Document
+///
+/// # Description
+/// Parsed markdown along with its source, queried with `headings`, `links`, `code_blocks`, `find`...
+#[derive(Debug)]
+pub struct Document<'a> {
+    content: &'a str,
+    options: ParserOptions,
+    tokens: BTreeMap<usize, TextualToken<'a>>,
+    code_tokens: BTreeMap<usize, CodeToken<'a>>,
+    // Byte range of each line
+    offsets: Vec<Range<usize>>,
+    // Lines holding a textual or a code token
+    lines: BTreeSet<usize>,
+    // First and last line of each code block
+    code_blocks: BTreeMap<usize, usize>
+}
+
+/// Selection
+///
+/// # Description
+/// Lines of a document the queries are restricted to i.e a section
+#[derive(Debug, Clone, Copy)]
+pub struct Selection<'d> {
+    document: &'d Document<'d>,
+    pub start_line: usize,
+    pub end_line: usize
+}
+
+/// Node
+///
+/// # Description
+/// Node returned by the queries. The span is the byte range of the node in the source
+#[derive(Debug, Clone)]
+pub enum Node<'d> {
+    Heading(Heading<'d>),
+    // Any other block i.e list item, block quote, html block
+    Tag {
+        token: &'d TagToken<'d>,
+        line: usize,
+        span: Range<usize>
+    },
+    Text {
+        token: &'d TextToken<'d>,
+        line: usize,
+        span: Range<usize>
+    },
+    Link(Link<'d>),
+    Image(Image<'d>),
+    CodeBlock(CodeBlock<'d>)
+}
+
+#[derive(Debug, Clone)]
+pub struct Heading<'d> {
+    pub level: HeadingLevel,
+    // Title without the inline markdown
+    pub title: String,
+    pub slug: Option<&'d str>,
+    pub line: usize,
+    pub span: Range<usize>
+}
+
+#[derive(Debug, Clone)]
+pub struct Link<'d> {
+    pub title: &'d str,
+    pub url: &'d str,
+    pub line: usize,
+    pub span: Range<usize>
+}
+
+#[derive(Debug, Clone)]
+pub struct Image<'d> {
+    pub alt_text: &'d str,
+    pub url: &'d str,
+    pub line: usize,
+    pub span: Range<usize>
+}
+
+#[derive(Debug, Clone)]
+pub struct CodeBlock<'d> {
+    // Language of the fence
+    pub info: &'d str,
+    // Lines of code without the fences
+    pub lines: Vec<&'d str>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub span: Range<usize>
+}
+
+impl CodeBlock<'_> {
+    /// Code
+    ///
+    /// # Description
+    /// Return the lines of code joined by a line break
+    ///
+    /// # Return
+    /// String
+    pub fn code(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+impl Node<'_> {
+    /// Line
+    ///
+    /// # Description
+    /// Return the first line of the node
+    ///
+    /// # Return
+    /// usize
+    pub fn line(&self) -> usize {
+        match self {
+            Node::Heading(heading) => heading.line,
+            Node::Tag { line, .. } | Node::Text { line, .. } => *line,
+            Node::Link(link) => link.line,
+            Node::Image(image) => image.line,
+            Node::CodeBlock(block) => block.start_line
+        }
+    }
+
+    /// Span
+    ///
+    /// # Description
+    /// Return the byte range of the node in the source
+    ///
+    /// # Return
+    /// Range<usize>
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Node::Heading(heading) => heading.span.clone(),
+            Node::Tag { span, .. } | Node::Text { span, .. } => span.clone(),
+            Node::Link(link) => link.span.clone(),
+            Node::Image(image) => image.span.clone(),
+            Node::CodeBlock(block) => block.span.clone()
+        }
+    }
+}
+
+impl<'a> Document<'a> {
+    /// New
+    ///
+    /// # Description
+    /// Parse the content into a document
+    ///
+    /// # Arguments
+    /// * `content` &'a str
+    /// * `options` &ParserOptions
+    ///
+    /// # Return
+    /// Result<Document<'a>, error::ParserError>
+    pub fn new(content: &'a str, options: &ParserOptions) -> Result<Self, error::ParserError> {
+        let tokens = token::get_textual_tokens_with_options(content, options)?;
+        let code_tokens = token::get_code_tokens(content)?;
+        let code_blocks = get_code_blocks(&code_tokens)
+            .into_iter()
+            .map(|block| (*block.start(), *block.end()))
+            .collect();
+
+        Ok(Document {
+            content,
+            options: options.clone(),
+            lines: tokens.keys().chain(code_tokens.keys()).copied().collect(),
+            offsets: get_line_offsets(content),
+            tokens,
+            code_tokens,
+            code_blocks
+        })
+    }
+
+    /// Tokens
+    ///
+    /// # Description
+    /// Return the textual tokens of the document
+    ///
+    /// # Return
+    /// &BTreeMap<usize, TextualToken>
+    pub fn tokens(&self) -> &BTreeMap<usize, TextualToken<'a>> {
+        &self.tokens
+    }
+
+    /// Code Tokens
+    ///
+    /// # Description
+    /// Return the code tokens of the document
+    ///
+    /// # Return
+    /// &BTreeMap<usize, CodeToken>
+    pub fn code_tokens(&self) -> &BTreeMap<usize, CodeToken<'a>> {
+        &self.code_tokens
+    }
+
+    /// Select
+    ///
+    /// # Description
+    /// Return a selection of the whole document
+    ///
+    /// # Return
+    /// Selection
+    pub fn select(&self) -> Selection<'_> {
+        Selection {
+            document: self,
+            start_line: 0,
+            end_line: self.offsets.len().saturating_sub(1)
+        }
+    }
+
+    /// Nodes
+    ///
+    /// # Description
+    /// Return every node of the document in the order of the source
+    ///
+    /// # Return
+    /// impl Iterator<Item = Node>
+    pub fn nodes(&self) -> impl Iterator<Item = Node<'_>> + '_ {
+        self.select().nodes()
+    }
+
+    /// Headings
+    ///
+    /// # Description
+    /// Return the headings
+    ///
+    /// # Return
+    /// impl Iterator<Item = Heading>
+    pub fn headings(&self) -> impl Iterator<Item = Heading<'_>> + '_ {
+        self.select().headings()
+    }
+
+    /// Links
+    ///
+    /// # Description
+    /// Return the links of the text
+    ///
+    /// # Return
+    /// impl Iterator<Item = Link>
+    pub fn links(&self) -> impl Iterator<Item = Link<'_>> + '_ {
+        self.select().links()
+    }
+
+    /// Images
+    ///
+    /// # Description
+    /// Return the images of the text
+    ///
+    /// # Return
+    /// impl Iterator<Item = Image>
+    pub fn images(&self) -> impl Iterator<Item = Image<'_>> + '_ {
+        self.select().images()
+    }
+
+    /// Code Blocks
+    ///
+    /// # Description
+    /// Return the fenced code blocks
+    ///
+    /// # Return
+    /// impl Iterator<Item = CodeBlock>
+    pub fn code_blocks(&self) -> impl Iterator<Item = CodeBlock<'_>> + '_ {
+        self.select().code_blocks()
+    }
+
+    /// Find
+    ///
+    /// # Description
+    /// Return the nodes matching the predicate
+    ///
+    /// # Arguments
+    /// * `predicate` P
+    ///
+    /// # Return
+    /// impl Iterator<Item = Node>
+    pub fn find<'d, P: FnMut(&Node) -> bool + 'd>(&'d self, predicate: P) -> impl Iterator<Item = Node<'d>> + 'd {
+        self.select().find(predicate)
+    }
+
+    /// Section
+    ///
+    /// # Description
+    /// Return the section of the first heading matching the title or the slug. The section ends
+    /// before the next heading of the same or a lower level, as a `split_sections` at the level of the heading
+    ///
+    /// # Arguments
+    /// * `heading` &str
+    ///
+    /// # Return
+    /// Option<Selection>
+    pub fn section(&self, heading: &str) -> Option<Selection<'_>> {
+        self.select().section(heading)
+    }
+
+    /// Get Span
+    ///
+    /// # Description
+    /// Return the byte range from the start of a line to the end of an other line
+    ///
+    /// # Arguments
+    /// * `start_line` usize
+    /// * `end_line` usize
+    ///
+    /// # Return
+    /// Range<usize>
+    fn get_span(&self, start_line: usize, end_line: usize) -> Range<usize> {
+        let start = self.offsets.get(start_line).map_or(self.content.len(), |o| o.start);
+        let end = self.offsets.get(end_line).map_or(self.content.len(), |o| o.end);
+
+        start..end.max(start)
+    }
+
+    /// Get Block End
+    ///
+    /// # Description
+    /// Return the last line of the block starting at the line. A block spanning several lines i.e a
+    /// html block, has no token on the following lines
+    ///
+    /// # Arguments
+    /// * `line` usize
+    ///
+    /// # Return
+    /// usize
+    fn get_block_end(&self, line: usize) -> usize {
+        self.lines
+            .range(line + 1..)
+            .next()
+            .map_or(self.offsets.len().saturating_sub(1), |next| next - 1)
+    }
+
+    /// Get External Nodes
+    ///
+    /// # Description
+    /// Return the links and the images of a line in the order of the source, each one spanning its
+    /// markdown. The code spans are masked and an escaped bracket doesn't open a link
+    ///
+    /// # Arguments
+    /// * `line` usize
+    ///
+    /// # Return
+    /// Vec<Node>
+    fn get_external_nodes(&self, line: usize) -> Vec<Node<'_>> {
+        let span = self.get_span(line, line);
+        let source = &self.content[span.clone()];
+        let masked = mask_code_spans(source);
+        let mut nodes = Vec::new();
+        let mut idx = 0;
+
+        while let Some(pos) = masked[idx..].find('[') {
+            let start = idx + pos;
+            // an image within the label of a link is matched as well
+            idx = start + 1;
+
+            if masked[..start].ends_with('\\') {
+                continue;
+            }
+
+            let (label, len) = match match_link(&masked[start..]) {
+                Some((label, _, len)) => (label.len(), len),
+                None => continue
+            };
+
+            let destination = source[start + label + 3..start + len - 1].trim();
+            // a destination within angle brackets may hold spaces
+            let url = match destination.strip_prefix('<') {
+                Some(rest) => rest.split('>').next().unwrap_or(rest),
+                None => destination.split_whitespace().next().unwrap_or("")
+            };
+            let text = &source[start + 1..start + 1 + label];
+            let end = span.start + start + len;
+
+            if masked[..start].ends_with('!') {
+                nodes.push(Node::Image(Image { alt_text: text, url, line, span: span.start + start - 1..end }));
+            } else {
+                nodes.push(Node::Link(Link { title: text, url, line, span: span.start + start..end }));
+            }
+        }
+
+        nodes
+    }
+
+    /// Get Line Nodes
+    ///
+    /// # Description
+    /// Return the nodes of a line, the block is followed by its links and its images. The links of
+    /// a heading, a list item, a block quote, a table or a definition list are returned as well
+    ///
+    /// # Arguments
+    /// * `line` usize
+    ///
+    /// # Return
+    /// Vec<Node>
+    fn get_line_nodes(&self, line: usize) -> Vec<Node<'_>> {
+        if let Some(end_line) = self.code_blocks.get(&line) {
+            return vec![Node::CodeBlock(self.get_code_block(line, *end_line))];
+        }
+
+        if self.code_tokens.contains_key(&line) {
+            return Vec::new();
+        }
+
+        let token = match self.tokens.get(&line) {
+            Some(token) => token,
+            None => return Vec::new()
+        };
+
+        let mut nodes = Vec::new();
+        let span = self.get_span(line, self.get_block_end(line));
+
+        if let Some(tag) = &token.tag {
+            if tag.operator == TagOperator::Heading {
+                let metas = tag.metas.as_ref();
+                nodes.push(Node::Heading(Heading {
+                    level: metas.map(|m| m.heading_kind.clone()).unwrap_or_default(),
+                    title: get_plain_text(&get_inline_nodes(&tag.content, &self.options)),
+                    slug: metas.and_then(|m| m.slug.as_deref()),
+                    line,
+                    span: span.clone()
+                }));
+            } else {
+                nodes.push(Node::Tag { token: tag, line, span: span.clone() });
+            }
+        }
+
+        let is_multiline = token.tag
+            .as_ref()
+            .is_some_and(|tag| matches!(tag.operator, TagOperator::Table | TagOperator::DefinitionList));
+
+        if let Some(text) = token.text.as_ref().filter(|t| !t.content.is_empty()) {
+            nodes.push(Node::Text { token: text, line, span });
+        } else if is_multiline {
+            // the cells of a table and the items of a definition list are spread over the lines of the block
+            for block_line in line..=self.get_block_end(line) {
+                nodes.extend(self.get_external_nodes(block_line));
+            }
+
+            return nodes;
+        } else if !token.tag.as_ref().is_some_and(|tag| tag.has_inline_content()) {
+            return nodes;
+        }
+
+        nodes.extend(self.get_external_nodes(line));
+        nodes
+    }
+
+    /// Get Code Block
+    ///
+    /// # Description
+    /// Build the code block between two lines. The lines of code are borrowed from the source in
+    /// order to keep their indentation
+    ///
+    /// # Arguments
+    /// * `start_line` usize
+    /// * `end_line` usize
+    ///
+    /// # Return
+    /// CodeBlock
+    fn get_code_block(&self, start_line: usize, end_line: usize) -> CodeBlock<'_> {
+        let fence = self.code_tokens.get(&start_line).map_or("", |t| t.content.as_ref());
+        let closed = end_line > start_line && self.code_tokens
+            .get(&end_line)
+            .is_some_and(|t| t.operator == CodeOperator::BlockCodeEnd);
+        let last_code_line = if closed { end_line - 1 } else { end_line };
+
+        CodeBlock {
+            info: get_info_string(fence),
+            lines: (start_line + 1..=last_code_line)
+                .filter_map(|idx| self.offsets.get(idx))
+                .map(|offset| &self.content[offset.clone()])
+                .collect(),
+            start_line,
+            end_line,
+            span: self.get_span(start_line, end_line)
+        }
+    }
+}
+
+impl<'d> Selection<'d> {
+    /// Nodes
+    ///
+    /// # Description
+    /// Return the nodes of the selected lines in the order of the source
+    ///
+    /// # Return
+    /// impl Iterator<Item = Node>
+    pub fn nodes(&self) -> impl Iterator<Item = Node<'d>> + 'd {
+        let document = self.document;
+
+        document.lines
+            .range(self.start_line..=self.end_line)
+            .flat_map(move |line| document.get_line_nodes(*line))
+    }
+
+    /// Headings
+    ///
+    /// # Description
+    /// Return the headings
+    ///
+    /// # Return
+    /// impl Iterator<Item = Heading>
+    pub fn headings(&self) -> impl Iterator<Item = Heading<'d>> + 'd {
+        self.nodes().filter_map(|node| match node {
+            Node::Heading(heading) => Some(heading),
+            _ => None
+        })
+    }
+
+    /// Links
+    ///
+    /// # Description
+    /// Return the links of the text
+    ///
+    /// # Return
+    /// impl Iterator<Item = Link>
+    pub fn links(&self) -> impl Iterator<Item = Link<'d>> + 'd {
+        self.nodes().filter_map(|node| match node {
+            Node::Link(link) => Some(link),
+            _ => None
+        })
+    }
+
+    /// Images
+    ///
+    /// # Description
+    /// Return the images of the text
+    ///
+    /// # Return
+    /// impl Iterator<Item = Image>
+    pub fn images(&self) -> impl Iterator<Item = Image<'d>> + 'd {
+        self.nodes().filter_map(|node| match node {
+            Node::Image(image) => Some(image),
+            _ => None
+        })
+    }
+
+    /// Code Blocks
+    ///
+    /// # Description
+    /// Return the fenced code blocks
+    ///
+    /// # Return
+    /// impl Iterator<Item = CodeBlock>
+    pub fn code_blocks(&self) -> impl Iterator<Item = CodeBlock<'d>> + 'd {
+        self.nodes().filter_map(|node| match node {
+            Node::CodeBlock(block) => Some(block),
+            _ => None
+        })
+    }
+
+    /// Find
+    ///
+    /// # Description
+    /// Return the nodes of the selected lines matching the predicate
+    ///
+    /// # Arguments
+    /// * `predicate` P
+    ///
+    /// # Return
+    /// impl Iterator<Item = Node>
+    pub fn find<P: FnMut(&Node) -> bool + 'd>(&self, mut predicate: P) -> impl Iterator<Item = Node<'d>> + 'd {
+        self.nodes().filter(move |node| predicate(node))
+    }
+
+    /// Section
+    ///
+    /// # Description
+    /// Return the section of the first selected heading matching the title or the slug. The section
+    /// ends before the next heading of the same or a lower level, as a `split_sections` at the level of the heading
+    ///
+    /// # Arguments
+    /// * `heading` &str
+    ///
+    /// # Return
+    /// Option<Selection>
+    pub fn section(&self, heading: &str) -> Option<Selection<'d>> {
+        let found = self.headings().find(|h| h.title == heading || h.slug == Some(heading))?;
+
+        Some(Selection {
+            document: self.document,
+            start_line: found.line,
+            end_line: get_section_end(&self.document.tokens, found.line, &found.level, self.end_line)
+        })
+    }
+
+    /// Span
+    ///
+    /// # Description
+    /// Return the byte range of the selected lines
+    ///
+    /// # Return
+    /// Range<usize>
+    pub fn span(&self) -> Range<usize> {
+        self.document.get_span(self.start_line, self.end_line)
+    }
+
+    /// Raw
+    ///
+    /// # Description
+    /// Return the source of the selected lines
+    ///
+    /// # Return
+    /// &str
+    pub fn raw(&self) -> &'d str {
+        &self.document.content[self.span()]
+    }
+}
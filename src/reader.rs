@@ -0,0 +1,265 @@
+use std::clone::Clone;
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::mem;
+use crate::error;
+use crate::options::ParserOptions;
+use crate::parser::front_matter::{FrontMatter, get_front_matter, is_opening_delimiter};
+use crate::token::{LineParser, TextualToken};
+use crate::token::code::{CodeToken, get_code_token};
+
+// Size of the leading lines kept while looking for the end of a front matter. A longer head is
+// parsed as markdown, as a front matter which isn't closed
+const FRONT_MATTER_MAX_BYTES: usize = 64 * 1024;
+
+/// Reader Token
+///
+/// # Description
+/// Token returned by `parse_reader`. The tokens own their content as the lines are dropped once parsed
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum ReaderToken {
+    Textual(TextualToken<'static>),
+    Code(CodeToken<'static>)
+}
+
+/// Reader Tokens
+///
+/// Iterator over the tokens of a markdown read line by line. A token is returned as soon as the
+/// following lines can't update it anymore, only the blocks being parsed are kept in memory. The
+/// document wide metas (footnote numbers, slugs, abbreviations) aren't set
+pub struct ReaderTokens<'o, R> {
+    reader: R,
+    // Bytes of the line being read
+    buffer: Vec<u8>,
+    // Index of the next line
+    line: usize,
+    parser: LineParser<'static, 'o>,
+    // Last code token, used to detect the lines within a code block
+    code_token: Option<CodeToken<'static>>,
+    // Code tokens which aren't returned yet
+    code_tokens: VecDeque<(usize, CodeToken<'static>)>,
+    // Leading lines kept until we know whether they are a front matter, and their size
+    head: Vec<String>,
+    head_bytes: usize,
+    head_done: bool,
+    front_matter: Option<FrontMatter>,
+    // Error of the reader, returned once the blocks read before it
+    error: Option<error::ParserError>,
+    // An error has been returned, the iterator is exhausted
+    failed: bool
+}
+
+impl<'o, R: BufRead> ReaderTokens<'o, R> {
+    /// New
+    ///
+    /// # Description
+    /// Create the iterator. The reader is only read when iterating
+    ///
+    /// # Arguments
+    /// * `reader` R
+    /// * `options` &'o ParserOptions
+    ///
+    /// # Return
+    /// ReaderTokens<'o, R>
+    pub fn new(reader: R, options: &'o ParserOptions) -> Self {
+        ReaderTokens {
+            reader,
+            buffer: Vec::new(),
+            line: 0,
+            parser: LineParser::new(options),
+            code_token: None,
+            code_tokens: VecDeque::new(),
+            head: Vec::new(),
+            head_bytes: 0,
+            head_done: false,
+            front_matter: None,
+            error: None,
+            failed: false
+        }
+    }
+
+    /// Front Matter
+    ///
+    /// # Description
+    /// Return the front matter once its closing delimiter has been read
+    ///
+    /// # Return
+    /// Option<&FrontMatter>
+    pub fn front_matter(&self) -> Option<&FrontMatter> {
+        self.front_matter.as_ref()
+    }
+
+    /// Read Line
+    ///
+    /// # Description
+    /// Read the next line without its line ending
+    ///
+    /// # Return
+    /// Result<Option<String>, error::ParserError> None at the end of the reader
+    fn read_line(&mut self) -> Result<Option<String>, error::ParserError> {
+        self.buffer.clear();
+        let read = self.reader
+            .read_until(b'\n', &mut self.buffer)
+            .map_err(|err| error::ParserError {
+                message: format!("{}: {}", error::READ_INPUT, err)
+            })?;
+
+        if read == 0 {
+            return Ok(None);
+        }
+
+        if self.buffer.ends_with(b"\n") {
+            self.buffer.pop();
+            if self.buffer.ends_with(b"\r") {
+                self.buffer.pop();
+            }
+        }
+
+        String::from_utf8(mem::take(&mut self.buffer))
+            .map(Some)
+            .map_err(|err| error::ParserError {
+                message: format!("{}: line {}, {}", error::INVALID_UTF8, self.line + 1, err)
+            })
+    }
+
+    /// Push Line
+    ///
+    /// # Description
+    /// Parse the line, or keep it while it may be part of a front matter. The lookahead is bounded by
+    /// `FRONT_MATTER_MAX_BYTES`
+    ///
+    /// # Arguments
+    /// * `line` String
+    fn push_line(&mut self, line: String) {
+        let idx = self.line;
+        self.line += 1;
+
+        if self.head_done {
+            self.parse_line(idx, &line);
+            return;
+        }
+
+        let trimmed = line.trim().to_string();
+        self.head_bytes += line.len() + 1;
+        self.head.push(line);
+
+        // only the first line may open a front matter
+        if self.head.len() == 1 {
+            if !is_opening_delimiter(&trimmed) {
+                self.flush_head();
+            }
+
+            return;
+        }
+
+        // only a delimiter may close the front matter
+        if !is_opening_delimiter(&trimmed) && trimmed != "..." {
+            if self.head_bytes > FRONT_MATTER_MAX_BYTES {
+                self.flush_head();
+            }
+
+            return;
+        }
+
+        self.front_matter = get_front_matter(&self.head.join("\n"));
+        if self.front_matter.is_some() {
+            self.head.clear();
+            self.flush_head();
+        }
+    }
+
+    /// Flush Head
+    ///
+    /// # Description
+    /// Parse the leading lines which aren't a front matter
+    fn flush_head(&mut self) {
+        // the head always start at the first line
+        for (idx, line) in mem::take(&mut self.head).iter().enumerate() {
+            self.parse_line(idx, line);
+        }
+
+        self.head_done = true;
+    }
+
+    /// Parse Line
+    ///
+    /// # Description
+    /// Parse the line into the code and the textual tokens
+    ///
+    /// # Arguments
+    /// * `idx` usize
+    /// * `line` &str
+    fn parse_line(&mut self, idx: usize, line: &str) {
+        if let Some(token) = get_code_token(idx, line, &self.code_token) {
+            let token = token.into_owned();
+            self.code_token = Some(token.clone());
+            self.code_tokens.push_back((idx, token));
+        }
+
+        let token = self.parser
+            .parse_line(idx, line)
+            .map(TextualToken::into_owned);
+
+        self.parser.push_token(idx, token);
+    }
+
+    /// Pop Code
+    ///
+    /// # Description
+    /// Return the first code token when every textual token before it has been returned
+    ///
+    /// # Return
+    /// Option<(usize, CodeToken)>
+    fn pop_code(&mut self) -> Option<(usize, CodeToken<'static>)> {
+        let (idx, _) = self.code_tokens.front()?;
+        if self.parser.first_line().is_some_and(|first| first < *idx) {
+            return None;
+        }
+
+        self.code_tokens.pop_front()
+    }
+}
+
+impl<R: BufRead> Iterator for ReaderTokens<'_, R> {
+    type Item = Result<(usize, ReaderToken), error::ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.failed {
+                return None;
+            }
+
+            if let Some((idx, token)) = self.pop_code() {
+                return Some(Ok((idx, ReaderToken::Code(token))));
+            }
+
+            if let Some((idx, token)) = self.parser.pop_ready() {
+                return Some(Ok((idx, ReaderToken::Textual(token))));
+            }
+
+            if self.parser.done {
+                self.failed = true;
+                return self.error.take().map(Err);
+            }
+
+            match self.read_line() {
+                Ok(Some(line)) => self.push_line(line),
+                Ok(None) if self.line == 0 => {
+                    self.failed = true;
+                    return Some(Err(error::ParserError{message: error::EMPTY_CONTENT.to_string()}));
+                },
+                Ok(None) => {
+                    self.flush_head();
+                    self.parser.done = true;
+                },
+                // the blocks read before the error are returned first
+                Err(err) => {
+                    self.error = Some(err);
+                    self.flush_head();
+                    self.parser.done = true;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,730 @@
+use std::collections::{BTreeMap, BTreeSet};
+use regex::Regex;
+use lazy_static::lazy_static;
+use crate::options::ParserOptions;
+use crate::parser::abbreviation::{AbbreviationMeta, get_abbreviation_definitions, get_abbreviations};
+use crate::parser::code::{CodeOperator, get_info_string};
+use crate::parser::directive::DirectiveMeta;
+use crate::parser::external::LinkMeta;
+use crate::parser::github::IssueRef;
+use crate::parser::heading::HeadingLevel;
+use crate::parser::inline::{InlineNode, get_inline_nodes};
+use crate::parser::table::TableAlignment;
+use crate::token::TextualToken;
+use crate::token::code::CodeToken;
+use crate::token::footnote::{Footnotes, get_footnotes};
+use crate::token::tag::{TagToken, TagOperator};
+
+lazy_static!{
+    static ref ENTITY: Regex = Regex::new(r"^&(#[0-9]{1,7}|#[xX][0-9a-fA-F]{1,6}|[A-Za-z][A-Za-z0-9]{1,31});").unwrap();
+}
+
+/// Html Renderer
+///
+/// Keep track of the blocks which are opened while walking through the lines
+struct HtmlRenderer<'a> {
+    html: String,
+    options: &'a ParserOptions,
+    footnotes: Footnotes,
+    paragraph: Vec<String>,
+    blockquote: Vec<String>,
+    list: Option<TagOperator>,
+    // Footnote labels which have already been referenced
+    referenced: Vec<String>,
+    abbreviations: Vec<AbbreviationMeta>,
+    // Number of directive containers which are opened
+    directives: usize,
+    // A code block is opened
+    code: bool
+}
+
+/// Render Html
+///
+/// # Description
+/// Render the textual tokens and the code tokens to html
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `code_tokens` &BTreeMap<usize, CodeToken>
+/// * `options` &ParserOptions
+///
+/// # Return
+/// String
+pub fn render_html(
+    tokens: &BTreeMap<usize, TextualToken>,
+    code_tokens: &BTreeMap<usize, CodeToken>,
+    options: &ParserOptions
+) -> String {
+    let mut renderer = HtmlRenderer {
+        html: String::new(),
+        options,
+        footnotes: get_footnotes(tokens),
+        paragraph: Vec::new(),
+        blockquote: Vec::new(),
+        list: None,
+        referenced: Vec::new(),
+        abbreviations: get_abbreviation_definitions(tokens),
+        directives: 0,
+        code: false
+    };
+
+    let lines: BTreeSet<usize> = tokens.keys().chain(code_tokens.keys()).copied().collect();
+    // A ```math fence is rendered as a display math instead of a code block
+    let mut in_math_fence = false;
+
+    for idx in lines {
+        let textual = tokens.get(&idx);
+        let code = code_tokens.get(&idx);
+
+        if let Some(tag) = textual.and_then(|t| t.tag.as_ref()).filter(|t| t.operator == TagOperator::DisplayMath) {
+            renderer.render_tag(tag);
+            in_math_fence = code.is_some_and(|c| c.operator == CodeOperator::BlockCodeStart);
+            continue;
+        }
+
+        if let Some(code) = code {
+            if in_math_fence {
+                in_math_fence = code.operator != CodeOperator::BlockCodeEnd;
+                continue;
+            }
+
+            renderer.render_code(code);
+            continue;
+        }
+
+        if let Some(textual) = textual {
+            renderer.render_textual(textual);
+        }
+    }
+
+    renderer.close_code();
+    renderer.close_blocks();
+    for _ in 0..renderer.directives {
+        renderer.html.push_str("</div>\n");
+    }
+
+    renderer.render_footnotes();
+
+    renderer.html
+}
+
+impl<'a> HtmlRenderer<'a> {
+    /// Render Code
+    ///
+    /// # Description
+    /// Render a line of a code block
+    ///
+    /// # Arguments
+    /// * `code` &CodeToken
+    fn render_code(&mut self, code: &CodeToken) {
+        match code.operator {
+            CodeOperator::BlockCodeStart => {
+                self.close_code();
+                self.close_blocks();
+                // only the first word of the info string is the language
+                let lang = get_info_string(&code.content)
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or("");
+
+                if lang.is_empty() {
+                    self.html.push_str("<pre><code>");
+                } else {
+                    self.html.push_str(&format!("<pre><code class=\"language-{}\">", escape_html(lang)));
+                }
+
+                self.code = true;
+            },
+            CodeOperator::BlockCodeContent => {
+                self.html.push_str(&escape_html(&code.content));
+                self.html.push('\n');
+            },
+            CodeOperator::BlockCodeEnd => self.close_code()
+        }
+    }
+
+    /// Close Code
+    ///
+    /// # Description
+    /// Close the code block if one is opened, a block which isn't closed ends with the document
+    fn close_code(&mut self) {
+        if self.code {
+            self.html.push_str("</code></pre>\n");
+            self.code = false;
+        }
+    }
+
+    /// Render Textual
+    ///
+    /// # Description
+    /// Render a textual token. Consecutive lines of text, list items and blockquotes are grouped
+    ///
+    /// # Arguments
+    /// * `textual` &TextualToken
+    fn render_textual(&mut self, textual: &TextualToken) {
+        if let Some(tag) = &textual.tag {
+            self.render_tag(tag);
+            return;
+        }
+
+        let text = match &textual.text {
+            Some(text) => text,
+            None => return
+        };
+
+        if text.content.is_empty() {
+            self.close_blocks();
+            return;
+        }
+
+        let links = text.metas.as_ref().and_then(|m| m.links.as_ref());
+        let line = self.render_inline(&text.content, links);
+
+        self.close_list();
+        self.close_blockquote();
+        self.paragraph.push(line);
+    }
+
+    /// Render Tag
+    ///
+    /// # Description
+    /// Render a tag token
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    fn render_tag(&mut self, tag: &TagToken) {
+        match tag.operator {
+            TagOperator::Heading => {
+                self.close_blocks();
+                let level = tag.metas.as_ref().map(|m| get_heading_number(&m.heading_kind)).unwrap_or(1);
+                let id = tag.metas
+                    .as_ref()
+                    .and_then(|m| m.slug.as_ref())
+                    .map(|slug| format!(" id=\"{}\"", escape_html(slug)))
+                    .unwrap_or_default();
+
+                let content = self.render_inline(&tag.content, None);
+                self.html.push_str(&format!("<h{}{}>{}</h{}>\n", level, id, content, level));
+            },
+            TagOperator::UnorderedList | TagOperator::OrderedList => {
+                self.close_paragraph();
+                self.close_blockquote();
+                if self.list.as_ref() != Some(&tag.operator) {
+                    self.close_list();
+                    self.html.push_str(get_list_tag(&tag.operator, false));
+                    self.list = Some(tag.operator.clone());
+                }
+
+                let content = self.render_inline(&tag.content, None);
+                self.html.push_str(&format!("<li>{}</li>\n", content));
+            },
+            TagOperator::BlockQuote => {
+                self.close_paragraph();
+                self.close_list();
+                let content = self.render_inline(&tag.content, None);
+                self.blockquote.push(content);
+            },
+            TagOperator::HtmlBlock => {
+                self.close_blocks();
+                self.html.push_str(&tag.content);
+                self.html.push('\n');
+            },
+            TagOperator::Custom(ref node) => {
+                self.close_blocks();
+                self.html.push_str(&node.render_html());
+                self.html.push('\n');
+            },
+            TagOperator::DisplayMath => {
+                self.close_blocks();
+                self.html.push_str(&format!("<span class=\"math math-display\">{}</span>\n", escape_html(&tag.content)));
+            },
+            TagOperator::DefinitionList => {
+                self.close_blocks();
+                self.render_definition_list(tag);
+            },
+            TagOperator::Table => {
+                self.close_blocks();
+                self.render_table(tag);
+            },
+            TagOperator::ContainerDirective => {
+                self.close_blocks();
+                let directive = match tag.metas.as_ref().and_then(|m| m.directive.as_ref()) {
+                    Some(directive) => directive,
+                    None => return
+                };
+
+                self.html.push_str(&format!("<div{}>\n", get_directive_attributes(directive)));
+                if !tag.content.is_empty() {
+                    let label = self.render_inline(&tag.content, None);
+                    self.html.push_str(&format!("<p class=\"directive-label\">{}</p>\n", label));
+                }
+
+                self.directives += 1;
+            },
+            TagOperator::ContainerDirectiveEnd => {
+                self.close_blocks();
+                if self.directives > 0 {
+                    self.html.push_str("</div>\n");
+                    self.directives -= 1;
+                }
+            },
+            TagOperator::LeafDirective => {
+                self.close_blocks();
+                if let Some(directive) = tag.metas.as_ref().and_then(|m| m.directive.as_ref()) {
+                    let label = self.render_inline(&tag.content, None);
+                    self.html.push_str(&format!("<div{}>{}</div>\n", get_directive_attributes(directive), label));
+                }
+            },
+            // rendered at the end of the document
+            TagOperator::FootnoteDefinition => self.close_blocks(),
+            // only used to annotate the text
+            TagOperator::AbbreviationDefinition => {}
+        }
+    }
+
+    /// Render Definition List
+    ///
+    /// # Description
+    /// Render the terms and the definitions of a definition list
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    fn render_definition_list(&mut self, tag: &TagToken) {
+        let items = match tag.metas.as_ref().and_then(|m| m.definition_list.as_ref()) {
+            Some(list) => &list.items,
+            None => return
+        };
+
+        self.html.push_str("<dl>\n");
+        for item in items {
+            let term = self.render_inline(&item.term, None);
+            self.html.push_str(&format!("<dt>{}</dt>\n", term));
+
+            for definition in &item.definitions {
+                let definition = self.render_inline(definition, None);
+                self.html.push_str(&format!("<dd>{}</dd>\n", definition));
+            }
+        }
+
+        self.html.push_str("</dl>\n");
+    }
+
+    /// Render Table
+    ///
+    /// # Description
+    /// Render the header and the rows of a table, the alignment of a column is set on its cells
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    fn render_table(&mut self, tag: &TagToken) {
+        let table = match tag.metas.as_ref().and_then(|m| m.table.as_ref()) {
+            Some(table) => table,
+            None => return
+        };
+
+        self.html.push_str("<table>\n<thead>\n");
+        self.render_table_row(&table.header, &table.alignments, "th");
+        self.html.push_str("</thead>\n");
+
+        if !table.rows.is_empty() {
+            self.html.push_str("<tbody>\n");
+            for row in &table.rows {
+                self.render_table_row(row, &table.alignments, "td");
+            }
+
+            self.html.push_str("</tbody>\n");
+        }
+
+        self.html.push_str("</table>\n");
+    }
+
+    /// Render Table Row
+    ///
+    /// # Description
+    /// Render the cells of a row of a table
+    ///
+    /// # Arguments
+    /// * `cells` &[String]
+    /// * `alignments` &[TableAlignment]
+    /// * `cell_tag` &str th or td
+    fn render_table_row(&mut self, cells: &[String], alignments: &[TableAlignment], cell_tag: &str) {
+        self.html.push_str("<tr>\n");
+        for (cell, alignment) in cells.iter().zip(alignments) {
+            let align = match alignment {
+                TableAlignment::Left => " align=\"left\"",
+                TableAlignment::Center => " align=\"center\"",
+                TableAlignment::Right => " align=\"right\"",
+                TableAlignment::None => ""
+            };
+
+            let content = self.render_inline(cell, None);
+            self.html.push_str(&format!("<{}{}>{}</{}>\n", cell_tag, align, content, cell_tag));
+        }
+
+        self.html.push_str("</tr>\n");
+    }
+
+    /// Render Footnotes
+    ///
+    /// # Description
+    /// Render the referenced footnotes at the end of the document
+    fn render_footnotes(&mut self) {
+        let definitions: Vec<_> = self.footnotes.definitions
+            .iter()
+            .filter(|def| def.number.is_some())
+            .cloned()
+            .collect();
+
+        if definitions.is_empty() {
+            return;
+        }
+
+        self.html.push_str("<section class=\"footnotes\">\n<ol>\n");
+        for def in definitions {
+            let label = escape_html(&def.label);
+            self.html.push_str(&format!("<li id=\"fn-{}\">\n", label));
+
+            let last = def.paragraphs.len().saturating_sub(1);
+            for (idx, paragraph) in def.paragraphs.iter().enumerate() {
+                let mut content = self.render_inline(paragraph, None);
+                if idx == last {
+                    content.push_str(&format!(" <a href=\"#fnref-{}\" class=\"footnote-backref\">↩</a>", label));
+                }
+
+                self.html.push_str(&format!("<p>{}</p>\n", content));
+            }
+
+            self.html.push_str("</li>\n");
+        }
+
+        self.html.push_str("</ol>\n</section>\n");
+    }
+
+    /// Render Inline
+    ///
+    /// # Description
+    /// Render the inline content of a line
+    ///
+    /// # Arguments
+    /// * `content` &str
+    /// * `links` Option<&Vec<LinkMeta>>
+    ///
+    /// # Return
+    /// String
+    fn render_inline(&mut self, content: &str, links: Option<&Vec<LinkMeta>>) -> String {
+        let nodes = get_inline_nodes(content, self.options);
+        self.render_nodes(&nodes, links)
+    }
+
+    /// Render Nodes
+    ///
+    /// # Description
+    /// Render a list of inline nodes
+    ///
+    /// # Arguments
+    /// * `nodes` &[InlineNode]
+    /// * `links` Option<&Vec<LinkMeta>>
+    ///
+    /// # Return
+    /// String
+    fn render_nodes(&mut self, nodes: &[InlineNode], links: Option<&Vec<LinkMeta>>) -> String {
+        let mut html = String::new();
+
+        for node in nodes {
+            let rendered = match node {
+                InlineNode::Text(text) => self.render_text(text),
+                InlineNode::Strong(children) => format!("<strong>{}</strong>", self.render_nodes(children, links)),
+                InlineNode::Emphasis(children) => format!("<em>{}</em>", self.render_nodes(children, links)),
+                InlineNode::Strike(children) => format!("<del>{}</del>", self.render_nodes(children, links)),
+                InlineNode::Code(code) => format!("<code>{}</code>", escape_html(code)),
+                InlineNode::Link { title, url, tooltip } => {
+                    let rel = links
+                        .and_then(|l| l.iter().find(|link| link.url == *url))
+                        .and_then(|link| link.rel.as_ref())
+                        .map(|rel| format!(" rel=\"{}\"", escape_html(rel)))
+                        .unwrap_or_default();
+
+                    format!("<a href=\"{}\"{}{}>{}</a>", escape_html(url), get_title_attribute(tooltip), rel, self.render_nodes(title, links))
+                },
+                InlineNode::Image { alt_text, url, tooltip } => {
+                    format!("<img src=\"{}\" alt=\"{}\"{} />", escape_html(url), escape_html(alt_text), get_title_attribute(tooltip))
+                },
+                InlineNode::FootnoteRef(label) => self.render_footnote_ref(label),
+                InlineNode::Html(html) => html.clone(),
+                InlineNode::InlineMath(math) => format!("<span class=\"math math-inline\">{}</span>", escape_html(math)),
+                InlineNode::Emoji { shortcode, unicode } => {
+                    format!("<g-emoji alias=\"{}\">{}</g-emoji>", escape_html(shortcode), unicode)
+                },
+                InlineNode::Mention(user) => self.render_mention(user),
+                InlineNode::IssueRef { repository, number } => self.render_issue_ref(repository, *number),
+                InlineNode::TextDirective { directive, label } => {
+                    format!("<span{}>{}</span>", get_directive_attributes(directive), self.render_nodes(label, links))
+                },
+                InlineNode::Custom(node) => node.render_html()
+            };
+
+            html.push_str(&rendered);
+        }
+
+        html
+    }
+
+    /// Render Footnote Ref
+    ///
+    /// # Description
+    /// Render a footnote reference. Undefined footnotes are rendered as text
+    ///
+    /// # Arguments
+    /// * `label` &str
+    ///
+    /// # Return
+    /// String
+    fn render_footnote_ref(&mut self, label: &str) -> String {
+        let number = self.footnotes.definitions
+            .iter()
+            .find(|def| def.label == label)
+            .and_then(|def| def.number);
+
+        let number = match number {
+            Some(n) => n,
+            None => return escape_html(&format!("[^{}]", label))
+        };
+
+        let label = escape_html(label);
+        if self.referenced.contains(&label) {
+            return format!("<sup class=\"footnote-ref\"><a href=\"#fn-{}\">{}</a></sup>", label, number);
+        }
+
+        self.referenced.push(label.clone());
+        format!("<sup class=\"footnote-ref\"><a href=\"#fn-{}\" id=\"fnref-{}\">{}</a></sup>", label, label, number)
+    }
+
+    /// Render Text
+    ///
+    /// # Description
+    /// Escape a text node and wrap the defined abbreviations
+    ///
+    /// # Arguments
+    /// * `text` &str
+    ///
+    /// # Return
+    /// String
+    fn render_text(&self, text: &str) -> String {
+        let abbreviations = match get_abbreviations(text, &self.abbreviations) {
+            Some(abbreviations) => abbreviations,
+            None => return escape_html(text)
+        };
+
+        let mut html = String::new();
+        let mut start = 0;
+        for abbreviation in abbreviations {
+            let col = abbreviation.col.unwrap_or(start);
+            html.push_str(&escape_html(&text[start..col]));
+            html.push_str(&format!(
+                "<abbr title=\"{}\">{}</abbr>",
+                escape_html(&abbreviation.title),
+                escape_html(&abbreviation.abbr)
+            ));
+            start = col + abbreviation.abbr.len();
+        }
+
+        html.push_str(&escape_html(&text[start..]));
+        html
+    }
+
+    /// Render Mention
+    ///
+    /// # Description
+    /// Render a mention as a link to the user
+    ///
+    /// # Arguments
+    /// * `user` &str
+    ///
+    /// # Return
+    /// String
+    fn render_mention(&self, user: &str) -> String {
+        let text = escape_html(&format!("@{}", user));
+        match self.options.github.as_ref() {
+            Some(github) => format!("<a href=\"{}\" class=\"user-mention\">{}</a>", escape_html(&github.get_mention_url(user)), text),
+            None => text
+        }
+    }
+
+    /// Render Issue Ref
+    ///
+    /// # Description
+    /// Render an issue reference as a link. A reference without known repository is rendered as text
+    ///
+    /// # Arguments
+    /// * `repository` &Option<String>
+    /// * `number` usize
+    ///
+    /// # Return
+    /// String
+    fn render_issue_ref(&self, repository: &Option<String>, number: usize) -> String {
+        let issue = IssueRef {
+            repository: repository.clone(),
+            number,
+            col: None
+        };
+
+        let text = escape_html(&format!("{}#{}", repository.as_deref().unwrap_or(""), number));
+        match self.options.github.as_ref().and_then(|github| github.get_issue_url(&issue)) {
+            Some(url) => format!("<a href=\"{}\" class=\"issue-link\">{}</a>", escape_html(&url), text),
+            None => text
+        }
+    }
+
+    /// Close Blocks
+    ///
+    /// # Description
+    /// Close the paragraph, the list and the blockquote which are opened
+    fn close_blocks(&mut self) {
+        self.close_paragraph();
+        self.close_list();
+        self.close_blockquote();
+    }
+
+    /// Close Paragraph
+    ///
+    /// # Description
+    /// Render the pending lines of text as a paragraph
+    fn close_paragraph(&mut self) {
+        if !self.paragraph.is_empty() {
+            self.html.push_str(&format!("<p>{}</p>\n", self.paragraph.join("\n")));
+            self.paragraph.clear();
+        }
+    }
+
+    /// Close List
+    ///
+    /// # Description
+    /// Close the list which is opened
+    fn close_list(&mut self) {
+        if let Some(list) = self.list.take() {
+            self.html.push_str(get_list_tag(&list, true));
+        }
+    }
+
+    /// Close Blockquote
+    ///
+    /// # Description
+    /// Render the pending lines of the blockquote
+    fn close_blockquote(&mut self) {
+        if !self.blockquote.is_empty() {
+            self.html.push_str(&format!("<blockquote>\n<p>{}</p>\n</blockquote>\n", self.blockquote.join("\n")));
+            self.blockquote.clear();
+        }
+    }
+}
+
+/// Get Directive Attributes
+///
+/// # Description
+/// Get the html attributes of a directive. The name of the directive is added to the classes and
+/// the attributes other than the id and the class are prefixed with `data-`
+///
+/// # Arguments
+/// * `directive` &DirectiveMeta
+///
+/// # Return
+/// String
+fn get_directive_attributes(directive: &DirectiveMeta) -> String {
+    let mut classes = format!("directive directive-{}", directive.name);
+    let mut attributes = String::new();
+
+    for (key, value) in &directive.attributes {
+        match key.as_str() {
+            "class" => classes.push_str(&format!(" {}", value)),
+            "id" => attributes.push_str(&format!(" id=\"{}\"", escape_html(value))),
+            _ => attributes.push_str(&format!(" data-{}=\"{}\"", key, escape_html(value)))
+        }
+    }
+
+    format!(" class=\"{}\"{}", escape_html(&classes), attributes)
+}
+
+/// Get Title Attribute
+///
+/// # Description
+/// Get the title attribute of a link or an image
+///
+/// # Arguments
+/// * `title` &Option<String>
+///
+/// # Return
+/// String
+fn get_title_attribute(title: &Option<String>) -> String {
+    title
+        .as_ref()
+        .map(|title| format!(" title=\"{}\"", escape_html(title)))
+        .unwrap_or_default()
+}
+
+/// Escape Html
+///
+/// # Description
+/// Escape the html special characters. Valid entity references i.e `&lt;` are kept as is
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// String
+pub fn escape_html(content: &str) -> String {
+    let mut escaped = String::with_capacity(content.len());
+
+    for (idx, c) in content.char_indices() {
+        match c {
+            '&' if ENTITY.is_match(&content[idx..]) => escaped.push('&'),
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c)
+        }
+    }
+
+    escaped
+}
+
+/// Get Heading Number
+///
+/// # Description
+/// Return the number of the html heading tag
+///
+/// # Arguments
+/// * `level` &HeadingLevel
+///
+/// # Return
+/// usize
+pub fn get_heading_number(level: &HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6
+    }
+}
+
+/// Get List Tag
+///
+/// # Description
+/// Return the html tag of a list
+///
+/// # Arguments
+/// * `operator` &TagOperator
+/// * `closing` bool
+///
+/// # Return
+/// &str
+fn get_list_tag(operator: &TagOperator, closing: bool) -> &'static str {
+    match (operator, closing) {
+        (TagOperator::OrderedList, false) => "<ol>\n",
+        (TagOperator::OrderedList, true) => "</ol>\n",
+        (_, false) => "<ul>\n",
+        (_, true) => "</ul>\n"
+    }
+}
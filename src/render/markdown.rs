@@ -0,0 +1,1030 @@
+use std::clone::Clone;
+use std::default::Default;
+use std::collections::{BTreeMap, BTreeSet};
+use crate::error;
+use crate::options::ParserOptions;
+use crate::parser::code::{CodeOperator, get_fence, get_info_string};
+use crate::parser::directive::{DirectiveMeta, format_directive};
+use crate::parser::front_matter::get_front_matter;
+use crate::parser::heading::{get_custom_id, get_heading_content};
+use crate::parser::inline::{InlineNode, get_inline_nodes};
+use crate::parser::math::get_inline_math_at;
+use crate::parser::operator::bytes;
+use crate::parser::table::{TableAlignment, TableMeta};
+use crate::token::{self, TextualToken};
+use crate::token::code::CodeToken;
+use crate::token::tag::{TagToken, TagOperator};
+use super::html::get_heading_number;
+
+// Indentation of the continuation lines of the footnotes and the definitions
+const CONTINUATION_INDENT: &str = "    ";
+// Characters escaped when a text can't be emitted as is
+const ESCAPED_CHARACTERS: &str = "\\`*_[]~<!$:@#";
+// Maximum number of escaped characters added to keep a line of text as text
+const MAX_LINE_ESCAPES: usize = 8;
+
+/// Ordered Numbering
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum OrderedNumbering {
+    // 1. 1. 1.
+    One,
+    // 1. 2. 3.
+    #[default]
+    Increment
+}
+
+/// Format Style
+///
+/// # Description
+/// Canonical style used to re-emit the markdown
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormatStyle {
+    // Marker of the unordered list items i.e -. / *. / +.
+    pub bullet: char,
+    pub ordered_numbering: OrderedNumbering,
+    // Marker of the emphasis, doubled for the strong i.e * / _
+    pub emphasis: char,
+    // Character of the code fences i.e ` / ~
+    pub fence: char,
+    // Maximum width of the lines of the paragraphs. Lines aren't wrapped when None
+    pub wrap_width: Option<usize>
+}
+
+impl Default for FormatStyle {
+    fn default() -> Self {
+        FormatStyle {
+            bullet: '-',
+            ordered_numbering: OrderedNumbering::Increment,
+            emphasis: '*',
+            fence: '`',
+            wrap_width: None
+        }
+    }
+}
+
+impl FormatStyle {
+    /// Validate
+    ///
+    /// # Description
+    /// Check that the markers of the style are supported by the parser
+    ///
+    /// # Return
+    /// Result<(), ParserError>
+    pub fn validate(&self) -> Result<(), error::ParserError> {
+        let valid = matches!(self.bullet, '-' | '*' | '+')
+            && matches!(self.emphasis, '*' | '_')
+            && matches!(self.fence, '`' | '~')
+            && self.wrap_width != Some(0);
+
+        if !valid {
+            return Err(error::ParserError{message: error::INVALID_FORMAT_STYLE.to_string()});
+        }
+
+        Ok(())
+    }
+}
+
+/// Markdown Formatter
+///
+/// Keep track of the emitted lines while walking through the tokens
+struct MarkdownFormatter<'a> {
+    lines: Vec<String>,
+    source: Vec<&'a str>,
+    options: &'a ParserOptions,
+    style: &'a FormatStyle,
+    // Number of the next ordered list item
+    ordered_count: usize,
+    // Index of the last line closing a display math or a code block
+    closing_line: Option<usize>,
+    // Index of the opening line of the container directives which aren't closed yet
+    containers: Vec<usize>
+}
+
+/// Format Markdown
+///
+/// # Description
+/// Re-emit the parsed markdown in the canonical style
+///
+/// # Arguments
+/// * `content` &str the source of the tokens
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `code_tokens` &BTreeMap<usize, CodeToken>
+/// * `options` &ParserOptions
+/// * `style` &FormatStyle
+///
+/// # Return
+/// String
+pub fn format_markdown(
+    content: &str,
+    tokens: &BTreeMap<usize, TextualToken>,
+    code_tokens: &BTreeMap<usize, CodeToken>,
+    options: &ParserOptions,
+    style: &FormatStyle
+) -> String {
+    // the punctuation of the source is kept as is
+    let options = &ParserOptions { smart_punctuation: false, ..options.clone() };
+    let mut formatter = MarkdownFormatter {
+        lines: Vec::new(),
+        source: content.lines().collect(),
+        options,
+        style,
+        ordered_count: 1,
+        closing_line: None,
+        containers: Vec::new()
+    };
+
+    if let Some(front_matter) = get_front_matter(content) {
+        for idx in front_matter.start_line..=front_matter.end_line {
+            formatter.lines.push(formatter.source[idx].trim().to_string());
+        }
+
+        formatter.push_blank();
+    }
+
+    let lines: BTreeSet<usize> = tokens.keys().chain(code_tokens.keys()).copied().collect();
+    // A ```math fence is emitted as a display math instead of a code block
+    let mut in_math_fence = false;
+    // Indentation of the opening fence of the code block
+    let mut fence_indent = 0;
+    // Fence of the code block, longer than the fences within the code
+    let mut fence = String::new();
+    // An unclosed code block is closed at the end so that its trailing blank lines are kept
+    let mut in_code_block = false;
+
+    for idx in lines {
+        let textual = tokens.get(&idx);
+        let code = code_tokens.get(&idx);
+
+        if let Some(tag) = textual.and_then(|t| t.tag.as_ref()).filter(|t| t.operator == TagOperator::DisplayMath) {
+            formatter.format_tag(tag);
+            in_math_fence = code.is_some_and(|c| c.operator == CodeOperator::BlockCodeStart);
+            continue;
+        }
+
+        if let Some(code) = code {
+            if in_math_fence {
+                in_math_fence = code.operator != CodeOperator::BlockCodeEnd;
+                continue;
+            }
+
+            if code.operator == CodeOperator::BlockCodeStart {
+                fence_indent = get_indent(formatter.source[idx]);
+                // the info string of a backtick fence can't contain a backtick
+                let character = if get_info_string(&code.content).contains('`') { '~' } else { style.fence };
+                fence = get_code_fence(code_tokens, idx, character);
+            }
+
+            in_code_block = code.operator != CodeOperator::BlockCodeEnd;
+            formatter.format_code(code, idx, fence_indent, &fence);
+            continue;
+        }
+
+        if let Some(textual) = textual {
+            formatter.format_textual(textual);
+        }
+    }
+
+    if in_code_block {
+        formatter.lines.push(fence);
+    }
+
+    while formatter.lines.last().is_some_and(|l| l.is_empty()) {
+        formatter.lines.pop();
+    }
+
+    let mut markdown = formatter.lines.join("\n");
+    markdown.push('\n');
+
+    markdown
+}
+
+impl<'a> MarkdownFormatter<'a> {
+    /// Format Code
+    ///
+    /// # Description
+    /// Emit a line of a code block. The indentation of the code is kept relative to the fence
+    ///
+    /// # Arguments
+    /// * `code` &CodeToken
+    /// * `idx` usize
+    /// * `fence_indent` usize
+    /// * `fence` &str
+    fn format_code(&mut self, code: &CodeToken, idx: usize, fence_indent: usize, fence: &str) {
+        self.ordered_count = 1;
+
+        match code.operator {
+            CodeOperator::BlockCodeStart => {
+                // an info string starting with the fence character would lengthen the fence
+                let lang = get_info_string(&code.content);
+                let separator = if lang.starts_with(fence.chars().next().unwrap_or('`')) { " " } else { "" };
+                self.lines.push(format!("{}{}{}", fence, separator, lang));
+            },
+            CodeOperator::BlockCodeContent => {
+                let line = self.source[idx];
+                let indent = get_indent(line).min(fence_indent);
+                self.lines.push(line[indent..].trim_end().to_string());
+            },
+            CodeOperator::BlockCodeEnd => {
+                self.lines.push(fence.to_string());
+                self.closing_line = Some(self.lines.len() - 1);
+            }
+        }
+    }
+
+    /// Format Textual
+    ///
+    /// # Description
+    /// Emit a textual token. Consecutive blank lines are collapsed
+    ///
+    /// # Arguments
+    /// * `textual` &TextualToken
+    fn format_textual(&mut self, textual: &TextualToken) {
+        if let Some(tag) = &textual.tag {
+            if tag.operator != TagOperator::OrderedList {
+                self.ordered_count = 1;
+            }
+
+            self.format_tag(tag);
+            return;
+        }
+
+        self.ordered_count = 1;
+        let text = match &textual.text {
+            Some(text) => text,
+            None => return
+        };
+
+        if text.content.is_empty() {
+            self.push_blank();
+            return;
+        }
+
+        self.push_paragraph_line(&text.content);
+    }
+
+    /// Format Tag
+    ///
+    /// # Description
+    /// Emit a tag token
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    fn format_tag(&mut self, tag: &TagToken) {
+        let metas = tag.metas.clone().unwrap_or_default();
+
+        match tag.operator {
+            TagOperator::Heading => {
+                let id = metas.id.map(|id| format!(" {{#{}}}", id)).unwrap_or_default();
+                let level = "#".repeat(get_heading_number(&metas.heading_kind));
+                let inline = self.format_inline(&tag.content);
+                let mut line = format!("{} {}", level, inline);
+                // a closing sequence keeps an empty title, a title ending with # i.e `## #` or with an id
+                let (title, title_id) = get_custom_id(&line);
+                if inline.is_empty() || title_id.is_some() || get_heading_content(title) != inline {
+                    line = format!("{} #", line.trim_end());
+                }
+
+                line.push_str(&id);
+                self.lines.push(line);
+            },
+            TagOperator::UnorderedList => {
+                let line = format!("{}. {}", self.style.bullet, self.format_inline(&tag.content));
+                self.lines.push(line);
+            },
+            TagOperator::OrderedList => {
+                let line = format!("{}. {}", self.ordered_count, self.format_inline(&tag.content));
+                self.lines.push(line);
+
+                if self.style.ordered_numbering == OrderedNumbering::Increment {
+                    self.ordered_count += 1;
+                }
+            },
+            TagOperator::BlockQuote => {
+                let inline = self.format_inline(&tag.content);
+                let mut line = format!("> {}", inline);
+                // the markers ending the line are trimmed, a closing > keeps an empty quote i.e `> >`
+                if inline.is_empty() || bytes::trim_matches_content(&line) != inline {
+                    line = format!("{} >", line.trim_end());
+                }
+
+                self.lines.push(line);
+            },
+            TagOperator::FootnoteDefinition => {
+                let footnote = match metas.footnote {
+                    Some(footnote) => footnote,
+                    None => return
+                };
+
+                let prefix = format!("[^{}]: ", footnote.label);
+                for (idx, paragraph) in footnote.paragraphs.iter().enumerate() {
+                    if idx > 0 {
+                        self.lines.push(String::new());
+                    }
+
+                    // an empty definition i.e `[^1]:` is kept
+                    for (pos, line) in paragraph.split('\n').enumerate() {
+                        let indent = if idx == 0 && pos == 0 { prefix.as_str() } else { CONTINUATION_INDENT };
+                        let line = format!("{}{}", indent, self.format_inline(line));
+                        self.lines.push(line);
+                    }
+                }
+            },
+            TagOperator::HtmlBlock => {
+                self.lines.extend(tag.content.lines().map(|l| l.to_string()));
+            },
+            TagOperator::DisplayMath => {
+                let mut lines: Vec<String> = tag.content.lines().map(|l| l.trim().to_string()).collect();
+                let last = lines.len().saturating_sub(1);
+                // a fence or a line ending with $$ ends a $$ block, only a ```math block holds them
+                let fenced = lines
+                    .iter()
+                    .enumerate()
+                    .any(|(idx, l)| (idx > 0 && get_fence(l).is_some()) || (idx < last && l.ends_with("$$")));
+
+                if fenced {
+                    self.lines.push("```math".to_string());
+                    self.lines.extend(lines);
+                    self.lines.push("```".to_string());
+                    self.closing_line = Some(self.lines.len() - 1);
+                    return;
+                }
+
+                // a first line starting with a fence is kept on the opening line i.e $$```
+                match lines.first_mut() {
+                    Some(first) if get_fence(first).is_some() => first.insert_str(0, "$$"),
+                    _ => lines.insert(0, "$$".to_string())
+                }
+
+                // a last line ending with $$ is kept on the closing line i.e x$$$$
+                let single = lines.len() == 1;
+                match lines.last_mut() {
+                    Some(last) if !single && last.ends_with("$$") => last.push_str("$$"),
+                    _ => lines.push("$$".to_string())
+                }
+
+                self.lines.extend(lines);
+                self.closing_line = Some(self.lines.len() - 1);
+            },
+            TagOperator::DefinitionList => {
+                let items = metas.definition_list.map(|l| l.items).unwrap_or_default();
+                for item in items {
+                    let term = self.format_text_line(&item.term);
+                    self.lines.push(term);
+
+                    for definition in item.definitions {
+                        for (pos, line) in definition.lines().enumerate() {
+                            let indent = if pos == 0 { ": " } else { CONTINUATION_INDENT };
+                            let line = format!("{}{}", indent, self.format_inline(line));
+                            self.lines.push(line);
+                        }
+                    }
+                }
+            },
+            TagOperator::Table => {
+                if let Some(table) = metas.table {
+                    self.format_table(&table);
+                }
+            },
+            TagOperator::ContainerDirective | TagOperator::LeafDirective => {
+                if let Some(directive) = metas.directive {
+                    let label = Some(self.format_inline(&tag.content)).filter(|l| !l.is_empty());
+                    self.lines.push(format_directive(&DirectiveMeta { label, ..directive }));
+                    if tag.operator == TagOperator::ContainerDirective {
+                        self.containers.push(self.lines.len() - 1);
+                    }
+                }
+            },
+            TagOperator::ContainerDirectiveEnd => {
+                let fence = metas.directive.map_or(3, |d| d.fence);
+                self.lines.push(":".repeat(fence));
+                self.containers.pop();
+            },
+            // the source of the block is kept as is
+            TagOperator::Custom(_) => {
+                self.lines.extend(tag.content.lines().map(|l| l.to_string()));
+            },
+            TagOperator::AbbreviationDefinition => {
+                if let Some(abbreviation) = metas.abbreviation {
+                    self.lines.push(format!("*[{}]: {}", abbreviation.abbr, abbreviation.title));
+                }
+            }
+        }
+    }
+
+    /// Format Table
+    ///
+    /// # Description
+    /// Emit the table with its columns padded to their widest cell
+    ///
+    /// # Arguments
+    /// * `table` &TableMeta
+    fn format_table(&mut self, table: &TableMeta) {
+        let rows: Vec<Vec<String>> = std::iter::once(&table.header)
+            .chain(&table.rows)
+            .map(|row| row.iter().map(|cell| self.format_inline(cell).replace('|', "\\|")).collect())
+            .collect();
+
+        // the delimiter row needs at least 3 characters
+        let widths: Vec<usize> = (0..table.alignments.len())
+            .map(|col| rows.iter().map(|row| row[col].chars().count()).max().unwrap_or(0).max(3))
+            .collect();
+
+        let delimiter: Vec<String> = table.alignments
+            .iter()
+            .zip(&widths)
+            .map(|(alignment, width)| match alignment {
+                TableAlignment::Left => format!(":{}", "-".repeat(width - 1)),
+                TableAlignment::Center => format!(":{}:", "-".repeat(width - 2)),
+                TableAlignment::Right => format!("{}:", "-".repeat(width - 1)),
+                TableAlignment::None => "-".repeat(*width)
+            })
+            .collect();
+
+        for (idx, row) in rows.iter().enumerate() {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - cell.chars().count())))
+                .collect();
+
+            self.lines.push(format!("| {} |", cells.join(" | ")));
+            if idx == 0 {
+                self.lines.push(format!("| {} |", delimiter.join(" | ")));
+            }
+        }
+    }
+
+    /// Format Text Line
+    ///
+    /// # Description
+    /// Emit a line of text. Punctuations are escaped when the line would otherwise be parsed as a block
+    ///
+    /// # Arguments
+    /// * `content` &str
+    ///
+    /// # Return
+    /// String
+    fn format_text_line(&self, content: &str) -> String {
+        let mut line = self.format_inline(content);
+
+        for _ in 0..MAX_LINE_ESCAPES {
+            if self.is_text_line(&line) {
+                break;
+            }
+
+            line = match escape_punctuation(&line, self.options) {
+                Some(escaped) => escaped,
+                None => break
+            };
+        }
+
+        line
+    }
+
+    /// Format Inline
+    ///
+    /// # Description
+    /// Emit the inline content of a line. The text is escaped when it can't be emitted as is
+    ///
+    /// # Arguments
+    /// * `content` &str
+    ///
+    /// # Return
+    /// String
+    fn format_inline(&self, content: &str) -> String {
+        let nodes = get_inline_nodes(content, self.options);
+        let line = self.format_nodes(&nodes, false, None);
+        if merge_text(get_inline_nodes(&line, self.options)) == merge_text(nodes.clone()) {
+            return line;
+        }
+
+        self.format_nodes(&nodes, true, None)
+    }
+
+    /// Format Nodes
+    ///
+    /// # Description
+    /// Emit the inline nodes in the canonical style
+    ///
+    /// # Arguments
+    /// * `nodes` &[InlineNode]
+    /// * `escape` bool escape every punctuation of the text
+    /// * `parent` Option<char> marker of the enclosing emphasis
+    ///
+    /// # Return
+    /// String
+    fn format_nodes(&self, nodes: &[InlineNode], escape: bool, parent: Option<char>) -> String {
+        let mut markdown = String::new();
+
+        for (idx, node) in nodes.iter().enumerate() {
+            let formatted = match node {
+                InlineNode::Text(text) => {
+                    let escaped = self.escape_text(text, escape);
+                    // a punctuation following a reference may extend it i.e @user\-
+                    let after_reference = idx > 0 && matches!(nodes[idx - 1], InlineNode::Mention(_) | InlineNode::IssueRef { .. });
+                    match escaped.chars().next() {
+                        Some(c) if escape && after_reference && c.is_ascii_punctuation() && c != '\\' => format!("\\{}", escaped),
+                        _ => escaped
+                    }
+                },
+                InlineNode::Strong(children) | InlineNode::Emphasis(children) => {
+                    let size = if matches!(node, InlineNode::Strong(_)) { 2 } else { 1 };
+                    let marker = self.get_emphasis_marker(&markdown, nodes.get(idx + 1), parent, children);
+                    let delimiter = marker.to_string().repeat(size);
+                    format!("{}{}{}", delimiter, self.format_nodes(children, escape, Some(marker)), delimiter)
+                },
+                InlineNode::Strike(children) => format!("~~{}~~", self.format_nodes(children, escape, None)),
+                InlineNode::Code(code) => format_code_span(code),
+                InlineNode::Link { title, url, tooltip } => {
+                    format!("[{}]({}{})", self.format_nodes(title, escape, None), url, format_link_title(tooltip))
+                },
+                InlineNode::Image { alt_text, url, tooltip } => {
+                    format!("![{}]({}{})", self.escape_text(alt_text, escape), url, format_link_title(tooltip))
+                },
+                InlineNode::FootnoteRef(label) => format!("[^{}]", label),
+                InlineNode::Html(html) => html.clone(),
+                InlineNode::InlineMath(math) => match nodes.get(idx + 1) {
+                    // a $ followed by a digit doesn't close the math i.e $x$1
+                    Some(InlineNode::Text(text)) if text.starts_with(|c: char| c.is_ascii_digit()) => format!("$${}$$", math),
+                    _ => format_inline_math(math)
+                },
+                InlineNode::Emoji { shortcode, .. } => format!(":{}:", shortcode),
+                InlineNode::Mention(user) => format!("@{}", user),
+                InlineNode::IssueRef { repository, number } => format!("{}#{}", repository.as_deref().unwrap_or(""), number),
+                InlineNode::TextDirective { directive, label } => {
+                    let label = Some(self.format_nodes(label, escape, None)).filter(|_| directive.label.is_some());
+                    format_directive(&DirectiveMeta { label, ..directive.clone() })
+                },
+                InlineNode::Custom(node) => node.source().to_string()
+            };
+
+            markdown.push_str(&formatted);
+        }
+
+        markdown
+    }
+
+    /// Escape Text
+    ///
+    /// # Description
+    /// Emit a text node. The text is kept as is unless it would be parsed as an other node
+    ///
+    /// # Arguments
+    /// * `text` &str
+    /// * `force` bool escape the punctuation even if the text could be kept as is
+    ///
+    /// # Return
+    /// String
+    fn escape_text(&self, text: &str, force: bool) -> String {
+        let nodes = get_inline_nodes(text, self.options);
+        let is_plain = nodes.is_empty() || nodes == [InlineNode::Text(text.to_string())];
+        if is_plain && !force {
+            return text.to_string();
+        }
+
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if ESCAPED_CHARACTERS.contains(c) {
+                escaped.push('\\');
+            }
+
+            escaped.push(c);
+        }
+
+        escaped
+    }
+
+    /// Get Emphasis Marker
+    ///
+    /// # Description
+    /// Return the marker of the style. An intraword emphasis always use * as _ can't be used within a word
+    /// nor before a reference i.e @user, so an emphasis enclosing one use _. A nested or a following
+    /// emphasis use the other marker so its delimiters aren't merged with the adjacent ones
+    ///
+    /// # Arguments
+    /// * `before` &str
+    /// * `next` Option<&InlineNode>
+    /// * `parent` Option<char>
+    /// * `children` &[InlineNode]
+    ///
+    /// # Return
+    /// char
+    fn get_emphasis_marker(&self, before: &str, next: Option<&InlineNode>, parent: Option<char>, children: &[InlineNode]) -> char {
+        if is_intraword(before.chars().last(), next) {
+            return '*';
+        }
+
+        let intraword_child = children.iter().enumerate().any(|(idx, child)| {
+            let previous = idx.checked_sub(1).and_then(|idx| match &children[idx] {
+                InlineNode::Text(text) => text.chars().last(),
+                InlineNode::Mention(user) => user.chars().last(),
+                InlineNode::IssueRef { number, .. } => number.to_string().chars().last(),
+                _ => None
+            });
+
+            matches!(child, InlineNode::Strong(_) | InlineNode::Emphasis(_)) && is_intraword(previous, children.get(idx + 1))
+        });
+
+        if intraword_child && parent != Some('_') {
+            return '_';
+        }
+
+        let other = if self.style.emphasis == '*' { '_' } else { '*' };
+        if parent == Some(self.style.emphasis) || before.ends_with(self.style.emphasis) {
+            return other;
+        }
+
+        self.style.emphasis
+    }
+
+    /// Push Paragraph Line
+    ///
+    /// # Description
+    /// Push a line of a paragraph. A line exceeding the width is wrapped at the spaces which
+    /// aren't part of an inline node, and every wrapped line is formatted on its own
+    ///
+    /// # Arguments
+    /// * `content` &str
+    fn push_paragraph_line(&mut self, content: &str) {
+        let line = self.format_text_line(content);
+        let width = match self.style.wrap_width {
+            Some(width) if line.chars().count() > width => width,
+            _ => {
+                self.lines.push(line);
+                return;
+            }
+        };
+
+        let mut current: Option<String> = None;
+        // Part of the content emitted on the previous line
+        let mut pushed: Option<String> = None;
+        for word in split_words(content, self.options) {
+            let previous = match current.take() {
+                Some(previous) => previous,
+                None => {
+                    current = Some(word);
+                    continue;
+                }
+            };
+
+            let candidate = format!("{} {}", previous, word);
+            if self.format_text_line(&candidate).chars().count() <= width {
+                current = Some(candidate);
+                continue;
+            }
+
+            // the line can't be broken if the escapes of the previous part would change its nodes
+            // i.e a lone <b> is an html block
+            let formatted = self.format_text_line(&previous);
+            if !self.is_line_part(&formatted, &previous) || !is_text_after(&formatted, &self.format_inline(&word), self.options) {
+                current = Some(candidate);
+                continue;
+            }
+
+            self.lines.push(formatted);
+            pushed = Some(previous);
+            current = Some(word);
+        }
+
+        let last = match current {
+            Some(last) => last,
+            None => return
+        };
+
+        let formatted = self.format_text_line(&last);
+        match pushed {
+            // the last part can't start a line, it's joined to the previous one
+            Some(previous) if !self.is_line_part(&formatted, &last) => {
+                self.lines.pop();
+                let joined = self.format_text_line(&format!("{} {}", previous, last));
+                self.lines.push(joined);
+            },
+            _ => self.lines.push(formatted)
+        }
+    }
+
+    /// Is Line Part
+    ///
+    /// # Description
+    /// Check that a part of a paragraph line can be emitted on its own line: it's parsed as text and the
+    /// escapes added to it don't change its inline nodes
+    ///
+    /// # Arguments
+    /// * `formatted` &str
+    /// * `content` &str
+    ///
+    /// # Return
+    /// bool
+    fn is_line_part(&self, formatted: &str, content: &str) -> bool {
+        self.is_text_line(formatted)
+            && merge_text(get_inline_nodes(formatted, self.options)) == merge_text(get_inline_nodes(content, self.options))
+    }
+
+    /// Is Text Line
+    ///
+    /// # Description
+    /// Check whether the line is parsed as a line of text after the emitted lines of its block
+    ///
+    /// # Arguments
+    /// * `line` &str
+    ///
+    /// # Return
+    /// bool
+    fn is_text_line(&self, line: &str) -> bool {
+        // the lines of the block since the last blank line, a closed block can't be read from its
+        // closing line i.e $$
+        let start = self.lines
+            .iter()
+            .rposition(|l| l.is_empty())
+            .max(self.closing_line)
+            .map_or(0, |idx| idx + 1);
+
+        // the open containers are kept so that a ::: closes them
+        let previous: Vec<&str> = self.containers
+            .iter()
+            .filter(|idx| **idx < start)
+            .map(|idx| self.lines[*idx].as_str())
+            .chain(self.lines[start..].iter().map(String::as_str))
+            .collect();
+
+        is_text_after(&previous.join("\n"), line, self.options)
+    }
+
+    /// Push Blank
+    ///
+    /// # Description
+    /// Push a blank line unless the previous line is already blank
+    fn push_blank(&mut self) {
+        self.ordered_count = 1;
+        if self.lines.last().is_some_and(|l| !l.is_empty()) {
+            self.lines.push(String::new());
+        }
+    }
+}
+
+/// Is Intraword
+///
+/// # Description
+/// Check whether an emphasis between the character and the node is within a word, or before a reference
+///
+/// # Arguments
+/// * `before` Option<char>
+/// * `next` Option<&InlineNode>
+///
+/// # Return
+/// bool
+fn is_intraword(before: Option<char>, next: Option<&InlineNode>) -> bool {
+    before.is_some_and(char::is_alphanumeric)
+        || matches!(next, Some(InlineNode::Text(t)) if t.starts_with(char::is_alphanumeric))
+        || matches!(next, Some(InlineNode::Mention(_) | InlineNode::IssueRef { .. }))
+}
+
+/// Is Text After
+///
+/// # Description
+/// Check whether the line is parsed as a line of text when it follow the previous lines
+///
+/// # Arguments
+/// * `previous` &str
+/// * `line` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// bool
+fn is_text_after(previous: &str, line: &str, options: &ParserOptions) -> bool {
+    let content = format!("{}\n{}", previous, line);
+    let tokens = match token::get_textual_tokens_with_options(&content, options) {
+        Ok(tokens) => tokens,
+        Err(_) => return false
+    };
+
+    tokens
+        .get(&previous.lines().count().max(1))
+        .is_some_and(|t| t.tag.is_none() && t.text.as_ref().is_some_and(|text| !text.content.is_empty()))
+}
+
+/// Split Words
+///
+/// # Description
+/// Split a line at the spaces and the tabs which aren't part of an inline node i.e a link or an emphasis
+///
+/// # Arguments
+/// * `line` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Vec<String>
+fn split_words(line: &str, options: &ParserOptions) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for (idx, c) in line.char_indices() {
+        let space = c == ' ' || c == '\t';
+        // consecutive spaces are collapsed at the breaks
+        if space && current.is_empty() && !words.is_empty() {
+            continue;
+        }
+
+        if space && !current.is_empty() && is_breakable(line, idx, options) {
+            words.push(std::mem::take(&mut current));
+            continue;
+        }
+
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+/// Is Breakable
+///
+/// # Description
+/// Check that breaking the line at the index doesn't change the inline nodes
+///
+/// # Arguments
+/// * `line` &str
+/// * `idx` usize
+/// * `options` &ParserOptions
+///
+/// # Return
+/// bool
+fn is_breakable(line: &str, idx: usize, options: &ParserOptions) -> bool {
+    let mut nodes = get_inline_nodes(&line[..idx], options);
+    nodes.push(InlineNode::Text(line[idx..idx + 1].to_string()));
+    nodes.extend(get_inline_nodes(&line[idx + 1..], options));
+
+    merge_text(nodes) == merge_text(get_inline_nodes(line, options))
+}
+
+/// Merge Text
+///
+/// # Description
+/// Merge the consecutive text nodes in order to compare two list of nodes
+///
+/// # Arguments
+/// * `nodes` Vec<InlineNode>
+///
+/// # Return
+/// Vec<InlineNode>
+fn merge_text(nodes: Vec<InlineNode>) -> Vec<InlineNode> {
+    let mut merged: Vec<InlineNode> = Vec::new();
+
+    for node in nodes {
+        match (merged.last_mut(), node) {
+            (Some(InlineNode::Text(previous)), InlineNode::Text(text)) => previous.push_str(&text),
+            (_, node) => merged.push(node)
+        }
+    }
+
+    merged
+}
+
+/// Escape Punctuation
+///
+/// # Description
+/// Escape the first punctuation of the line which isn't escaped yet and whose escape keeps the inline
+/// nodes i.e the [ of a footnote reference is skipped. The first punctuation is escaped if none keep them
+///
+/// # Arguments
+/// * `line` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Option<String>
+fn escape_punctuation(line: &str, options: &ParserOptions) -> Option<String> {
+    let nodes = merge_text(get_inline_nodes(line, options));
+    let mut first = None;
+    let mut escaped = false;
+
+    for (idx, c) in line.char_indices() {
+        if c == '\\' && !escaped {
+            escaped = true;
+            continue;
+        }
+
+        if c.is_ascii_punctuation() && !escaped {
+            let candidate = format!("{}\\{}", &line[..idx], &line[idx..]);
+            if merge_text(get_inline_nodes(&candidate, options)) == nodes {
+                return Some(candidate);
+            }
+
+            first.get_or_insert(candidate);
+        }
+
+        escaped = false;
+    }
+
+    first
+}
+
+/// Format Code Span
+///
+/// # Description
+/// Emit a code span with a delimiter longer than the backticks of the code
+///
+/// # Arguments
+/// * `code` &str
+///
+/// # Return
+/// String
+pub fn format_code_span(code: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in code.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+
+    let delimiter = "`".repeat(longest + 1);
+    let padded = code.starts_with('`')
+        || code.ends_with('`')
+        || (code.len() > 2 && code.starts_with(' ') && code.ends_with(' '));
+
+    if padded {
+        return format!("{} {} {}", delimiter, code, delimiter);
+    }
+
+    format!("{}{}{}", delimiter, code, delimiter)
+}
+
+/// Format Inline Math
+///
+/// # Description
+/// Emit an inline math between $, or between $$ when the math can't be closed by a single $ i.e
+/// it ends with a space
+///
+/// # Arguments
+/// * `math` &str
+///
+/// # Return
+/// String
+pub fn format_inline_math(math: &str) -> String {
+    let inline = format!("${}$", math);
+    if get_inline_math_at(&inline, 0).is_some_and(|(_, len)| len == inline.len()) {
+        return inline;
+    }
+
+    format!("$${}$$", math)
+}
+
+/// Format Link Title
+///
+/// # Description
+/// Emit the title following the url of a link or an image, the quotes of the title are escaped
+///
+/// # Arguments
+/// * `title` &Option<String>
+///
+/// # Return
+/// String
+pub fn format_link_title(title: &Option<String>) -> String {
+    title
+        .as_ref()
+        .map(|title| format!(" \"{}\"", title.replace('\\', "\\\\").replace('"', "\\\"")))
+        .unwrap_or_default()
+}
+
+/// Get Code Fence
+///
+/// # Description
+/// Get the fence of the style for the code block starting at the line. The fence is longer than
+/// any run of the fence character starting a line of the code so that the code can't close it
+///
+/// # Arguments
+/// * `code_tokens` &BTreeMap<usize, CodeToken>
+/// * `start` usize
+/// * `character` char
+///
+/// # Return
+/// String
+fn get_code_fence(code_tokens: &BTreeMap<usize, CodeToken>, start: usize, character: char) -> String {
+    let longest = code_tokens
+        .range(start + 1..)
+        .take_while(|(_, code)| code.operator == CodeOperator::BlockCodeContent)
+        .map(|(_, code)| code.content.len() - code.content.trim_start_matches(character).len())
+        .max()
+        .unwrap_or(0);
+
+    character.to_string().repeat(longest.max(2) + 1)
+}
+
+/// Get Indent
+///
+/// # Description
+/// Return the number of leading whitespace of a line
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// usize
+fn get_indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
@@ -0,0 +1,9 @@
+/// Render
+///
+/// # Description
+/// Renderers of the parsed markdown. The renderers consume the textual tokens and the code tokens
+/// which are returned by the parser
+pub mod html;
+pub mod markdown;
+pub mod plain;
+pub mod terminal;
@@ -0,0 +1,247 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::options::ParserOptions;
+use crate::parser::code::CodeOperator;
+use crate::parser::inline::{get_inline_nodes, get_plain_text};
+use crate::token::TextualToken;
+use crate::token::code::CodeToken;
+use crate::token::tag::{TagToken, TagOperator};
+
+// Appended to a truncated text
+const ELLIPSIS: char = '…';
+
+/// Plain Text Renderer
+///
+/// Keep track of the paragraph being rendered while walking through the lines
+struct PlainTextRenderer<'a> {
+    lines: Vec<String>,
+    options: &'a ParserOptions,
+    paragraph: Vec<String>
+}
+
+/// Render Plain Text
+///
+/// # Description
+/// Render the textual tokens and the code tokens to plain text. A paragraph is rendered on a single line,
+/// each heading, list item, blockquote and line of code on its own line. Links keep their title and
+/// images their alt text while the urls and the html are dropped
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `code_tokens` &BTreeMap<usize, CodeToken>
+/// * `options` &ParserOptions
+///
+/// # Return
+/// String
+pub fn render_plain_text(
+    tokens: &BTreeMap<usize, TextualToken>,
+    code_tokens: &BTreeMap<usize, CodeToken>,
+    options: &ParserOptions
+) -> String {
+    let mut renderer = PlainTextRenderer {
+        lines: Vec::new(),
+        options,
+        paragraph: Vec::new()
+    };
+
+    let lines: BTreeSet<usize> = tokens.keys().chain(code_tokens.keys()).copied().collect();
+    // The content of a ```math fence is already part of the display math
+    let mut in_math_fence = false;
+
+    for idx in lines {
+        let textual = tokens.get(&idx);
+        let code = code_tokens.get(&idx);
+
+        if let Some(tag) = textual.and_then(|t| t.tag.as_ref()).filter(|t| t.operator == TagOperator::DisplayMath) {
+            renderer.render_tag(tag);
+            in_math_fence = code.is_some_and(|c| c.operator == CodeOperator::BlockCodeStart);
+            continue;
+        }
+
+        if let Some(code) = code {
+            if in_math_fence {
+                in_math_fence = code.operator != CodeOperator::BlockCodeEnd;
+                continue;
+            }
+
+            renderer.close_paragraph();
+            if code.operator == CodeOperator::BlockCodeContent {
+                renderer.lines.push(code.content.to_string());
+            }
+
+            continue;
+        }
+
+        if let Some(textual) = textual {
+            renderer.render_textual(textual);
+        }
+    }
+
+    renderer.close_paragraph();
+    renderer.lines.join("\n")
+}
+
+/// Truncate Plain Text
+///
+/// # Description
+/// Truncate the text to the max number of characters, ellipsis included. The text is cut at the
+/// last whitespace preceding the limit unless a single word exceeds it
+///
+/// # Arguments
+/// * `text` &str
+/// * `max_length` usize
+///
+/// # Return
+/// String
+pub fn truncate_plain_text(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_string();
+    }
+
+    if max_length == 0 {
+        return String::new();
+    }
+
+    let end = text.char_indices().nth(max_length - 1).map_or(text.len(), |(idx, _)| idx);
+    let window = &text[..end];
+    // a cut at the limit is at a word boundary when the next character is a whitespace
+    let at_boundary = text[end..].starts_with(char::is_whitespace);
+    let cut = match window.rfind(char::is_whitespace) {
+        Some(pos) if !at_boundary => &window[..pos],
+        _ => window
+    };
+
+    let mut truncated = cut.trim_end().to_string();
+    truncated.push(ELLIPSIS);
+
+    truncated
+}
+
+impl<'a> PlainTextRenderer<'a> {
+    /// Render Textual
+    ///
+    /// # Description
+    /// Render a textual token. Consecutive lines of text are joined into a paragraph
+    ///
+    /// # Arguments
+    /// * `textual` &TextualToken
+    fn render_textual(&mut self, textual: &TextualToken) {
+        if let Some(tag) = &textual.tag {
+            self.render_tag(tag);
+            return;
+        }
+
+        let text = match &textual.text {
+            Some(text) => text,
+            None => return
+        };
+
+        if text.content.is_empty() {
+            self.close_paragraph();
+            return;
+        }
+
+        let line = self.render_inline(&text.content);
+        if !line.is_empty() {
+            self.paragraph.push(line);
+        }
+    }
+
+    /// Render Tag
+    ///
+    /// # Description
+    /// Render a tag token on its own lines
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    fn render_tag(&mut self, tag: &TagToken) {
+        self.close_paragraph();
+
+        match tag.operator {
+            TagOperator::Heading
+            | TagOperator::UnorderedList
+            | TagOperator::OrderedList
+            | TagOperator::BlockQuote => {
+                let line = self.render_inline(&tag.content);
+                self.push_line(line);
+            },
+            TagOperator::FootnoteDefinition => {
+                for line in tag.content.lines() {
+                    let line = self.render_inline(line.trim());
+                    self.push_line(line);
+                }
+            },
+            TagOperator::DisplayMath => self.push_line(tag.content.trim().to_string()),
+            TagOperator::DefinitionList => {
+                let items = tag.metas.as_ref().and_then(|m| m.definition_list.as_ref()).map(|l| l.items.clone());
+                for item in items.unwrap_or_default() {
+                    let term = self.render_inline(&item.term);
+                    self.push_line(term);
+
+                    for definition in item.definitions {
+                        let definition = self.render_inline(&definition);
+                        self.push_line(definition);
+                    }
+                }
+            },
+            // a line per row, the cells are separated by a tab
+            TagOperator::Table => {
+                let table = tag.metas.as_ref().and_then(|m| m.table.as_ref());
+                for row in table.into_iter().flat_map(|t| std::iter::once(&t.header).chain(&t.rows)) {
+                    let cells: Vec<String> = row.iter().map(|cell| self.render_inline(cell)).collect();
+                    self.push_line(cells.join("\t"));
+                }
+            },
+            TagOperator::ContainerDirective | TagOperator::LeafDirective => {
+                let label = self.render_inline(&tag.content);
+                self.push_line(label);
+            },
+            TagOperator::Custom(ref node) => {
+                for line in node.render_text().lines() {
+                    self.push_line(line.to_string());
+                }
+            },
+            TagOperator::HtmlBlock | TagOperator::AbbreviationDefinition | TagOperator::ContainerDirectiveEnd => {}
+        }
+    }
+
+    /// Render Inline
+    ///
+    /// # Description
+    /// Get the text of a line without its markup
+    ///
+    /// # Arguments
+    /// * `content` &str
+    ///
+    /// # Return
+    /// String
+    fn render_inline(&self, content: &str) -> String {
+        get_plain_text(&get_inline_nodes(content, self.options)).trim().to_string()
+    }
+
+    /// Push Line
+    ///
+    /// # Description
+    /// Push a non empty line
+    ///
+    /// # Arguments
+    /// * `line` String
+    fn push_line(&mut self, line: String) {
+        if !line.is_empty() {
+            self.lines.push(line);
+        }
+    }
+
+    /// Close Paragraph
+    ///
+    /// # Description
+    /// Join the lines of the paragraph being rendered
+    fn close_paragraph(&mut self) {
+        if self.paragraph.is_empty() {
+            return;
+        }
+
+        let paragraph = self.paragraph.join(" ");
+        self.paragraph.clear();
+        self.lines.push(paragraph);
+    }
+}
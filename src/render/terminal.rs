@@ -0,0 +1,738 @@
+use std::clone::Clone;
+use std::default::Default;
+use std::collections::{BTreeMap, BTreeSet};
+use crate::options::ParserOptions;
+use crate::parser::code::{CodeOperator, get_info_string};
+use crate::parser::table::TableAlignment;
+use crate::parser::inline::{InlineNode, get_inline_nodes, get_plain_text};
+use crate::token::TextualToken;
+use crate::token::code::CodeToken;
+use crate::token::footnote::{Footnotes, get_footnotes};
+use crate::token::tag::{TagToken, TagOperator};
+use super::html::get_heading_number;
+
+// SGR parameters of the headings from H1 to H6
+const HEADING_STYLES: [&str; 6] = ["1;35", "1;36", "1;34", "1;32", "1;33", "1;37"];
+// Resets every SGR attribute
+const RESET: &str = "\x1b[0m";
+// Glyph of an unordered list item
+const BULLET: &str = "•";
+// Indentation of the list items and the block quotes
+const BLOCK_INDENT: &str = "  ";
+// Bar preceding each line of a block quote
+const QUOTE_BAR: &str = "│ ";
+// Indentation of the definitions and the display math
+const DEFINITION_INDENT: &str = "    ";
+// Separator of the cells of a table
+const TABLE_SEPARATOR: &str = " │ ";
+// Rule below the header of a table
+const TABLE_RULE: &str = "─";
+const TABLE_RULE_SEPARATOR: &str = "─┼─";
+// Default wrapping width
+const DEFAULT_WIDTH: usize = 80;
+
+/// Terminal Options
+///
+/// # Description
+/// Options of the terminal renderer
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TerminalOptions {
+    // Number of columns the text is wrapped to
+    pub width: usize,
+    // Emit the ANSI SGR and OSC-8 escape sequences. Without them the output is plain text
+    pub colour: bool
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        TerminalOptions {
+            width: DEFAULT_WIDTH,
+            colour: true
+        }
+    }
+}
+
+/// Terminal Renderer
+///
+/// Keep track of the blocks which are opened while walking through the lines
+struct TerminalRenderer<'a> {
+    lines: Vec<String>,
+    options: &'a ParserOptions,
+    terminal: &'a TerminalOptions,
+    footnotes: Footnotes,
+    paragraph: Vec<String>,
+    blockquote: Vec<String>,
+    // Kind of the list being rendered and the number of its next item
+    list: Option<(TagOperator, usize)>,
+    // Language and lines of the code block being rendered
+    code: Option<(String, Vec<String>)>
+}
+
+/// Render Terminal
+///
+/// # Description
+/// Render the textual tokens and the code tokens for a terminal. Blocks are separated by a blank line
+/// and the text is wrapped to the width of the options
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `code_tokens` &BTreeMap<usize, CodeToken>
+/// * `options` &ParserOptions
+/// * `terminal` &TerminalOptions
+///
+/// # Return
+/// String
+pub fn render_terminal(
+    tokens: &BTreeMap<usize, TextualToken>,
+    code_tokens: &BTreeMap<usize, CodeToken>,
+    options: &ParserOptions,
+    terminal: &TerminalOptions
+) -> String {
+    let mut renderer = TerminalRenderer {
+        lines: Vec::new(),
+        options,
+        terminal,
+        footnotes: get_footnotes(tokens),
+        paragraph: Vec::new(),
+        blockquote: Vec::new(),
+        list: None,
+        code: None
+    };
+
+    let lines: BTreeSet<usize> = tokens.keys().chain(code_tokens.keys()).copied().collect();
+    // A ```math fence is rendered as a display math instead of a code block
+    let mut in_math_fence = false;
+
+    for idx in lines {
+        let textual = tokens.get(&idx);
+        let code = code_tokens.get(&idx);
+
+        if let Some(tag) = textual.and_then(|t| t.tag.as_ref()).filter(|t| t.operator == TagOperator::DisplayMath) {
+            renderer.render_tag(tag);
+            in_math_fence = code.is_some_and(|c| c.operator == CodeOperator::BlockCodeStart);
+            continue;
+        }
+
+        if let Some(code) = code {
+            if in_math_fence {
+                in_math_fence = code.operator != CodeOperator::BlockCodeEnd;
+                continue;
+            }
+
+            renderer.render_code(code);
+            continue;
+        }
+
+        if let Some(textual) = textual {
+            renderer.render_textual(textual);
+        }
+    }
+
+    renderer.close_blocks();
+    renderer.close_code();
+
+    let mut output = renderer.lines.join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    output
+}
+
+impl<'a> TerminalRenderer<'a> {
+    /// Render Code
+    ///
+    /// # Description
+    /// Gather the lines of a code block. The block is boxed once closed
+    ///
+    /// # Arguments
+    /// * `code` &CodeToken
+    fn render_code(&mut self, code: &CodeToken) {
+        match code.operator {
+            CodeOperator::BlockCodeStart => {
+                self.close_blocks();
+                let lang = get_info_string(&code.content);
+                self.code = Some((strip_control_characters(lang), Vec::new()));
+            },
+            CodeOperator::BlockCodeContent => {
+                if let Some((_, lines)) = self.code.as_mut() {
+                    lines.push(strip_control_characters(&code.content.replace('\t', "    ")));
+                }
+            },
+            CodeOperator::BlockCodeEnd => self.close_code()
+        }
+    }
+
+    /// Render Textual
+    ///
+    /// # Description
+    /// Render a textual token. Consecutive lines of text, list items and blockquotes are grouped
+    ///
+    /// # Arguments
+    /// * `textual` &TextualToken
+    fn render_textual(&mut self, textual: &TextualToken) {
+        if let Some(tag) = &textual.tag {
+            self.render_tag(tag);
+            return;
+        }
+
+        let text = match &textual.text {
+            Some(text) => text,
+            None => return
+        };
+
+        if text.content.is_empty() {
+            self.close_blocks();
+            return;
+        }
+
+        let line = self.render_inline(&text.content);
+        self.close_list();
+        self.close_blockquote();
+        self.paragraph.push(line);
+    }
+
+    /// Render Tag
+    ///
+    /// # Description
+    /// Render a tag token
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    fn render_tag(&mut self, tag: &TagToken) {
+        match tag.operator {
+            TagOperator::Heading => {
+                self.close_blocks();
+                let level = tag.metas.as_ref().map(|m| get_heading_number(&m.heading_kind)).unwrap_or(1);
+                let title = strip_control_characters(&get_plain_text(&get_inline_nodes(&tag.content, self.options)));
+                let heading = if self.terminal.colour {
+                    format!("\x1b[{}m{}{}", HEADING_STYLES[level - 1], title, RESET)
+                } else {
+                    format!("{} {}", "#".repeat(level), title)
+                };
+
+                self.push_block(vec![heading]);
+            },
+            TagOperator::UnorderedList | TagOperator::OrderedList => {
+                self.close_paragraph();
+                self.close_blockquote();
+                if self.list.as_ref().map(|(operator, _)| operator) != Some(&tag.operator) {
+                    self.close_list();
+                    self.start_block();
+                    self.list = Some((tag.operator.clone(), 1));
+                }
+
+                let marker = match self.list.as_mut() {
+                    Some((TagOperator::OrderedList, number)) => {
+                        *number += 1;
+                        format!("{}{}. ", BLOCK_INDENT, *number - 1)
+                    },
+                    _ => format!("{}{} ", BLOCK_INDENT, BULLET)
+                };
+
+                let content = self.render_inline(&tag.content);
+                let lines = self.wrap_with_marker(&content, &marker);
+                self.lines.extend(lines);
+            },
+            TagOperator::BlockQuote => {
+                self.close_paragraph();
+                self.close_list();
+                let content = self.render_inline(&tag.content);
+                self.blockquote.push(content);
+            },
+            TagOperator::FootnoteDefinition => {
+                self.close_blocks();
+                let label = tag.metas.as_ref().and_then(|m| m.footnote.as_ref()).map(|f| f.label.clone()).unwrap_or_default();
+                let marker = format!("[{}] ", self.get_footnote_number(&label).unwrap_or_else(|| strip_control_characters(&label)));
+                let content: Vec<String> = tag.content.lines().map(|l| self.render_inline(l.trim())).collect();
+                let lines = self.wrap_with_marker(&content.join(" "), &marker);
+                self.push_block(lines);
+            },
+            TagOperator::HtmlBlock => {
+                self.close_blocks();
+                self.push_block(tag.content.lines().map(strip_control_characters).collect());
+            },
+            TagOperator::Custom(ref node) => {
+                self.close_blocks();
+                self.push_block(node.render_text().lines().map(strip_control_characters).collect());
+            },
+            TagOperator::DisplayMath => {
+                self.close_blocks();
+                let lines = tag.content.trim().lines().map(|l| format!("{}{}", DEFINITION_INDENT, strip_control_characters(l.trim()))).collect();
+                self.push_block(lines);
+            },
+            TagOperator::DefinitionList => {
+                self.close_blocks();
+                self.render_definition_list(tag);
+            },
+            TagOperator::Table => {
+                self.close_blocks();
+                self.render_table(tag);
+            },
+            // the name of the directive is written before its label i.e TIP: Title
+            TagOperator::ContainerDirective | TagOperator::LeafDirective => {
+                self.close_blocks();
+                let name = tag.metas.as_ref().and_then(|m| m.directive.as_ref()).map(|d| strip_control_characters(&d.name.to_uppercase())).unwrap_or_default();
+                let line = match self.render_inline(&tag.content) {
+                    label if label.is_empty() => name,
+                    label => format!("{}: {}", name, label)
+                };
+
+                let line = self.style(&line, "1", "22");
+                self.push_block(vec![line]);
+            },
+            TagOperator::ContainerDirectiveEnd => self.close_blocks(),
+            // only used to annotate the text
+            TagOperator::AbbreviationDefinition => {}
+        }
+    }
+
+    /// Render Definition List
+    ///
+    /// # Description
+    /// Render the terms in bold followed by their indented definitions
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    fn render_definition_list(&mut self, tag: &TagToken) {
+        let items = match tag.metas.as_ref().and_then(|m| m.definition_list.as_ref()) {
+            Some(list) => list.items.clone(),
+            None => return
+        };
+
+        let mut lines = Vec::new();
+        for item in items {
+            let term = self.render_inline(&item.term);
+            lines.push(self.style(&term, "1", "22"));
+
+            for definition in item.definitions {
+                let definition = self.render_inline(&definition);
+                lines.extend(self.wrap_with_marker(&definition, DEFINITION_INDENT));
+            }
+        }
+
+        self.push_block(lines);
+    }
+
+    /// Render Table
+    ///
+    /// # Description
+    /// Render the table with its columns padded to their widest cell, the header is in bold and
+    /// separated from the rows by a rule
+    ///
+    /// # Arguments
+    /// * `tag` &TagToken
+    fn render_table(&mut self, tag: &TagToken) {
+        let table = match tag.metas.as_ref().and_then(|m| m.table.as_ref()) {
+            Some(table) => table,
+            None => return
+        };
+
+        let rows: Vec<Vec<String>> = std::iter::once(&table.header)
+            .chain(&table.rows)
+            .map(|row| row.iter().map(|cell| self.render_inline(cell)).collect())
+            .collect();
+
+        let widths: Vec<usize> = (0..table.alignments.len())
+            .map(|col| rows.iter().map(|row| get_visible_width(&row[col])).max().unwrap_or(0))
+            .collect();
+
+        let mut lines = Vec::new();
+        for (idx, row) in rows.iter().enumerate() {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&table.alignments)
+                .zip(&widths)
+                .map(|((cell, alignment), width)| pad_cell(cell, alignment, *width))
+                .collect();
+
+            let line = format!("{}{}", BLOCK_INDENT, cells.join(TABLE_SEPARATOR));
+            if idx > 0 {
+                lines.push(line);
+                continue;
+            }
+
+            lines.push(self.style(&line, "1", "22"));
+            let rule: Vec<String> = widths.iter().map(|width| TABLE_RULE.repeat(*width)).collect();
+            lines.push(format!("{}{}", BLOCK_INDENT, rule.join(TABLE_RULE_SEPARATOR)));
+        }
+
+        self.push_block(lines);
+    }
+
+    /// Render Inline
+    ///
+    /// # Description
+    /// Render the inline content of a line
+    ///
+    /// # Arguments
+    /// * `content` &str
+    ///
+    /// # Return
+    /// String
+    fn render_inline(&self, content: &str) -> String {
+        let nodes = get_inline_nodes(content, self.options);
+        self.render_nodes(&nodes)
+    }
+
+    /// Render Nodes
+    ///
+    /// # Description
+    /// Render a list of inline nodes with the SGR attributes of their style. The control characters
+    /// of the content are removed so that the markdown can't emit its own escape sequences
+    ///
+    /// # Arguments
+    /// * `nodes` &[InlineNode]
+    ///
+    /// # Return
+    /// String
+    fn render_nodes(&self, nodes: &[InlineNode]) -> String {
+        let mut output = String::new();
+
+        for node in nodes {
+            let rendered = match node {
+                InlineNode::Text(text) => strip_control_characters(text),
+                InlineNode::Strong(children) => self.style(&self.render_nodes(children), "1", "22"),
+                InlineNode::Emphasis(children) => self.style(&self.render_nodes(children), "3", "23"),
+                InlineNode::Strike(children) => self.style(&self.render_nodes(children), "9", "29"),
+                InlineNode::Code(code) => self.style(&strip_control_characters(code), "36", "39"),
+                InlineNode::Link { title, url, .. } => self.render_link(&self.render_nodes(title), url),
+                InlineNode::Image { alt_text, url, .. } => self.render_link(&strip_control_characters(alt_text), url),
+                InlineNode::FootnoteRef(label) => format!(
+                    "[{}]",
+                    self.get_footnote_number(label).unwrap_or_else(|| format!("^{}", strip_control_characters(label)))
+                ),
+                InlineNode::Html(_) => String::new(),
+                InlineNode::InlineMath(math) => strip_control_characters(math),
+                InlineNode::Emoji { unicode, .. } => unicode.clone(),
+                InlineNode::Mention(user) => format!("@{}", user),
+                InlineNode::IssueRef { repository, number } => format!("{}#{}", repository.as_deref().unwrap_or(""), number),
+                InlineNode::TextDirective { label, .. } => self.render_nodes(label),
+                InlineNode::Custom(node) => strip_control_characters(&node.render_text())
+            };
+
+            output.push_str(&rendered);
+        }
+
+        output
+    }
+
+    /// Render Link
+    ///
+    /// # Description
+    /// Render an OSC-8 hyperlink. Without colour, the url follows the title unless they're the same.
+    /// An url holding a control character could end the escape sequence, only its title is rendered
+    ///
+    /// # Arguments
+    /// * `title` &str
+    /// * `url` &str
+    ///
+    /// # Return
+    /// String
+    fn render_link(&self, title: &str, url: &str) -> String {
+        if url.chars().any(char::is_control) {
+            return title.to_string();
+        }
+
+        if !self.terminal.colour {
+            if title == url || title.is_empty() {
+                return url.to_string();
+            }
+
+            return format!("{} ({})", title, url);
+        }
+
+        format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, self.style(title, "4", "24"))
+    }
+
+    /// Style
+    ///
+    /// # Description
+    /// Wrap the text with the SGR attribute and the attribute resetting it
+    ///
+    /// # Arguments
+    /// * `text` &str
+    /// * `attribute` &str
+    /// * `reset` &str
+    ///
+    /// # Return
+    /// String
+    fn style(&self, text: &str, attribute: &str, reset: &str) -> String {
+        if !self.terminal.colour {
+            return text.to_string();
+        }
+
+        format!("\x1b[{}m{}\x1b[{}m", attribute, text, reset)
+    }
+
+    /// Get Footnote Number
+    ///
+    /// # Description
+    /// Get the number of a referenced footnote
+    ///
+    /// # Arguments
+    /// * `label` &str
+    ///
+    /// # Return
+    /// Option<String>
+    fn get_footnote_number(&self, label: &str) -> Option<String> {
+        self.footnotes.definitions
+            .iter()
+            .find(|def| def.label == label)
+            .and_then(|def| def.number)
+            .map(|number| number.to_string())
+    }
+
+    /// Wrap With Marker
+    ///
+    /// # Description
+    /// Wrap the text after the marker. The following lines are indented by the width of the marker
+    ///
+    /// # Arguments
+    /// * `text` &str
+    /// * `marker` &str
+    ///
+    /// # Return
+    /// Vec<String>
+    fn wrap_with_marker(&self, text: &str, marker: &str) -> Vec<String> {
+        let indent = " ".repeat(get_visible_width(marker));
+        let width = self.terminal.width.saturating_sub(indent.len());
+
+        wrap_text(text, width)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, line)| format!("{}{}", if idx == 0 { marker } else { &indent }, line))
+            .collect()
+    }
+
+    /// Start Block
+    ///
+    /// # Description
+    /// Separate the block being started from the previous one by a blank line
+    fn start_block(&mut self) {
+        if !self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+    }
+
+    /// Push Block
+    ///
+    /// # Description
+    /// Push the lines of a block
+    ///
+    /// # Arguments
+    /// * `lines` Vec<String>
+    fn push_block(&mut self, lines: Vec<String>) {
+        if lines.is_empty() {
+            return;
+        }
+
+        self.start_block();
+        self.lines.extend(lines);
+    }
+
+    /// Close Blocks
+    ///
+    /// # Description
+    /// Close the paragraph, the list and the blockquote which are opened
+    fn close_blocks(&mut self) {
+        self.close_paragraph();
+        self.close_list();
+        self.close_blockquote();
+    }
+
+    /// Close Paragraph
+    ///
+    /// # Description
+    /// Wrap the lines of the paragraph being rendered
+    fn close_paragraph(&mut self) {
+        if self.paragraph.is_empty() {
+            return;
+        }
+
+        let paragraph = self.paragraph.join(" ");
+        self.paragraph.clear();
+
+        let lines = wrap_text(&paragraph, self.terminal.width);
+        self.push_block(lines);
+    }
+
+    /// Close List
+    ///
+    /// # Description
+    /// Close the list being rendered
+    fn close_list(&mut self) {
+        self.list = None;
+    }
+
+    /// Close Blockquote
+    ///
+    /// # Description
+    /// Wrap the lines of the blockquote being rendered behind a bar
+    fn close_blockquote(&mut self) {
+        if self.blockquote.is_empty() {
+            return;
+        }
+
+        let quote = self.blockquote.join(" ");
+        self.blockquote.clear();
+
+        let marker = format!("{}{}", BLOCK_INDENT, QUOTE_BAR);
+        let width = self.terminal.width.saturating_sub(get_visible_width(&marker));
+        let lines = wrap_text(&quote, width)
+            .into_iter()
+            .map(|line| format!("{}{}", marker, line))
+            .collect();
+
+        self.push_block(lines);
+    }
+
+    /// Close Code
+    ///
+    /// # Description
+    /// Draw a box around the code block being rendered. The language is written on the top border
+    fn close_code(&mut self) {
+        let (lang, lines) = match self.code.take() {
+            Some(code) => code,
+            None => return
+        };
+
+        let label = if lang.is_empty() { String::new() } else { format!("─ {} ", lang) };
+        let inner = lines
+            .iter()
+            .map(|l| l.chars().count())
+            .chain(std::iter::once(label.chars().count()))
+            .max()
+            .unwrap_or(0);
+
+        let mut boxed = vec![format!("┌{}{}┐", label, "─".repeat(inner + 2 - label.chars().count()))];
+        for line in lines {
+            let padding = " ".repeat(inner - line.chars().count());
+            boxed.push(format!("│ {}{} │", line, padding));
+        }
+
+        boxed.push(format!("└{}┘", "─".repeat(inner + 2)));
+        self.push_block(boxed);
+    }
+}
+
+/// Strip Control Characters
+///
+/// # Description
+/// Remove the C0 and C1 control characters but the tab, i.e the ESC starting an escape sequence
+///
+/// # Arguments
+/// * `text` &str
+///
+/// # Return
+/// String
+fn strip_control_characters(text: &str) -> String {
+    text.chars().filter(|c| *c == '\t' || !c.is_control()).collect()
+}
+
+/// Pad Cell
+///
+/// # Description
+/// Pad the cell of a table to the width of its column according to the alignment
+///
+/// # Arguments
+/// * `cell` &str
+/// * `alignment` &TableAlignment
+/// * `width` usize
+///
+/// # Return
+/// String
+fn pad_cell(cell: &str, alignment: &TableAlignment, width: usize) -> String {
+    let padding = width.saturating_sub(get_visible_width(cell));
+
+    match alignment {
+        TableAlignment::Right => format!("{}{}", " ".repeat(padding), cell),
+        TableAlignment::Center => format!("{}{}{}", " ".repeat(padding / 2), cell, " ".repeat(padding - padding / 2)),
+        TableAlignment::Left | TableAlignment::None => format!("{}{}", cell, " ".repeat(padding))
+    }
+}
+
+/// Wrap Text
+///
+/// # Description
+/// Greedy wrap the text at the spaces. The escape sequences don't count in the width and a word
+/// longer than the width is kept on its own line
+///
+/// # Arguments
+/// * `text` &str
+/// * `width` usize
+///
+/// # Return
+/// Vec<String>
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+
+    for word in text.split(' ').filter(|w| !w.is_empty()) {
+        let word_width = get_visible_width(word);
+        if !current.is_empty() && current_width + 1 + word_width > width {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+
+        if !current.is_empty() {
+            current.push(' ');
+            current_width += 1;
+        }
+
+        current.push_str(word);
+        current_width += word_width;
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+/// Get Visible Width
+///
+/// # Description
+/// Count the characters of the text which are displayed, skipping the SGR and the OSC escape sequences
+///
+/// # Arguments
+/// * `text` &str
+///
+/// # Return
+/// usize
+pub fn get_visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            width += 1;
+            continue;
+        }
+
+        match chars.next() {
+            // CSI sequence ended by a final byte
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            },
+            // OSC sequence ended by ST or BEL
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    width
+}
@@ -0,0 +1,458 @@
+use std::borrow::Cow;
+use std::default::Default;
+use std::clone::Clone;
+use std::collections::BTreeMap;
+use regex::{Regex, Captures};
+use lazy_static::lazy_static;
+use crate::options::ParserOptions;
+use crate::token::TextualToken;
+use crate::token::tag::TagOperator;
+use crate::parser::inline::match_link;
+
+// Tags disallowed by the GFM tagfilter extension
+// See: https://github.github.com/gfm/#disallowed-raw-html-extension-
+const GFM_TAG_FILTER: [&str; 9] = [
+    "title",
+    "textarea",
+    "style",
+    "xmp",
+    "iframe",
+    "noembed",
+    "noframes",
+    "script",
+    "plaintext"
+];
+
+// Url schemes which may execute code
+const UNSAFE_SCHEMES: [&str; 3] = ["javascript", "vbscript", "data"];
+
+// Default rel attribute of the external links
+const EXTERNAL_LINK_REL: &str = "nofollow noopener";
+
+// Html attributes holding an url
+const URL_ATTRIBUTES: [&str; 13] = [
+    "href",
+    "src",
+    "action",
+    "formaction",
+    "xlink:href",
+    "poster",
+    "background",
+    "cite",
+    "data",
+    "codebase",
+    "longdesc",
+    "lowsrc",
+    "dynsrc"
+];
+
+// Named character references standing for an ascii character, the only ones which could spell a scheme
+// See: https://html.spec.whatwg.org/multipage/named-characters.html
+const ASCII_ENTITIES: [(&str, &str); 48] = [
+    ("Tab", "\t"), ("NewLine", "\n"), ("excl", "!"), ("quot", "\""), ("QUOT", "\""),
+    ("num", "#"), ("dollar", "$"), ("percnt", "%"), ("amp", "&"), ("AMP", "&"),
+    ("apos", "'"), ("lpar", "("), ("rpar", ")"), ("ast", "*"), ("midast", "*"),
+    ("plus", "+"), ("comma", ","), ("period", "."), ("sol", "/"), ("colon", ":"),
+    ("semi", ";"), ("lt", "<"), ("LT", "<"), ("equals", "="), ("gt", ">"),
+    ("GT", ">"), ("quest", "?"), ("commat", "@"), ("lsqb", "["), ("lbrack", "["),
+    ("bsol", "\\"), ("rsqb", "]"), ("rbrack", "]"), ("Hat", "^"), ("lowbar", "_"),
+    ("UnderBar", "_"), ("grave", "`"), ("DiacriticalGrave", "`"), ("lcub", "{"), ("lbrace", "{"),
+    ("verbar", "|"), ("vert", "|"), ("VerticalLine", "|"), ("rcub", "}"), ("rbrace", "}"),
+    ("nbsp", "\u{a0}"), ("NonBreakingSpace", "\u{a0}"), ("fjlig", "fj")
+];
+
+lazy_static!{
+    static ref ENTITY: Regex = Regex::new(r"&(?:#([xX][0-9a-fA-F]+|[0-9]+);?|([A-Za-z][A-Za-z0-9]*);)").unwrap();
+}
+
+/// Html Attribute
+///
+/// Attribute of a raw html tag. The raw slice is kept so that the safe attributes are emitted as is
+struct HtmlAttribute<'a> {
+    raw: &'a str,
+    name: &'a str,
+    value: Option<&'a str>
+}
+
+/// Sanitize Policy
+///
+/// # Description
+/// Policy applied to the tokens before rendering untrusted markdown. The default policy
+/// follow the GFM tagfilter extension
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SanitizePolicy {
+    // Raw html tags which are escaped i.e <script> -> &lt;script>
+    pub tag_filter: Vec<String>,
+    // Url schemes which are removed from the links, images and html attributes
+    pub unsafe_schemes: Vec<String>,
+    // Remove the on* attributes of the raw html i.e onerror, onclick
+    pub strip_event_handlers: bool,
+    // rel attribute added to the external links
+    pub external_link_rel: Option<String>
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        SanitizePolicy {
+            tag_filter: GFM_TAG_FILTER.iter().map(|t| t.to_string()).collect(),
+            unsafe_schemes: UNSAFE_SCHEMES.iter().map(|s| s.to_string()).collect(),
+            strip_event_handlers: true,
+            external_link_rel: Some(EXTERNAL_LINK_REL.to_string())
+        }
+    }
+}
+
+impl SanitizePolicy {
+    /// Is Unsafe Url
+    ///
+    /// # Description
+    /// Check whether the url use one of the unsafe schemes. The character references are decoded and
+    /// the whitespaces and control characters are removed as a browser would before reading the scheme
+    ///
+    /// # Arguments
+    /// * `url` &str
+    ///
+    /// # Return
+    /// bool
+    pub fn is_unsafe_url(&self, url: &str) -> bool {
+        let normalized: String = decode_entities(url)
+            .chars()
+            .filter(|c| !c.is_whitespace() && !c.is_control())
+            .collect::<String>()
+            .to_lowercase();
+
+        self.unsafe_schemes
+            .iter()
+            .any(|scheme| normalized.starts_with(&format!("{}:", scheme.to_lowercase())))
+    }
+
+    /// Filter Html
+    ///
+    /// # Description
+    /// Escape the disallowed tags, remove the event handlers and the unsafe urls of a raw html. The
+    /// attributes are tokenized so that a quoted `>` doesn't end the tag
+    ///
+    /// # Arguments
+    /// * `html` &str
+    ///
+    /// # Return
+    /// String
+    pub fn filter_html(&self, html: &str) -> String {
+        let mut cleaned = String::with_capacity(html.len());
+        let mut idx = 0;
+
+        while let Some(pos) = html[idx..].find('<') {
+            let start = idx + pos;
+            cleaned.push_str(&html[idx..start]);
+
+            if !html[start + 1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+                cleaned.push('<');
+                idx = start + 1;
+                continue;
+            }
+
+            let (name, attributes, len) = parse_tag(&html[start..]);
+            cleaned.push_str(&self.filter_tag(&html[start..start + len], name, &attributes));
+            idx = start + len;
+        }
+
+        cleaned.push_str(&html[idx..]);
+        self.filter_tags(&cleaned)
+    }
+
+    /// Filter Tag
+    ///
+    /// # Description
+    /// Drop the event handlers and empty the unsafe urls of a tag. The tag is kept as is when
+    /// every attribute is safe
+    ///
+    /// # Arguments
+    /// * `tag` &str
+    /// * `name` &str
+    /// * `attributes` &[HtmlAttribute]
+    ///
+    /// # Return
+    /// String
+    fn filter_tag(&self, tag: &str, name: &str, attributes: &[HtmlAttribute]) -> String {
+        let is_handler = |attr: &HtmlAttribute| {
+            self.strip_event_handlers && attr.name.to_lowercase().starts_with("on")
+        };
+        let is_unsafe = |attr: &HtmlAttribute| {
+            URL_ATTRIBUTES.contains(&attr.name.to_lowercase().as_str())
+                && attr.value.is_some_and(|value| self.is_unsafe_url(value))
+        };
+
+        if !attributes.iter().any(|attr| is_handler(attr) || is_unsafe(attr)) {
+            return tag.to_string();
+        }
+
+        let mut filtered = format!("<{}", name);
+        for attr in attributes.iter().filter(|attr| !is_handler(attr)) {
+            filtered.push(' ');
+            match is_unsafe(attr) {
+                true => filtered.push_str(&format!("{}=\"\"", attr.name)),
+                false => filtered.push_str(attr.raw)
+            }
+        }
+
+        if tag.ends_with("/>") {
+            filtered.push_str(" /");
+        }
+
+        filtered.push('>');
+        filtered
+    }
+
+    /// Filter Tags
+    ///
+    /// # Description
+    /// Escape the opening bracket of the disallowed tags
+    ///
+    /// # Arguments
+    /// * `content` &str
+    ///
+    /// # Return
+    /// String
+    fn filter_tags(&self, content: &str) -> String {
+        if self.tag_filter.is_empty() {
+            return content.to_string();
+        }
+
+        let names: Vec<String> = self.tag_filter.iter().map(|t| regex::escape(t)).collect();
+        let re = Regex::new(&format!(r"(?i)<(/?(?:{})(?:\s|/?>|$))", names.join("|"))).unwrap();
+
+        re.replace_all(content, "&lt;$1").to_string()
+    }
+
+    /// Filter Markdown Urls
+    ///
+    /// # Description
+    /// Remove the unsafe urls of the links and images of a markdown line. The links are matched as
+    /// the inline parser does so that the checked url is the one the renderers emit
+    ///
+    /// # Arguments
+    /// * `content` &str
+    ///
+    /// # Return
+    /// String
+    fn filter_markdown_urls(&self, content: &str) -> String {
+        let mut filtered = String::with_capacity(content.len());
+        let mut cursor = 0;
+
+        for (idx, _) in content.match_indices('[') {
+            if idx < cursor {
+                continue;
+            }
+
+            if let Some((label, url, len)) = match_link(&content[idx..]) {
+                // keep the brackets and the parentheses, only the destination is removed
+                let dest_start = idx + label.len() + 3;
+                let dest_end = idx + len - 1;
+
+                if self.is_unsafe_url(&url) || self.is_unsafe_url(&content[dest_start..dest_end]) {
+                    filtered.push_str(&content[cursor..dest_start]);
+                    cursor = dest_end;
+                }
+            }
+        }
+
+        filtered.push_str(&content[cursor..]);
+        filtered
+    }
+
+    /// Filter Content
+    ///
+    /// # Description
+    /// Apply the policy on the raw content of a line
+    ///
+    /// # Arguments
+    /// * `content` &str
+    ///
+    /// # Return
+    /// String
+    fn filter_content(&self, content: &str) -> String {
+        self.filter_html(&self.filter_markdown_urls(content))
+    }
+}
+
+/// Sanitize Tokens
+///
+/// # Description
+/// Apply the sanitize policy on the textual tokens. The texts are sanitized again with the options
+/// they have been parsed with
+///
+/// # Arguments
+/// * `tokens` &mut BTreeMap<usize, TextualToken>
+/// * `policy` &SanitizePolicy
+/// * `options` &ParserOptions
+pub fn sanitize_tokens(tokens: &mut BTreeMap<usize, TextualToken>, policy: &SanitizePolicy, options: &ParserOptions) {
+    for token in tokens.values_mut() {
+        if let Some(tag) = token.tag.as_mut() {
+            tag.content = match tag.operator {
+                TagOperator::HtmlBlock => Cow::Owned(policy.filter_html(&tag.content)),
+                _ => Cow::Owned(policy.filter_content(&tag.content))
+            };
+
+            if let Some(footnote) = tag.metas.as_mut().and_then(|m| m.footnote.as_mut()) {
+                for paragraph in footnote.paragraphs.iter_mut() {
+                    *paragraph = policy.filter_content(paragraph);
+                }
+            }
+
+            if let Some(list) = tag.metas.as_mut().and_then(|m| m.definition_list.as_mut()) {
+                for item in list.items.iter_mut() {
+                    item.term = policy.filter_content(&item.term);
+                    for definition in item.definitions.iter_mut() {
+                        *definition = policy.filter_content(definition);
+                    }
+                }
+            }
+
+            if let Some(table) = tag.metas.as_mut().and_then(|m| m.table.as_mut()) {
+                for cell in table.header.iter_mut().chain(table.rows.iter_mut().flatten()) {
+                    *cell = policy.filter_content(cell);
+                }
+            }
+        }
+
+        if let Some(text) = token.text.as_mut() {
+            text.content = Cow::Owned(policy.filter_content(&text.content));
+            text.resanitize(options);
+
+            if let Some(metas) = text.metas.as_mut() {
+                for link in metas.links.iter_mut().flatten() {
+                    if policy.is_unsafe_url(link.url.trim_start_matches('<')) {
+                        link.url = Cow::Borrowed("");
+                    }
+
+                    if is_external_url(&link.url) {
+                        link.rel = policy.external_link_rel.clone();
+                    }
+                }
+
+                for image in metas.images.iter_mut().flatten() {
+                    if policy.is_unsafe_url(image.url.trim_start_matches('<')) {
+                        image.url = Cow::Borrowed("");
+                    }
+                }
+
+                for html in metas.html.iter_mut().flatten() {
+                    html.word = Cow::Owned(policy.filter_html(&html.word));
+                }
+            }
+        }
+    }
+}
+
+/// Is External Url
+///
+/// # Description
+/// Check whether the url target an other website
+///
+/// # Arguments
+/// * `url` &str
+///
+/// # Return
+/// bool
+fn is_external_url(url: &str) -> bool {
+    let url = url.trim().to_lowercase();
+
+    url.starts_with("http://") || url.starts_with("https://") || url.starts_with("//")
+}
+
+/// Decode Entities
+///
+/// # Description
+/// Decode the numeric character references and the named ones standing for an ascii character
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Cow<str>
+fn decode_entities(content: &str) -> Cow<'_, str> {
+    ENTITY.replace_all(content, |c: &Captures| {
+        if let Some(name) = c.get(2) {
+            return ASCII_ENTITIES
+                .iter()
+                .find(|(entity, _)| *entity == name.as_str())
+                .map_or_else(|| c[0].to_string(), |(_, value)| value.to_string());
+        }
+
+        let value = &c[1];
+        let code = match value.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => value.parse::<u32>().ok()
+        };
+
+        code.and_then(std::char::from_u32).map(|c| c.to_string()).unwrap_or_default()
+    })
+}
+
+/// Parse Tag
+///
+/// # Description
+/// Tokenize the name and the attributes of the tag starting the content as a browser does. A
+/// quoted value may contain a `>`, a tag which isn't closed runs until the end of the content
+///
+/// # Arguments
+/// * `content` &str starting with `<` and a letter
+///
+/// # Return
+/// (&str, Vec<HtmlAttribute>, usize) the name, the attributes and the length of the tag
+fn parse_tag(content: &str) -> (&str, Vec<HtmlAttribute<'_>>, usize) {
+    let is_name_end = |c: char| c.is_whitespace() || c == '/' || c == '>';
+    let name_end = content[1..].find(is_name_end).map_or(content.len(), |pos| pos + 1);
+    let name = &content[1..name_end];
+    let mut attributes = Vec::new();
+    let mut idx = name_end;
+
+    loop {
+        let rest = &content[idx..];
+        idx += rest.len() - rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/').len();
+
+        if idx >= content.len() {
+            return (name, attributes, content.len());
+        }
+
+        if content[idx..].starts_with('>') {
+            return (name, attributes, idx + 1);
+        }
+
+        // a leading = belongs to the attribute name
+        let start = idx;
+        let first = content[idx..].chars().next().map_or(1, char::len_utf8);
+        idx += content[idx + first..]
+            .find(|c: char| is_name_end(c) || c == '=')
+            .map_or(content.len() - idx, |pos| pos + first);
+        let attr_name = &content[start..idx];
+
+        let rest = content[idx..].trim_start();
+        let mut value = None;
+        if let Some(rest) = rest.strip_prefix('=') {
+            let rest = rest.trim_start();
+            let value_start = content.len() - rest.len();
+
+            let (range, end) = match rest.chars().next() {
+                Some(quote) if quote == '"' || quote == '\'' => match rest[1..].find(quote) {
+                    Some(pos) => (value_start + 1..value_start + 1 + pos, value_start + pos + 2),
+                    None => (value_start + 1..content.len(), content.len())
+                },
+                _ => {
+                    let len = rest.find(|c: char| c.is_whitespace() || c == '>').unwrap_or(rest.len());
+                    (value_start..value_start + len, value_start + len)
+                }
+            };
+
+            value = Some(&content[range]);
+            idx = end;
+        }
+
+        attributes.push(HtmlAttribute {
+            raw: &content[start..idx],
+            name: attr_name,
+            value
+        });
+    }
+}
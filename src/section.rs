@@ -0,0 +1,183 @@
+use std::clone::Clone;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use crate::options::ParserOptions;
+use crate::parser::front_matter::get_front_matter;
+use crate::parser::heading::HeadingLevel;
+use crate::parser::inline::{get_inline_nodes, get_plain_text};
+use crate::token::TextualToken;
+use crate::token::tag::TagOperator;
+
+/// Section
+///
+/// # Description
+/// Part of a document starting at a heading and ending before the next heading up to the max
+/// level of the split. The sections are flat, a `##` section ends at the following `###` when the
+/// max level is H3. The content preceding the first heading is a section without heading
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Section {
+    pub heading: Option<String>,
+    pub level: Option<HeadingLevel>,
+    pub slug: Option<String>,
+    // Headings of the parent sections followed by the heading of the section
+    pub path: Vec<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    // Byte range of the section in the source
+    pub span: Range<usize>,
+    pub raw: String
+}
+
+impl Section {
+    /// Contains Line
+    ///
+    /// # Description
+    /// Return if the line is part of the section
+    ///
+    /// # Arguments
+    /// * `line` usize
+    ///
+    /// # Return
+    /// bool
+    pub fn contains_line(&self, line: usize) -> bool {
+        line >= self.start_line && line <= self.end_line
+    }
+}
+
+/// Split Sections
+///
+/// # Description
+/// Split the document by the headings up to the max level, each of these headings starts a
+/// new section. Deeper headings are kept within the section of their parent
+///
+/// # Arguments
+/// * `content` &str
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `max_level` HeadingLevel
+///
+/// # Return
+/// Vec<Section>
+pub fn split_sections(content: &str, tokens: &BTreeMap<usize, TextualToken>, max_level: HeadingLevel) -> Vec<Section> {
+    let offsets = get_line_offsets(content);
+    if offsets.is_empty() {
+        return Vec::new();
+    }
+
+    let options = ParserOptions::default();
+    let first_line = get_front_matter(content).map_or(0, |f| f.end_line + 1);
+    let headings: Vec<(usize, String, HeadingLevel, Option<String>)> = tokens
+        .iter()
+        .filter(|(idx, _)| **idx >= first_line)
+        .filter_map(|(idx, t)| t.tag.as_ref().map(|tag| (*idx, tag)))
+        .filter(|(_, tag)| tag.operator == TagOperator::Heading)
+        .map(|(idx, tag)| {
+            let metas = tag.metas.clone().unwrap_or_default();
+            let title = get_plain_text(&get_inline_nodes(&tag.content, &options));
+            (idx, title, metas.heading_kind, metas.slug)
+        })
+        .filter(|(_, _, level, _)| *level <= max_level)
+        .collect();
+
+    let mut sections = Vec::new();
+    let last_line = offsets.len() - 1;
+
+    // content preceding the first heading
+    let preamble_end = headings.first().map_or(last_line + 1, |(idx, ..)| *idx);
+    if preamble_end > first_line {
+        let span = offsets[first_line].start..offsets[preamble_end - 1].end;
+        if !content[span.clone()].trim().is_empty() {
+            sections.push(Section {
+                heading: None,
+                level: None,
+                slug: None,
+                path: Vec::new(),
+                start_line: first_line,
+                end_line: preamble_end - 1,
+                raw: content[span.clone()].to_string(),
+                span
+            });
+        }
+    }
+
+    // Level and title of the parent headings
+    let mut stack: Vec<(HeadingLevel, String)> = Vec::new();
+    for (idx, title, level, slug) in headings.iter() {
+        let end_line = get_section_end(tokens, *idx, &max_level, last_line);
+        while stack.last().is_some_and(|(parent, _)| parent >= level) {
+            stack.pop();
+        }
+
+        stack.push((level.clone(), title.clone()));
+
+        let span = offsets[*idx].start..offsets[end_line].end;
+        sections.push(Section {
+            heading: Some(title.clone()),
+            level: Some(level.clone()),
+            slug: slug.clone(),
+            path: stack.iter().map(|(_, title)| title.clone()).collect(),
+            start_line: *idx,
+            end_line,
+            raw: content[span.clone()].to_string(),
+            span
+        });
+    }
+
+    sections
+}
+
+/// Get Section End
+///
+/// # Description
+/// Get the last line of the section starting at the heading line, i.e the line preceding the next
+/// heading up to the level
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `line` usize
+/// * `level` &HeadingLevel
+/// * `last_line` usize
+///
+/// # Return
+/// usize
+pub fn get_section_end(tokens: &BTreeMap<usize, TextualToken>, line: usize, level: &HeadingLevel, last_line: usize) -> usize {
+    if line >= last_line {
+        return last_line;
+    }
+
+    tokens
+        .range(line + 1..=last_line)
+        .filter_map(|(idx, t)| t.tag.as_ref().map(|tag| (*idx, tag)))
+        .filter(|(_, tag)| tag.operator == TagOperator::Heading)
+        .find(|(_, tag)| tag.metas.as_ref().map(|m| m.heading_kind.clone()).unwrap_or_default() <= *level)
+        .map_or(last_line, |(idx, _)| idx - 1)
+}
+
+/// Get Line Offsets
+///
+/// # Description
+/// Get the byte range of each line, line ending excluded
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Vec<Range<usize>>
+pub fn get_line_offsets(content: &str) -> Vec<Range<usize>> {
+    let mut offsets = Vec::new();
+    let mut start = 0;
+
+    for line in content.lines() {
+        offsets.push(start..start + line.len());
+        start += line.len();
+
+        let rest = &content[start..];
+        if rest.starts_with("\r\n") {
+            start += 2;
+        } else if rest.starts_with('\n') {
+            start += 1;
+        }
+    }
+
+    offsets
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod borrowtest {
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+    use crate::{parse_code_markdown, parse_markdown, parse_markdown_with_options, ParserOptions};
+    use crate::token::TextualToken;
+
+    #[test]
+    fn text_token_borrow_the_line() {
+        let tokens = parse_markdown("Plain text with a [link](https://example.com) and **bold**").unwrap();
+        let text = tokens[&0].text.as_ref().unwrap();
+        let metas = text.metas.as_ref().unwrap();
+
+        assert!(matches!(text.content, Cow::Borrowed(_)));
+        assert!(matches!(metas.links.as_ref().unwrap()[0].url, Cow::Borrowed("https://example.com")));
+        assert!(matches!(metas.bold.as_ref().unwrap()[0].word, Cow::Borrowed("bold")));
+        // the style characters are removed from the sanitized content
+        assert!(matches!(text.sanitize_content, Cow::Owned(_)));
+
+        let tokens = parse_markdown("Plain text").unwrap();
+        assert!(matches!(tokens[&0].text.as_ref().unwrap().sanitize_content, Cow::Borrowed("Plain text")));
+    }
+
+    #[test]
+    fn tag_and_code_tokens_borrow_the_line() {
+        let content = "# Title\n-. item\n1. first\n> quote\n```rust\nlet a = 1;\n```";
+        let tokens = parse_markdown(content).unwrap();
+        let code_tokens = parse_code_markdown(content).unwrap();
+
+        for idx in 0..4 {
+            assert!(matches!(tokens[&idx].tag.as_ref().unwrap().content, Cow::Borrowed(_)));
+        }
+
+        assert_eq!(tokens[&1].tag.as_ref().unwrap().content, "item");
+        assert!(code_tokens.values().all(|token| matches!(token.content, Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn stripped_html_fallback_to_owned() {
+        let options = ParserOptions { drop_html: true, ..Default::default() };
+        let tokens = parse_markdown_with_options("Press <kbd>Ctrl</kbd> **now**", &options).unwrap();
+        let text = tokens[&0].text.as_ref().unwrap();
+
+        assert!(matches!(text.content, Cow::Owned(_)));
+        assert_eq!(text.content, "Press Ctrl **now**");
+        assert_eq!(text.metas.as_ref().unwrap().bold.as_ref().unwrap()[0].word, "now");
+    }
+
+    #[test]
+    fn into_owned_outlive_the_content() {
+        let content = String::from("# Title\n\nSome [link](https://example.com)");
+        let tokens: BTreeMap<usize, TextualToken<'static>> = parse_markdown(&content)
+            .unwrap()
+            .into_iter()
+            .map(|(idx, token)| (idx, token.into_owned()))
+            .collect();
+
+        drop(content);
+
+        assert_eq!(tokens[&0].tag.as_ref().unwrap().content, "Title");
+        let text = tokens[&2].text.as_ref().unwrap();
+        assert_eq!(text.metas.as_ref().unwrap().links.as_ref().unwrap()[0].url, "https://example.com");
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod csttest {
+    use crate::{parse_cst, CodeOperator, CstInlineKind, CstKind, GithubOptions, ParserOptions, TagOperator};
+
+    const DOCUMENT: &str = "---\ntitle: Doc\n---\n\n  ## Install ##  \r\n-.   Baozi\n2. Xiaolongbao {#id}\n> quote\n\n```rust  \n    let a = 1;\n```\n[^1]: note\n    continued\nlast line without newline";
+
+    #[test]
+    fn expect_to_string_to_reproduce_input() {
+        let options = ParserOptions::default();
+        let contents = [
+            DOCUMENT,
+            "# Title {#custom}\n\n\n",
+            "text\r\nwith\r\ncrlf\r\n",
+            "   \n\t\n",
+            "<div>\n  <p>html</p>\n</div>\n"
+        ];
+
+        for content in contents {
+            let cst = parse_cst(content, &options).unwrap();
+            assert_eq!(cst.to_string(), content);
+        }
+    }
+
+    #[test]
+    fn split_line_into_trivia_and_content() {
+        let cst = parse_cst(DOCUMENT, &ParserOptions::default()).unwrap();
+        let nodes = cst.nodes();
+
+        assert_eq!(nodes[0].kind, CstKind::FrontMatter);
+        assert_eq!(nodes[3].kind, CstKind::Blank);
+
+        let heading = &nodes[4];
+        assert_eq!(heading.kind, CstKind::Tag(TagOperator::Heading));
+        assert_eq!(heading.indent(), "  ");
+        assert_eq!(heading.marker(), "## ");
+        assert_eq!(heading.content(), "Install");
+        assert_eq!(heading.trailing(), " ##  ");
+        assert_eq!(heading.line_ending(), "\r\n");
+
+        assert_eq!(nodes[5].marker(), "-.   ");
+        assert_eq!(nodes[5].content(), "Baozi");
+        assert_eq!(nodes[6].marker(), "2. ");
+        assert_eq!(nodes[6].content(), "Xiaolongbao {#id}");
+
+        assert_eq!(nodes[9].kind, CstKind::Code(CodeOperator::BlockCodeStart));
+        assert_eq!(nodes[9].marker(), "```");
+        assert_eq!(nodes[9].content(), "rust");
+        assert_eq!(nodes[10].content(), "    let a = 1;");
+
+        assert_eq!(nodes[12].marker(), "[^1]: ");
+        assert_eq!(nodes[13].kind, CstKind::Continuation);
+        assert_eq!(nodes[14].line_ending(), "");
+    }
+
+    #[test]
+    fn edit_only_reemit_modified_nodes() {
+        let options = ParserOptions::default();
+        let content = "# Changelog\n\nVersion:   1.0.0  \n-.  item\n";
+        let mut cst = parse_cst(content, &options).unwrap();
+
+        let version = cst.nodes_mut().find(|n| n.content().starts_with("Version")).unwrap();
+        version.set_content("Version:   1.1.0");
+        assert!(version.is_modified());
+
+        let heading = cst.node_mut(0).unwrap();
+        heading.set_marker("## ");
+
+        assert_eq!(cst.to_string(), "## Changelog\n\nVersion:   1.1.0  \n-.  item\n");
+        assert!(!cst.nodes()[3].is_modified());
+    }
+
+    #[test]
+    fn insert_and_remove_lines() {
+        let options = ParserOptions::default();
+        let mut cst = parse_cst("# Title\nlast", &options).unwrap();
+
+        cst.insert_lines(1, "-. first\n-. second", &options).unwrap();
+        assert_eq!(cst.to_string(), "# Title\n-. first\n-. second\nlast");
+        assert_eq!(cst.nodes()[1].kind, CstKind::Tag(TagOperator::UnorderedList));
+        assert!(cst.nodes()[1].line.is_none());
+
+        cst.insert_lines(4, "appended", &options).unwrap();
+        assert_eq!(cst.to_string(), "# Title\n-. first\n-. second\nlast\nappended");
+
+        let removed = cst.remove_node(0).unwrap();
+        assert_eq!(removed.content(), "Title");
+        assert_eq!(cst.to_string(), "-. first\n-. second\nlast\nappended");
+        assert!(cst.remove_node(10).is_none());
+    }
+
+    #[test]
+    fn split_content_into_inline_nodes() {
+        let options = ParserOptions {
+            github: Some(GithubOptions::default()),
+            ..Default::default()
+        };
+
+        let content = "-. See [the @docs](https://a.com/v1) and **bold _it_** `code`\n[^1]: note\n    with *more*\n";
+        let cst = parse_cst(content, &options).unwrap();
+        let item = &cst.nodes()[0];
+        let inlines = item.inlines();
+
+        let kinds: Vec<&CstInlineKind> = inlines.iter().map(|i| i.kind()).collect();
+        assert_eq!(kinds, [
+            &CstInlineKind::Text,
+            &CstInlineKind::Link,
+            &CstInlineKind::Text,
+            &CstInlineKind::Strong,
+            &CstInlineKind::Text,
+            &CstInlineKind::Code
+        ]);
+
+        // the spans cover the whole content
+        assert_eq!(inlines[0].span().start, 0);
+        assert_eq!(inlines.last().unwrap().span().end, item.content().len());
+        assert!(inlines.windows(2).all(|w| w[0].span().end == w[1].span().start));
+
+        let link = &inlines[1];
+        assert_eq!(&item.content()[link.span()], "[the @docs](https://a.com/v1)");
+        // the mention of a label is a text
+        assert_eq!(link.children().len(), 1);
+        assert_eq!(&item.content()[link.children()[0].span()], "the @docs");
+
+        let strong = &inlines[3];
+        assert_eq!(strong.children()[1].kind(), &CstInlineKind::Emphasis);
+        assert_eq!(&item.content()[strong.children()[1].span()], "_it_");
+        assert_eq!(&item.content()[strong.children()[1].children()[0].span()], "it");
+
+        let continuation = &cst.nodes()[2];
+        assert_eq!(continuation.kind, CstKind::Continuation);
+        assert_eq!(continuation.inlines()[1].kind(), &CstInlineKind::Emphasis);
+    }
+
+    #[test]
+    fn edit_inline_span() {
+        let options = ParserOptions::default();
+        let content = "# Title\n\nRead   the [docs](https://a.com/v1)  *now*\n";
+        let mut cst = parse_cst(content, &options).unwrap();
+
+        let node = cst.node_mut(2).unwrap();
+        let link = node.inlines()[1].span();
+        assert!(node.set_inline(link, "[docs](https://a.com/v2)", &options));
+        assert_eq!(node.inlines()[1].kind(), &CstInlineKind::Link);
+        assert_eq!(node.inlines()[3].kind(), &CstInlineKind::Emphasis);
+        assert!(!node.set_inline(40..60, "out of range", &options));
+
+        assert_eq!(cst.to_string(), "# Title\n\nRead   the [docs](https://a.com/v2)  *now*\n");
+        assert!(!cst.nodes()[0].is_modified());
+        assert_eq!(cst.nodes()[0].inlines().len(), 1);
+        assert!(cst.nodes()[1].inlines().is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod directivetest {
+    use crate::{parse_markdown_with_options, render_html, format_markdown, DirectiveKind, FormatStyle, ParserOptions};
+    use crate::token::tag::TagOperator;
+
+    fn directive_options() -> ParserOptions {
+        ParserOptions::new().directives(true)
+    }
+
+    #[test]
+    fn parse_container_directive() {
+        let content = "
+            ::::tabs
+            :::tip[Read *this*]{#intro .note .wide level=2 title=\"A title\" open}
+            Inside
+            :::
+            ::::
+            ::: warning Be careful
+            :::
+        ";
+
+        let res = parse_markdown_with_options(content, &directive_options()).unwrap();
+        let tabs = res.get(&1).unwrap().tag.as_ref().unwrap();
+        assert_eq!(tabs.operator, TagOperator::ContainerDirective);
+        assert_eq!(tabs.metas.as_ref().unwrap().directive.as_ref().unwrap().fence, 4);
+
+        let tip = res.get(&2).unwrap().tag.as_ref().unwrap();
+        let directive = tip.metas.as_ref().unwrap().directive.as_ref().unwrap();
+        assert_eq!(tip.content, "Read *this*");
+        assert_eq!(directive.kind, DirectiveKind::Container);
+        assert_eq!(directive.name, "tip");
+        assert_eq!(directive.label.as_deref(), Some("Read *this*"));
+        assert_eq!(directive.attributes["id"], "intro");
+        assert_eq!(directive.attributes["class"], "note wide");
+        assert_eq!(directive.attributes["level"], "2");
+        assert_eq!(directive.attributes["title"], "A title");
+        assert_eq!(directive.attributes["open"], "");
+
+        // the innermost container is closed first
+        let end = res.get(&4).unwrap().tag.as_ref().unwrap();
+        assert_eq!(end.operator, TagOperator::ContainerDirectiveEnd);
+        assert_eq!(end.metas.as_ref().unwrap().directive.as_ref().unwrap().name, "tip");
+        let end = res.get(&5).unwrap().tag.as_ref().unwrap();
+        assert_eq!(end.metas.as_ref().unwrap().directive.as_ref().unwrap().name, "tabs");
+
+        let warning = res.get(&6).unwrap().tag.as_ref().unwrap();
+        assert_eq!(warning.content, "Be careful");
+        assert_eq!(warning.metas.as_ref().unwrap().directive.as_ref().unwrap().name, "warning");
+    }
+
+    #[test]
+    fn parse_leaf_and_text_directives() {
+        let content = "
+            ::youtube[Intro]{vid=123}
+            Press :kbd[Ctrl]{.key} at 10:30 or :smile: and https://example.com
+            :::
+            ::invalid[label] trailing
+        ";
+
+        let options = directive_options();
+        let res = parse_markdown_with_options(content, &options).unwrap();
+        let leaf = res.get(&1).unwrap().tag.as_ref().unwrap();
+        let directive = leaf.metas.as_ref().unwrap().directive.as_ref().unwrap();
+        assert_eq!(leaf.operator, TagOperator::LeafDirective);
+        assert_eq!(directive.kind, DirectiveKind::Leaf);
+        assert_eq!(directive.attributes["vid"], "123");
+
+        // a closing fence without container and an invalid leaf are text
+        assert!(res.get(&3).unwrap().tag.is_none());
+        assert!(res.get(&4).unwrap().tag.is_none());
+
+        let html = render_html(content, &options).unwrap();
+        assert_eq!(html, concat!(
+            "<div class=\"directive directive-youtube\" data-vid=\"123\">Intro</div>\n",
+            "<p>Press <span class=\"directive directive-kbd key\">Ctrl</span> at 10:30 or :smile: and https://example.com\n",
+            ":::\n",
+            "::invalid[label] trailing</p>\n"
+        ));
+    }
+
+    #[test]
+    fn render_container_directive() {
+        let content = "
+            ::::tabs
+            :::tip[Title]{#intro onclick=alert}
+            -. item
+            :::
+            After
+        ";
+
+        let html = render_html(content, &directive_options()).unwrap();
+        assert_eq!(html, concat!(
+            "<div class=\"directive directive-tabs\">\n",
+            "<div class=\"directive directive-tip\" id=\"intro\" data-onclick=\"alert\">\n",
+            "<p class=\"directive-label\">Title</p>\n",
+            "<ul>\n<li>item</li>\n</ul>\n",
+            "</div>\n",
+            "<p>After</p>\n",
+            "</div>\n"
+        ));
+
+        let markdown = format_markdown(content, &directive_options(), &FormatStyle::default()).unwrap();
+        assert_eq!(markdown, "::::tabs\n:::tip[Title]{#intro onclick=\"alert\"}\n-. item\n:::\nAfter\n");
+    }
+
+    #[test]
+    fn expect_shorter_closing_fence_to_be_text() {
+        let content = "::::outer\n:::\ntext\n:::::\nafter";
+        let res = parse_markdown_with_options(content, &directive_options()).unwrap();
+
+        // the closing fence must be at least as long as the opening one
+        assert!(res.get(&1).unwrap().tag.is_none());
+        assert_eq!(res.get(&3).unwrap().tag.as_ref().unwrap().operator, TagOperator::ContainerDirectiveEnd);
+
+        let html = render_html(content, &directive_options()).unwrap();
+        assert_eq!(html, "<div class=\"directive directive-outer\">\n<p>:::\ntext</p>\n</div>\n<p>after</p>\n");
+    }
+
+    #[test]
+    fn unclosed_container_end_with_the_document() {
+        let html = render_html(":::tip\nunclosed", &directive_options()).unwrap();
+        assert_eq!(html, "<div class=\"directive directive-tip\">\n<p>unclosed</p>\n</div>\n");
+    }
+
+    #[test]
+    fn fence_within_code_block_is_code() {
+        let content = "```\n:::tip\n```\n:::";
+        let res = parse_markdown_with_options(content, &directive_options()).unwrap();
+
+        assert!(res.values().all(|token| token.tag.is_none()));
+
+        let html = render_html(content, &directive_options()).unwrap();
+        assert_eq!(html, "<pre><code>:::tip\n</code></pre>\n<p>:::</p>\n");
+    }
+
+    #[test]
+    fn invalid_directives_are_text() {
+        let content = "::video{#}\na:kbd[x] :kbd[y]{#} :kbd[z]{.k}";
+        let res = parse_markdown_with_options(content, &directive_options()).unwrap();
+
+        assert!(res.get(&0).unwrap().tag.is_none());
+
+        // within a word or with an empty id
+        let html = render_html(content, &directive_options()).unwrap();
+        assert_eq!(html, "<p>::video{#}\na:kbd[x] :kbd[y]{#} <span class=\"directive directive-kbd k\">z</span></p>\n");
+    }
+
+    #[test]
+    fn expect_directives_to_be_opt_in() {
+        let content = ":::tip\nSome :kbd[Ctrl] text\n:::";
+
+        let res = parse_markdown_with_options(content, &ParserOptions::new()).unwrap();
+        assert!(res.values().all(|token| token.tag.is_none()));
+
+        let html = render_html(content, &ParserOptions::new()).unwrap();
+        assert_eq!(html, "<p>:::tip\nSome :kbd[Ctrl] text\n:::</p>\n");
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod eventtest {
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+    use crate::{parse_events, parse_markdown_with_options, Event, HeadingLevel, ParserOptions, Tag};
+    use crate::token::TextualTokens;
+
+    #[test]
+    fn paragraph_events() {
+        let options = ParserOptions::default();
+        let events: Vec<Event> = parse_events("# Title {#top}\n\nSome **bold**\n[link](https://example.com) `code`\n", &options).collect();
+
+        assert_eq!(events, vec![
+            Event::Start(Tag::Heading { level: HeadingLevel::H1, id: Some(Cow::from("top")) }),
+            Event::Text(Cow::from("Title")),
+            Event::End(Tag::Heading { level: HeadingLevel::H1, id: Some(Cow::from("top")) }),
+            Event::Start(Tag::Paragraph),
+            Event::Text(Cow::from("Some ")),
+            Event::Start(Tag::Strong),
+            Event::Text(Cow::from("bold")),
+            Event::End(Tag::Strong),
+            Event::SoftBreak,
+            Event::Start(Tag::Link { url: Cow::from("https://example.com") }),
+            Event::Text(Cow::from("link")),
+            Event::End(Tag::Link { url: Cow::from("https://example.com") }),
+            Event::Text(Cow::from(" ")),
+            Event::Code(Cow::from("code")),
+            Event::End(Tag::Paragraph)
+        ]);
+    }
+
+    #[test]
+    fn list_and_quote_events() {
+        let options = ParserOptions::default();
+        let events: Vec<Event> = parse_events("3. third\n4. fourth\n-. item\n> quote\n> next\n", &options).collect();
+
+        assert_eq!(events, vec![
+            Event::Start(Tag::List(Some(3))),
+            Event::Start(Tag::Item),
+            Event::Text(Cow::from("third")),
+            Event::End(Tag::Item),
+            Event::Start(Tag::Item),
+            Event::Text(Cow::from("fourth")),
+            Event::End(Tag::Item),
+            Event::End(Tag::List(Some(3))),
+            Event::Start(Tag::List(None)),
+            Event::Start(Tag::Item),
+            Event::Text(Cow::from("item")),
+            Event::End(Tag::Item),
+            Event::End(Tag::List(None)),
+            Event::Start(Tag::BlockQuote),
+            Event::Start(Tag::Paragraph),
+            Event::Text(Cow::from("quote")),
+            Event::SoftBreak,
+            Event::Text(Cow::from("next")),
+            Event::End(Tag::Paragraph),
+            Event::End(Tag::BlockQuote)
+        ]);
+    }
+
+    #[test]
+    fn code_block_borrow_source() {
+        let options = ParserOptions::default();
+        let content = String::from("text\n  ```rust\n  fn main() {\n      run();\n  }\n  ```\n");
+        let events: Vec<Event> = parse_events(&content, &options).collect();
+
+        assert_eq!(events[3..], [
+            Event::Start(Tag::CodeBlock(Cow::from("rust"))),
+            Event::Text(Cow::from("fn main() {")),
+            Event::SoftBreak,
+            Event::Text(Cow::from("    run();")),
+            Event::SoftBreak,
+            Event::Text(Cow::from("}")),
+            Event::End(Tag::CodeBlock(Cow::from("rust")))
+        ]);
+
+        assert!(matches!(events[4], Event::Text(Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn inline_events_borrow_source() {
+        let options = ParserOptions::default();
+        let content = String::from("# Title\n\nSome `code` and [link](https://example.com) \\*not\\*\n> quote\n");
+        let events: Vec<Event> = parse_events(&content, &options).collect();
+
+        let borrowed = events
+            .iter()
+            .filter(|event| matches!(
+                event,
+                Event::Text(Cow::Borrowed(_)) | Event::Code(Cow::Borrowed(_)) | Event::Start(Tag::Link { url: Cow::Borrowed(_) })
+            ))
+            .count();
+
+        // the escaped stars aren't written as is
+        assert_eq!(borrowed, 7);
+        assert!(events.contains(&Event::Text(Cow::Owned(String::from(" *not*")))));
+    }
+
+    #[test]
+    fn blocks_spanning_lines_are_in_order() {
+        let options = ParserOptions { math: true, ..Default::default() };
+        let content = "[^1]: note\n    more\n\n<div>\nhtml\n</div>\n\n$$\nx^2\n$$\nend";
+        let events: Vec<Event> = parse_events(content, &options).collect();
+
+        assert_eq!(events, vec![
+            Event::Start(Tag::FootnoteDefinition(Cow::from("1"))),
+            Event::Start(Tag::Paragraph),
+            Event::Text(Cow::from("note")),
+            Event::SoftBreak,
+            Event::Text(Cow::from("more")),
+            Event::End(Tag::Paragraph),
+            Event::End(Tag::FootnoteDefinition(Cow::from("1"))),
+            Event::Start(Tag::HtmlBlock),
+            Event::Html(Cow::from("<div>\nhtml\n</div>")),
+            Event::End(Tag::HtmlBlock),
+            Event::DisplayMath(Cow::from("x^2")),
+            Event::Start(Tag::Paragraph),
+            Event::Text(Cow::from("end")),
+            Event::End(Tag::Paragraph)
+        ]);
+    }
+
+    #[test]
+    fn parse_markdown_collect_the_stream() {
+        let options = ParserOptions { math: true, ..Default::default() };
+        let content = "# Title\n\n[^1]: note\n    more\n\ntext[^1]\n```\ncode\n```\n$$\nx\n$$\n";
+        let streamed: BTreeMap<usize, String> = TextualTokens::new(content, &options)
+            .map(|(idx, token)| (idx, format!("{:?}", token.tag.map(|t| t.content))))
+            .collect();
+
+        let parsed: BTreeMap<usize, String> = parse_markdown_with_options(content, &options)
+            .unwrap()
+            .into_iter()
+            .map(|(idx, token)| (idx, format!("{:?}", token.tag.map(|t| t.content))))
+            .collect();
+
+        assert_eq!(streamed, parsed);
+    }
+}
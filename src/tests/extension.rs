@@ -0,0 +1,276 @@
+#[cfg(test)]
+mod extensiontest {
+    use std::sync::Arc;
+    use crate::{
+        parse_markdown_with_options, parse_events, render_html, render_plain_text, format_markdown,
+        BlockContinuation, BlockParserExt, CustomNode, Event, FormatStyle, InlineParserExt, ParserOptions
+    };
+    use crate::token::tag::TagOperator;
+
+    #[derive(Debug, Clone)]
+    struct Shortcode {
+        source: String,
+        name: String
+    }
+
+    impl CustomNode for Shortcode {
+        fn name(&self) -> &str {
+            "shortcode"
+        }
+
+        fn source(&self) -> &str {
+            &self.source
+        }
+
+        fn render_html(&self) -> String {
+            format!("<include src=\"{}\" />", self.name)
+        }
+    }
+
+    #[derive(Debug)]
+    struct ShortcodeParser;
+
+    impl InlineParserExt for ShortcodeParser {
+        fn parse_inline(&self, content: &str, cursor: usize) -> Option<(Box<dyn CustomNode>, usize)> {
+            let rest = content[cursor..].strip_prefix("{{<")?;
+            let end = rest.find(">}}")?;
+            let node = Shortcode {
+                source: format!("{{{{<{}>}}}}", &rest[..end]),
+                name: rest[..end].trim().to_string()
+            };
+
+            Some((Box::new(node), end + 6))
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct Warning {
+        source: String,
+        lines: Vec<String>,
+        closed: bool
+    }
+
+    impl CustomNode for Warning {
+        fn name(&self) -> &str {
+            "warning"
+        }
+
+        fn source(&self) -> &str {
+            &self.source
+        }
+
+        fn render_html(&self) -> String {
+            format!("<aside>{}</aside>", self.lines.join(" "))
+        }
+
+        fn render_text(&self) -> String {
+            format!("Warning: {}", self.lines.join(" "))
+        }
+
+        fn push_line(&mut self, line: &str) -> BlockContinuation {
+            if self.closed {
+                return BlockContinuation::Stop;
+            }
+
+            self.source.push('\n');
+            self.source.push_str(line.trim());
+            if line.trim() == ":::" {
+                self.closed = true;
+                return BlockContinuation::Close;
+            }
+
+            self.lines.push(line.trim().to_string());
+            BlockContinuation::Append
+        }
+    }
+
+    #[derive(Debug)]
+    struct WarningParser;
+
+    impl BlockParserExt for WarningParser {
+        fn parse_block(&self, _idx: usize, line: &str) -> Option<Box<dyn CustomNode>> {
+            if line.trim() != "::: warning" {
+                return None;
+            }
+
+            Some(Box::new(Warning { source: line.trim().to_string(), lines: Vec::new(), closed: false }))
+        }
+    }
+
+    fn extension_options() -> ParserOptions {
+        ParserOptions::new()
+            .block_parser(Arc::new(WarningParser))
+            .inline_parser(Arc::new(ShortcodeParser))
+    }
+
+    #[test]
+    fn parse_custom_block() {
+        let content = "
+            ::: warning
+            Don't **panic**
+            - not a list
+            :::
+            After
+        ";
+
+        let res = parse_markdown_with_options(content, &extension_options()).unwrap();
+        let tag = res.get(&1).unwrap().tag.as_ref().unwrap();
+        let node = match &tag.operator {
+            TagOperator::Custom(node) => node,
+            _ => panic!("expect a custom block")
+        };
+
+        let warning = node.as_any().downcast_ref::<Warning>().unwrap();
+        assert_eq!(warning.lines, vec!["Don't **panic**", "- not a list"]);
+        assert_eq!(tag.content, "::: warning\nDon't **panic**\n- not a list\n:::");
+
+        // the lines of the block aren't parsed
+        assert!(!res.contains_key(&2));
+        assert!(!res.contains_key(&4));
+        assert_eq!(res.get(&5).unwrap().text.as_ref().unwrap().content, "After");
+    }
+
+    #[derive(Debug, Clone)]
+    struct Marker {
+        name: &'static str,
+        source: String
+    }
+
+    impl CustomNode for Marker {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn source(&self) -> &str {
+            &self.source
+        }
+
+        fn render_html(&self) -> String {
+            format!("<{}>", self.name)
+        }
+    }
+
+    // Claim the lines starting with the prefix
+    #[derive(Debug)]
+    struct PrefixParser(&'static str, &'static str);
+
+    impl BlockParserExt for PrefixParser {
+        fn parse_block(&self, _idx: usize, line: &str) -> Option<Box<dyn CustomNode>> {
+            if !line.trim().starts_with(self.1) {
+                return None;
+            }
+
+            Some(Box::new(Marker { name: self.0, source: line.trim().to_string() }))
+        }
+    }
+
+    // Match the first byte of a multibyte character
+    #[derive(Debug)]
+    struct SplitParser;
+
+    impl InlineParserExt for SplitParser {
+        fn parse_inline(&self, content: &str, cursor: usize) -> Option<(Box<dyn CustomNode>, usize)> {
+            content[cursor..].starts_with('é').then(|| {
+                (Box::new(Marker { name: "split", source: String::from("é") }) as Box<dyn CustomNode>, 1)
+            })
+        }
+    }
+
+    #[test]
+    fn parse_custom_inline() {
+        let content = "See {{< include file.md >}} and `{{< code >}}`";
+
+        let html = render_html(content, &extension_options()).unwrap();
+        assert_eq!(html, "<p>See <include src=\"include file.md\" /> and <code>{{&lt; code &gt;}}</code></p>\n");
+
+        let text = render_plain_text(content, &extension_options(), None).unwrap();
+        assert_eq!(text, "See {{< include file.md >}} and {{< code >}}");
+
+        // without the extension the shortcode is a text
+        let html = render_html(content, &ParserOptions::new()).unwrap();
+        assert_eq!(html, "<p>See {{&lt; include file.md &gt;}} and <code>{{&lt; code &gt;}}</code></p>\n");
+    }
+
+    #[test]
+    fn adjacent_and_unclosed_shortcodes() {
+        let html = render_html("{{<a>}}{{<b>}} {{< open", &extension_options()).unwrap();
+        assert_eq!(html, "<p><include src=\"a\" /><include src=\"b\" /> {{&lt; open</p>\n");
+    }
+
+    #[test]
+    fn expect_inline_match_within_a_character_to_be_ignored() {
+        let options = ParserOptions::new().inline_parser(Arc::new(SplitParser));
+        let html = render_html("café", &options).unwrap();
+
+        assert_eq!(html, "<p>café</p>\n");
+    }
+
+    #[test]
+    fn first_block_parser_win_over_the_builtin_blocks() {
+        let options = ParserOptions::new()
+            .block_parser(Arc::new(PrefixParser("todo", "# TODO")))
+            .block_parser(Arc::new(PrefixParser("hash", "#")));
+
+        let res = parse_markdown_with_options("# TODO write\n# Title\n-. item", &options).unwrap();
+        let names: Vec<String> = res
+            .values()
+            .map(|token| match &token.tag.as_ref().unwrap().operator {
+                TagOperator::Custom(node) => node.name().to_string(),
+                operator => format!("{:?}", operator)
+            })
+            .collect();
+
+        assert_eq!(names, vec!["todo", "hash", "UnorderedList"]);
+    }
+
+    #[test]
+    fn unclosed_custom_block_run_to_the_end() {
+        let content = "::: warning\nFirst\n\nSecond";
+        let res = parse_markdown_with_options(content, &extension_options()).unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res.get(&0).unwrap().tag.as_ref().unwrap().content, content);
+
+        let html = render_html(content, &extension_options()).unwrap();
+        assert_eq!(html, "<aside>First  Second</aside>\n");
+    }
+
+    #[test]
+    fn render_custom_nodes() {
+        let content = "::: warning\nCareful\n:::\n\nText {{< note >}}";
+        let options = extension_options();
+
+        let html = render_html(content, &options).unwrap();
+        assert_eq!(html, "<aside>Careful</aside>\n<p>Text <include src=\"note\" /></p>\n");
+
+        let text = render_plain_text(content, &options, None).unwrap();
+        assert_eq!(text, "Warning: Careful\nText {{< note >}}");
+
+        let markdown = format_markdown(content, &options, &FormatStyle::default()).unwrap();
+        assert_eq!(markdown, "::: warning\nCareful\n:::\n\nText {{< note >}}\n");
+
+        let customs: Vec<String> = parse_events(content, &options)
+            .filter_map(|event| match event {
+                Event::Custom(node) => Some(node.name().to_string()),
+                _ => None
+            })
+            .collect();
+
+        assert_eq!(customs, vec!["warning", "shortcode"]);
+    }
+
+    #[test]
+    fn stop_custom_block_at_code_fence() {
+        let content = "
+            ::: warning
+            ```
+            :::
+            ```
+        ";
+
+        let res = parse_markdown_with_options(content, &extension_options()).unwrap();
+        let tag = res.get(&1).unwrap().tag.as_ref().unwrap();
+        assert_eq!(tag.content, "::: warning");
+        assert!(!res.contains_key(&3));
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+#[cfg(feature = "definition_list")]
+mod definitionlisttest {
+    use crate::{parse_markdown, parse_markdown_with_options, render_html, ParserOptions};
+    use crate::token::tag::TagOperator;
+
+    fn definition_options() -> ParserOptions {
+        ParserOptions {
+            definition_list: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_definition_list() {
+        let content = "
+            Apple
+            : A fruit
+            : A company
+
+            Orange
+            :   The fruit of an
+                evergreen tree
+            Paragraph
+        ";
+
+        let res = parse_markdown_with_options(content, &definition_options()).unwrap();
+        let tag = res.get(&1).unwrap().tag.as_ref().unwrap();
+        assert_eq!(tag.operator, TagOperator::DefinitionList);
+
+        let items = &tag.metas.as_ref().unwrap().definition_list.as_ref().unwrap().items;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].term, "Apple");
+        assert_eq!(items[0].definitions, vec!["A fruit", "A company"]);
+        assert_eq!(items[1].term, "Orange");
+        assert_eq!(items[1].definitions, vec!["The fruit of an\nevergreen tree"]);
+
+        // the folded lines aren't part of the result
+        assert!(!res.contains_key(&2));
+        assert!(!res.contains_key(&5));
+        assert_eq!(res.get(&8).unwrap().text.as_ref().unwrap().content, "Paragraph");
+    }
+
+    #[test]
+    fn expect_definition_list_to_be_opt_in() {
+        let content = "
+            Apple
+            : A fruit
+            :not a definition
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        assert_eq!(res.get(&1).unwrap().text.as_ref().unwrap().content, "Apple");
+        assert_eq!(res.get(&2).unwrap().text.as_ref().unwrap().content, ": A fruit");
+
+        let res = parse_markdown_with_options(content, &definition_options()).unwrap();
+        assert_eq!(res.get(&3).unwrap().text.as_ref().unwrap().content, ":not a definition");
+    }
+
+    #[test]
+    fn expect_definition_to_follow_its_term() {
+        let content = "
+            Term
+
+            : after a blank line
+            Apple
+            : A fruit
+            :
+        ";
+
+        let res = parse_markdown_with_options(content, &definition_options()).unwrap();
+        assert!(res.get(&1).unwrap().tag.is_none());
+        assert_eq!(res.get(&3).unwrap().text.as_ref().unwrap().content, ": after a blank line");
+
+        // an empty definition doesn't extend the list
+        let tag = res.get(&4).unwrap().tag.as_ref().unwrap();
+        let items = &tag.metas.as_ref().unwrap().definition_list.as_ref().unwrap().items;
+        assert_eq!(items[0].definitions, vec!["A fruit"]);
+        assert_eq!(res.get(&6).unwrap().text.as_ref().unwrap().content, ":");
+    }
+
+    #[test]
+    fn render_definition_list() {
+        let content = "
+            **Apple**
+            : A fruit
+        ";
+
+        let html = render_html(content, &definition_options()).unwrap();
+        assert_eq!(html, "<dl>\n<dt><strong>Apple</strong></dt>\n<dd>A fruit</dd>\n</dl>\n");
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "abbreviation")]
+mod abbreviationtest {
+    use crate::{parse_markdown, parse_markdown_with_options, render_html, ParserOptions};
+    use crate::token::tag::TagOperator;
+
+    fn abbreviation_options() -> ParserOptions {
+        ParserOptions {
+            abbreviation: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_abbreviations() {
+        let content = "
+            The HTML and HTML5 specs but not HTMLS or `HTML`
+
+            *[HTML]: Hyper Text Markup Language
+            *[HTML5]: Hyper Text Markup Language 5
+        ";
+
+        let res = parse_markdown_with_options(content, &abbreviation_options()).unwrap();
+        let tag = res.get(&3).unwrap().tag.as_ref().unwrap();
+        assert_eq!(tag.operator, TagOperator::AbbreviationDefinition);
+
+        let metas = res.get(&1).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap();
+        let abbreviations = metas.abbreviations.as_ref().unwrap();
+        assert_eq!(abbreviations.len(), 2);
+        assert_eq!(abbreviations[0].abbr, "HTML");
+        assert_eq!(abbreviations[0].title, "Hyper Text Markup Language");
+        assert_eq!(abbreviations[0].col.unwrap(), 4);
+        assert_eq!(abbreviations[1].abbr, "HTML5");
+        assert_eq!(abbreviations[1].col.unwrap(), 13);
+    }
+
+    #[test]
+    fn expect_abbreviation_to_be_opt_in() {
+        let content = "
+            The HTML spec
+            *[HTML]: Hyper Text Markup Language
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        assert!(res.get(&1).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap().abbreviations.is_none());
+        assert!(res.get(&2).unwrap().tag.is_none());
+    }
+
+    #[test]
+    fn expect_abbreviations_to_skip_the_markup() {
+        let content = "
+            See [HTML](/HTML) <a title=\"HTML\">HTML</a> `HTML` C++ C++x
+
+            *[HTML]: Old title
+            *[C++]: A language
+            *[HTML]: New title
+        ";
+
+        let res = parse_markdown_with_options(content, &abbreviation_options()).unwrap();
+        let metas = res.get(&1).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap();
+        let abbreviations = metas.abbreviations.as_ref().unwrap();
+
+        let cols: Vec<usize> = abbreviations.iter().filter_map(|a| a.col).collect();
+        assert_eq!(cols, vec![5, 34, 50]);
+        assert_eq!(abbreviations[0].title, "New title");
+        assert_eq!(abbreviations[2].abbr, "C++");
+    }
+
+    #[test]
+    fn render_abbreviations() {
+        let content = "
+            The *HTML* spec
+            *[HTML]: Hyper \"Text\" Markup Language
+        ";
+
+        let html = render_html(content, &abbreviation_options()).unwrap();
+        assert_eq!(html, "<p>The <em><abbr title=\"Hyper &quot;Text&quot; Markup Language\">HTML</abbr></em> spec</p>\n");
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod footnotetest {
+    use crate::{parse_footnotes, parse_markdown, parse_markdown_with_options, render_html, ParserOptions};
+    use crate::token::footnote::get_footnotes;
+    use crate::token::tag::TagOperator;
+
+    #[test]
+    fn parse_footnote_reference() {
+        let content = "
+            Here is a note[^note] and another one[^1]
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        let refs = res.get(&1)
+            .unwrap()
+            .text
+            .as_ref()
+            .unwrap()
+            .metas
+            .as_ref()
+            .unwrap()
+            .footnotes
+            .as_ref()
+            .unwrap();
+
+        assert_eq!(refs[0].label, "note");
+        assert_eq!(refs[0].col.unwrap(), 14);
+        assert_eq!(refs[0].number.unwrap(), 1);
+        assert_eq!(refs[1].label, "1");
+        assert_eq!(refs[1].number.unwrap(), 2);
+    }
+
+    #[test]
+    fn skip_footnote_reference_in_code_span() {
+        let content = "
+            Use `arr[^1]` or ``[^x]`` then a note[^2]
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        let refs = res.get(&1).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap().footnotes.as_ref().unwrap();
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].label, "2");
+        assert_eq!(refs[0].col.unwrap(), 37);
+        assert_eq!(refs[0].number.unwrap(), 1);
+
+        let footnotes = parse_footnotes(content).unwrap();
+        assert_eq!(footnotes.references.len(), 1);
+    }
+
+    #[test]
+    fn parse_footnote_definition() {
+        let content = "
+            Baozi[^1] are tasty
+
+            [^1]: A steamed bun
+                filled with pork
+
+                Second paragraph
+            After the footnote
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        let definition = res.get(&3).unwrap().tag.as_ref().unwrap();
+        let meta = definition.metas.as_ref().unwrap().footnote.as_ref().unwrap();
+
+        assert_eq!(definition.operator, TagOperator::FootnoteDefinition);
+        assert_eq!(definition.content, "A steamed bun\nfilled with pork\n\nSecond paragraph");
+        assert_eq!(meta.label, "1");
+        assert_eq!(meta.number.unwrap(), 1);
+        assert_eq!(meta.paragraphs.len(), 2);
+
+        // continuation lines are part of the definition
+        assert!(!res.contains_key(&4));
+        assert!(!res.contains_key(&6));
+
+        let after = res.get(&7).unwrap().text.as_ref().unwrap();
+        assert_eq!(after.content, "After the footnote");
+    }
+
+    #[test]
+    fn list_footnotes_in_reference_order() {
+        let content = "
+            First[^b] then[^a] and again[^b]
+
+            [^a]: Note a
+            [^b]: Note b
+            [^c]: Note c
+            Missing[^d]
+        ";
+
+        let footnotes = parse_footnotes(content).unwrap();
+
+        assert_eq!(footnotes.definitions[0].label, "b");
+        assert_eq!(footnotes.definitions[0].number.unwrap(), 1);
+        assert_eq!(footnotes.definitions[1].label, "a");
+        assert_eq!(footnotes.definitions[1].number.unwrap(), 2);
+        assert_eq!(footnotes.references.len(), 4);
+
+        let unused = footnotes.unused();
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].label, "c");
+        assert_eq!(unused[0].line, 5);
+
+        let undefined = footnotes.undefined();
+        assert_eq!(undefined.len(), 1);
+        assert_eq!(undefined[0].label, "d");
+        assert_eq!(undefined[0].line, 6);
+    }
+
+    #[test]
+    fn skip_footnote_reference_in_html_block() {
+        let content = "<div>\n[^b]\n</div>\n\n| x |\n|---|\n| see[^t] |\n\n[^b]: Note b\n[^t]: Note t\n";
+        let options = ParserOptions::gfm();
+
+        let tokens = parse_markdown_with_options(content, &options).unwrap();
+        let footnotes = get_footnotes(&tokens);
+
+        let references: Vec<&str> = footnotes.references.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(references, vec!["t"]);
+
+        let unused = footnotes.unused();
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].label, "b");
+
+        let html = render_html(content, &options).unwrap();
+        assert!(html.starts_with("<div>\n[^b]\n</div>\n"));
+        assert_eq!(html.matches("footnote-backref").count(), 1);
+        assert!(html.contains("<li id=\"fn-t\">\n<p>Note t"));
+    }
+}
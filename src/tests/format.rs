@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod formattest {
+    use crate::{format_markdown, render_html, FormatStyle, GithubOptions, OrderedNumbering, ParserOptions};
+
+    // Hand written documents covering the syntaxes handled by the parser and the fences within code
+    const CORPUS: &[&str] = &[
+        "# Title\n\nA little *turtle* is walking\ndown the __road__\n",
+        "## Install {#setup}\n\n+. Baozi\n*. _Xiaolongbao_ and **bold _nested_**\n\n1. First\n3. Second\n\n5. Restart\n",
+        "> A quote with `code` and [a *link*](https://baozi.com)\n> ![cat](cat.png)\n",
+        "Some code\n\n```rust\nfn main() {\n    println!(\"hello\");\n}\n```\n\n~~~\nplain\n~~~\n",
+        "A note[^1] and an other[^note].\n\n[^1]: The first note\n    on two lines\n\n    With a second paragraph\n[^note]: Other\n",
+        "<div class=\"box\">\n  <p>Raw</p>\n</div>\n\nInline <kbd>Ctrl</kbd> html\n\n<!-- toc -->\n- [Title](#title)\n<!-- tocstop -->\n",
+        "Escaped \\*stars\\* and \\# hash\n\\# Not a heading\n1\\. Not a list\n\\+. Not a bullet\n",
+        "The value $x_1$ and\n\n$$\nE = mc^2\n$$\n\n```math\nx^2\n```\n",
+        ":rocket: shipped by @octocat in #12 and org/repo#7\n",
+        "snake_case_name and intra*word*emphasis with ~~strike~~ and ``code ` tick``\n",
+        "---\ntitle: Doc\n---\n\n\n# Heading **bold**\n\n\n\nText    with   spaces\n",
+        "A very long line of text which goes on and on with a [link that should not be split](https://example.com/a/long/path) and `some code span` at the end\n",
+        "```\nhas ~~~ inside\n~~~\n```\nafter\n\n````md\n```rust\nlet a = 1;\n```\n````\n\ntext ``` inline\n"
+    ];
+
+    fn corpus_options() -> ParserOptions {
+        ParserOptions {
+            math: true,
+            github: Some(GithubOptions::default()),
+            ..Default::default()
+        }
+    }
+
+    fn styles() -> Vec<FormatStyle> {
+        vec![
+            FormatStyle::default(),
+            FormatStyle {
+                bullet: '*',
+                ordered_numbering: OrderedNumbering::One,
+                emphasis: '_',
+                fence: '~',
+                wrap_width: Some(40)
+            },
+            FormatStyle {
+                bullet: '+',
+                wrap_width: Some(10),
+                ..Default::default()
+            }
+        ]
+    }
+
+    // The wrapping only change the whitespaces
+    fn normalize(html: &str) -> String {
+        html.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn expect_format_to_be_idempotent() {
+        let options = corpus_options();
+        for style in styles() {
+            for content in CORPUS {
+                let formatted = format_markdown(content, &options, &style).unwrap();
+                let twice = format_markdown(&formatted, &options, &style).unwrap();
+                assert_eq!(formatted, twice, "\n{:?}\n{}", style, content);
+            }
+        }
+    }
+
+    #[test]
+    fn expect_format_to_preserve_semantics() {
+        let options = corpus_options();
+        for style in styles() {
+            for content in CORPUS {
+                let formatted = format_markdown(content, &options, &style).unwrap();
+                let expected = render_html(content, &options).unwrap();
+                let res = render_html(&formatted, &options).unwrap();
+                assert_eq!(normalize(&expected), normalize(&res), "\n{:?}\n{}\n{}", style, content, formatted);
+            }
+        }
+    }
+
+    // Fragments joined at random to reach the corners the hand written corpus doesn't
+    const FRAGMENTS: &[&str] = &[
+        "# ", "## ", ">", "> ", "-. ", "1. ", "```", "~~~", "x", "y z", " ", "\n", "\n\n", "*", "**", "_",
+        "`", "[a](b)", "![i](p)", "$", "$$", "|", "---", "\\", "<b>", "<div>", "#", "~~", "[^1]", "[^1]: n",
+        "    ", "\t", ":::", "-", "+. ", "é", ": ", "@u", "#1", ":tada:", "| a |", "|---|", "::: n", "{#i}", "\\*"
+    ];
+
+    // Deterministic documents built from the fragments with a xorshift generator
+    fn generated_documents(count: usize) -> Vec<String> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+
+        (0..count)
+            .map(|_| {
+                let len = 1 + next() % 8;
+                (0..len).map(|_| FRAGMENTS[next() % FRAGMENTS.len()]).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expect_format_to_be_idempotent_on_generated_documents() {
+        let options = ParserOptions {
+            footnotes: true,
+            directives: true,
+            ..corpus_options()
+        };
+
+        for style in styles() {
+            for content in generated_documents(2000) {
+                let formatted = format_markdown(&content, &options, &style).unwrap();
+                let twice = format_markdown(&formatted, &options, &style).unwrap();
+                assert_eq!(formatted, twice, "\n{:?}\n{:?}", style, content);
+
+                let expected = render_html(&content, &options).unwrap();
+                let res = render_html(&formatted, &options).unwrap();
+                assert_eq!(normalize(&expected), normalize(&res), "\n{:?}\n{:?}\n{:?}", style, content, formatted);
+            }
+        }
+    }
+
+    #[test]
+    fn format_empty_markers() {
+        let options = ParserOptions::default();
+        let style = FormatStyle::default();
+
+        assert_eq!(format_markdown("> >\n", &options, &style).unwrap(), "> >\n");
+        assert_eq!(format_markdown(">## \n", &options, &style).unwrap(), "> >\n");
+        assert_eq!(format_markdown("## #", &options, &style).unwrap(), "## #\n");
+    }
+
+    #[test]
+    fn format_code_block_blank_lines() {
+        let options = ParserOptions::default();
+        let style = FormatStyle::default();
+
+        assert_eq!(format_markdown("```x\n\n", &options, &style).unwrap(), "```x\n\n```\n");
+        assert_eq!(format_markdown("~~~    ```\nx\n~~~", &options, &style).unwrap(), "~~~```\nx\n~~~\n");
+    }
+
+    #[test]
+    fn format_escaped_delimiters() {
+        let options = ParserOptions {
+            math: true,
+            footnotes: true,
+            ..Default::default()
+        };
+
+        let style = FormatStyle::default();
+
+        assert_eq!(format_markdown("_\\*_", &options, &style).unwrap(), "*\\**\n");
+        assert_eq!(format_markdown("\\[^1] a", &options, &style).unwrap(), "\\[^1\\] a\n");
+        assert_eq!(format_markdown("$$y z\n$$$$", &options, &style).unwrap(), "$$\ny z\n$$$$\n");
+    }
+
+    #[test]
+    fn format_with_style() {
+        let content = "
+            # Title #
+            -. _one_
+            +. __two__
+            1. a
+            1. b
+            ```js
+            if (a) {
+              b();
+            }
+            ```
+        ";
+
+        let style = FormatStyle {
+            bullet: '*',
+            emphasis: '_',
+            fence: '~',
+            ..Default::default()
+        };
+
+        let formatted = format_markdown(content, &ParserOptions::default(), &style).unwrap();
+        assert_eq!(formatted, "# Title\n*. _one_\n*. __two__\n1. a\n2. b\n~~~js\nif (a) {\n  b();\n}\n~~~\n");
+    }
+
+    #[test]
+    fn format_wrap_width() {
+        let content = "one two **three four** five six";
+        let style = FormatStyle {
+            wrap_width: Some(12),
+            ..Default::default()
+        };
+
+        let formatted = format_markdown(content, &ParserOptions::default(), &style).unwrap();
+        assert_eq!(formatted, "one two\n**three four**\nfive six\n");
+
+        let invalid = FormatStyle {
+            bullet: '#',
+            ..Default::default()
+        };
+
+        assert!(format_markdown(content, &ParserOptions::default(), &invalid).is_err());
+    }
+}
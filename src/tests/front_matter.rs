@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod frontmattertest {
+    use crate::{parse_front_matter, parse_markdown};
+    use crate::parser::front_matter::FrontMatterKind;
+
+    #[test]
+    fn parse_yaml_front_matter() {
+        let content = "---\ntitle: Baozi\ntags: [food]\n---\n# Heading\n";
+
+        let front_matter = parse_front_matter(content).unwrap().unwrap();
+        assert_eq!(front_matter.kind, FrontMatterKind::Yaml);
+        assert_eq!(front_matter.raw, "title: Baozi\ntags: [food]\n");
+        assert_eq!(front_matter.start_line, 0);
+        assert_eq!(front_matter.end_line, 3);
+        assert_eq!(&content[front_matter.span], "---\ntitle: Baozi\ntags: [food]\n---\n");
+    }
+
+    #[test]
+    fn parse_toml_front_matter_excluded_from_body() {
+        let content = "+++
+            title = \"Baozi\"
+            +++
+            # Heading
+        ";
+
+        let front_matter = parse_front_matter(content).unwrap().unwrap();
+        assert_eq!(front_matter.kind, FrontMatterKind::Toml);
+        assert_eq!(front_matter.start_line, 0);
+        assert_eq!(front_matter.end_line, 2);
+
+        let res = parse_markdown(content).unwrap();
+        assert!(!res.contains_key(&0));
+        assert!(!res.contains_key(&1));
+        assert!(!res.contains_key(&2));
+        assert_eq!(res.get(&3).unwrap().tag.as_ref().unwrap().content, "Heading");
+    }
+
+    #[test]
+    fn expect_no_front_matter() {
+        let unclosed = "---\ntitle: Baozi\n# Heading";
+        assert!(parse_front_matter(unclosed).unwrap().is_none());
+
+        let not_leading = "# Heading\n---\ntitle: Baozi\n---";
+        assert!(parse_front_matter(not_leading).unwrap().is_none());
+
+        // a thematic break after a blank line doesn't swallow the document
+        let after_blank = "\n---\n# Heading\ntext\n---\nafter";
+        assert!(parse_front_matter(after_blank).unwrap().is_none());
+
+        let res = parse_markdown(after_blank).unwrap();
+        assert_eq!(res.get(&2).unwrap().tag.as_ref().unwrap().content, "Heading");
+        assert_eq!(res.get(&3).unwrap().text.as_ref().unwrap().content, "text");
+    }
+
+    #[cfg(feature = "front_matter")]
+    #[test]
+    fn deserialize_front_matter() {
+        use serde::Deserialize;
+
+        #[derive(Deserialize)]
+        struct Metas {
+            title: String,
+            tags: Vec<String>
+        }
+
+        let yaml = "---\ntitle: Baozi\ntags: [food]\n---\n";
+        let metas: Metas = parse_front_matter(yaml).unwrap().unwrap().deserialize().unwrap();
+        assert_eq!(metas.title, "Baozi");
+        assert_eq!(metas.tags, vec!["food"]);
+
+        let toml = "+++\ntitle = \"Baozi\"\ntags = [\"food\"]\n+++\n";
+        let metas: Metas = parse_front_matter(toml).unwrap().unwrap().deserialize().unwrap();
+        assert_eq!(metas.title, "Baozi");
+
+        let invalid = "---\ntitle: [\n---\n";
+        let res: Result<Metas, _> = parse_front_matter(invalid).unwrap().unwrap().deserialize();
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod githubtest {
+    use crate::{parse_markdown, parse_markdown_with_options, render_html, GithubOptions, ParserOptions};
+    use crate::parser::emoji_table::EMOJI_SHORTCODES;
+
+    fn github_options() -> ParserOptions {
+        ParserOptions {
+            github: Some(GithubOptions {
+                repository: Some("octo/crate".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_github_extensions() {
+        let content = "
+            Shipped :rocket: by @octocat and @octo/team fix #12 and other/repo#7 :unknown:
+        ";
+
+        let res = parse_markdown_with_options(content, &github_options()).unwrap();
+        let metas = res.get(&1).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap();
+
+        let emojis = metas.emojis.as_ref().unwrap();
+        assert_eq!(emojis.len(), 1);
+        assert_eq!(emojis[0].shortcode, "rocket");
+        assert_eq!(emojis[0].unicode, "🚀");
+        assert_eq!(emojis[0].col.unwrap(), 8);
+
+        let mentions = metas.mentions.as_ref().unwrap();
+        assert_eq!(mentions.len(), 2);
+        assert_eq!(mentions[0].user, "octocat");
+        assert_eq!(mentions[1].user, "octo/team");
+
+        let issues = metas.issue_refs.as_ref().unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].repository, None);
+        assert_eq!(issues[0].number, 12);
+        assert_eq!(issues[1].repository.as_deref(), Some("other/repo"));
+        assert_eq!(issues[1].number, 7);
+    }
+
+    #[test]
+    fn expect_emoji_table_to_be_sorted() {
+        // the shortcodes are looked up with a binary search
+        assert!(EMOJI_SHORTCODES.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn expect_github_extensions_to_be_opt_in() {
+        let content = "
+            Shipped :rocket: by @octocat fix #12
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        let metas = res.get(&1).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap();
+        assert!(metas.emojis.is_none());
+        assert!(metas.mentions.is_none());
+        assert!(metas.issue_refs.is_none());
+    }
+
+    #[test]
+    fn expect_emails_urls_and_code_to_be_skipped() {
+        let content = "
+            Mail me@example.com see https://example.com/#12 or `@octocat :rocket:`
+            Read https://medium.com/@user and [post](https://dev.to/@user#12) www.site.com/@a
+            Entity &#123; C# a#1 #12abc ``@octo `#3` ``
+        ";
+
+        let res = parse_markdown_with_options(content, &github_options()).unwrap();
+        for idx in 1..4 {
+            let metas = res.get(&idx).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap();
+            assert!(metas.emojis.is_none());
+            assert!(metas.mentions.is_none());
+            assert!(metas.issue_refs.is_none());
+        }
+
+        let html = render_html(content, &github_options()).unwrap();
+        assert!(!html.contains("user-mention"));
+        assert!(!html.contains("issue-link"));
+    }
+
+    #[test]
+    fn expect_references_around_urls() {
+        let content = "
+            (@octocat) https://example.com @hubot, #3.
+        ";
+
+        let res = parse_markdown_with_options(content, &github_options()).unwrap();
+        let metas = res.get(&1).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap();
+
+        let mentions = metas.mentions.as_ref().unwrap();
+        assert_eq!(mentions.len(), 2);
+        assert_eq!(mentions[0].col.unwrap(), 1);
+        assert_eq!(mentions[1].user, "hubot");
+        assert_eq!(mentions[1].col.unwrap(), 31);
+
+        let issues = metas.issue_refs.as_ref().unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].number, 3);
+    }
+
+    #[test]
+    fn render_github_extensions() {
+        let content = "
+            :tada: thanks @octocat for #12
+        ";
+
+        let html = render_html(content, &github_options()).unwrap();
+        assert_eq!(
+            html,
+            "<p><g-emoji alias=\"tada\">🎉</g-emoji> thanks <a href=\"https://github.com/octocat\" class=\"user-mention\">@octocat</a> \
+            for <a href=\"https://github.com/octo/crate/issues/12\" class=\"issue-link\">#12</a></p>\n"
+        );
+
+        let options = ParserOptions {
+            github: Some(GithubOptions {
+                mention_url: "https://gitlab.com/{user}".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let html = render_html(content, &options).unwrap();
+        assert!(html.contains("<a href=\"https://gitlab.com/octocat\" class=\"user-mention\">@octocat</a>"));
+        // no repository to resolve the issue
+        assert!(html.contains("for #12</p>"));
+    }
+
+    #[test]
+    fn expect_references_within_link_labels_to_be_text() {
+        let content = "[link @bob #1 :tada:](http://x) @alice";
+
+        let html = render_html(content, &github_options()).unwrap();
+        assert_eq!(
+            html,
+            "<p><a href=\"http://x\">link @bob #1 <g-emoji alias=\"tada\">🎉</g-emoji></a> \
+            <a href=\"https://github.com/alice\" class=\"user-mention\">@alice</a></p>\n"
+        );
+
+        let res = parse_markdown_with_options(content, &github_options()).unwrap();
+        let metas = res.get(&0).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap();
+        let mentions: Vec<&str> = metas.mentions.iter().flatten().map(|m| m.user.as_str()).collect();
+        assert_eq!(mentions, vec!["alice"]);
+        assert!(metas.issue_refs.is_none());
+        assert_eq!(metas.emojis.as_ref().unwrap().len(), 1);
+    }
+}
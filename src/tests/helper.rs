@@ -0,0 +1,21 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Assert Golden
+///
+/// # Description
+/// Compare the output with the golden file. The file is rewritten when UPDATE_GOLDEN is set
+///
+/// # Arguments
+/// * `name` &str
+/// * `output` &str
+pub fn assert_golden(name: &str, output: &str) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "src", "tests", "golden", name].iter().collect();
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, output).unwrap();
+    }
+
+    let expected = fs::read_to_string(&path).unwrap();
+    assert_eq!(output, expected, "{}", name);
+}
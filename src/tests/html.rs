@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod htmltest {
+    use crate::{parse_markdown, parse_markdown_with_options, ParserOptions};
+    use crate::token::tag::TagOperator;
+    use crate::parser::html::HtmlBlockKind;
+
+    #[test]
+    fn parse_html_block_details() {
+        let content = "
+            <details>
+              <summary>Baozi</summary>
+              A steamed bun
+            </details>
+
+            After the block
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        let block = res.get(&1).unwrap().tag.as_ref().unwrap();
+
+        assert_eq!(block.operator, TagOperator::HtmlBlock);
+        assert_eq!(block.metas.as_ref().unwrap().html_kind, Some(HtmlBlockKind::BlockTag));
+        assert_eq!(block.content, "<details>\n  <summary>Baozi</summary>\n  A steamed bun\n</details>");
+        assert!(!res.contains_key(&2));
+        assert_eq!(res.get(&6).unwrap().text.as_ref().unwrap().content, "After the block");
+    }
+
+    #[test]
+    fn parse_html_block_kinds() {
+        let content = "
+            <!-- a comment
+            on two lines -->
+            <script>
+            let a = 1 > 0;
+            </script>
+            <?php echo 1; ?>
+            <!DOCTYPE html>
+            <![CDATA[ x ]]>
+
+            <my-component>
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        let kind = |idx: usize| res.get(&idx).unwrap().tag.as_ref().unwrap().metas.as_ref().unwrap().html_kind.clone();
+
+        assert_eq!(kind(1), Some(HtmlBlockKind::Comment));
+        assert_eq!(kind(3), Some(HtmlBlockKind::RawText));
+        assert_eq!(kind(6), Some(HtmlBlockKind::ProcessingInstruction));
+        assert_eq!(kind(7), Some(HtmlBlockKind::Declaration));
+        assert_eq!(kind(8), Some(HtmlBlockKind::CData));
+        assert_eq!(kind(10), Some(HtmlBlockKind::CompleteTag));
+        assert!(!res.contains_key(&4));
+    }
+
+    #[test]
+    fn expect_complete_tag_to_not_interrupt_paragraph() {
+        let content = "
+            Some text
+            <my-component>
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        assert!(res.get(&2).unwrap().tag.is_none());
+    }
+
+    #[test]
+    fn parse_inline_html() {
+        let content = "
+            Press <kbd>Ctrl</kbd> then<br> done <!-- note -->
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        let text = res.get(&1).unwrap().text.as_ref().unwrap();
+        let html = text.metas.as_ref().unwrap().html.as_ref().unwrap();
+
+        assert_eq!(html[0].word, "<kbd>");
+        assert_eq!(html[0].col.unwrap(), 6);
+        assert_eq!(html[1].word, "</kbd>");
+        assert_eq!(html[2].word, "<br>");
+        assert_eq!(html[3].word, "<!-- note -->");
+        assert_eq!(text.content, "Press <kbd>Ctrl</kbd> then<br> done <!-- note -->");
+    }
+
+    #[test]
+    fn expect_html_to_be_dropped() {
+        let content = "
+            <div>
+            inside
+            </div>
+
+            Press <kbd>Ctrl</kbd>
+        ";
+
+        let options = ParserOptions {
+            drop_html: true,
+            ..Default::default()
+        };
+
+        let res = parse_markdown_with_options(content, &options).unwrap();
+        assert!(!res.contains_key(&1));
+        assert!(!res.contains_key(&2));
+
+        let text = res.get(&5).unwrap().text.as_ref().unwrap();
+        assert_eq!(text.content, "Press Ctrl");
+        assert!(text.metas.as_ref().unwrap().html.is_none());
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod jsontest {
+    use crate::{
+        parse_to_json, CodeOperator, CstInlineKind, CstKind, CustomNode, DirectiveKind, GithubOptions, HeadingLevel,
+        ParsedMarkdown, ParserOptions, TableAlignment, TagOperator
+    };
+    use crate::tests::helper::assert_golden;
+
+    /// Pretty
+    ///
+    /// # Description
+    /// Indent the json to keep the golden files readable
+    ///
+    /// # Arguments
+    /// * `json` &str
+    ///
+    /// # Return
+    /// String
+    fn pretty(json: &str) -> String {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let mut pretty = serde_json::to_string_pretty(&value).unwrap();
+        pretty.push('\n');
+
+        pretty
+    }
+
+    #[derive(Debug, Clone)]
+    struct Note;
+
+    impl CustomNode for Note {
+        fn name(&self) -> &str {
+            "note"
+        }
+
+        fn source(&self) -> &str {
+            "!!! note"
+        }
+
+        fn render_html(&self) -> String {
+            String::new()
+        }
+    }
+
+    #[test]
+    fn serialize_blocks_to_json() {
+        let content = "---\ntitle: Doc\n---\n# Title {#top}\n-. item\n1. first\n> quote\n```rust\nlet a = 1;\n```\n[^1]: note\n";
+        let json = parse_to_json(content, &ParserOptions::default()).unwrap();
+
+        assert_golden("blocks.json", &pretty(&json));
+    }
+
+    #[test]
+    fn serialize_text_metas_to_json() {
+        let options = ParserOptions {
+            github: Some(GithubOptions::default()),
+            ..Default::default()
+        };
+
+        let content = "**bold** _italic_ `code` [link](https://example.com) ![alt](img.png) :tada: @octocat #12[^1]";
+        let json = parse_to_json(content, &options).unwrap();
+
+        assert_golden("text_metas.json", &pretty(&json));
+    }
+
+    #[test]
+    fn deserialize_json_back() {
+        let content = "## Install\n\nRun `cargo build`\n";
+        let json = parse_to_json(content, &ParserOptions::default()).unwrap();
+        let parsed: ParsedMarkdown = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.version, 1);
+        assert_eq!(parsed.tokens.len(), 3);
+
+        let heading = parsed.tokens[&0].tag.as_ref().unwrap();
+        assert_eq!(heading.content, "Install");
+        assert_eq!(heading.metas.as_ref().unwrap().slug.as_deref(), Some("install"));
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+    }
+
+    #[test]
+    fn serialize_enums_as_tagged_values() {
+        let value: serde_json::Value = serde_json::from_str(&parse_to_json("# Title", &ParserOptions::default()).unwrap()).unwrap();
+        let tag = &value["tokens"]["0"]["tag"];
+
+        assert_eq!(tag["operator"], "heading");
+        assert_eq!(tag["metas"]["heading_kind"], "h1");
+
+        let kind = serde_json::to_value(crate::CstKind::Tag(crate::TagOperator::Heading)).unwrap();
+        assert_eq!(kind, serde_json::json!({ "type": "tag", "value": "heading" }));
+    }
+
+    #[test]
+    fn serialize_enum_shapes() {
+        let shapes = serde_json::json!({
+            "tag_operator": TagOperator::Heading,
+            "tag_operator_custom": TagOperator::Custom(Box::new(Note)),
+            "code_operator": CodeOperator::BlockCodeStart,
+            "heading_level": HeadingLevel::H2,
+            "table_alignment": TableAlignment::Center,
+            "directive_kind": DirectiveKind::Container,
+            "cst_kind": CstKind::Blank,
+            "cst_kind_tag": CstKind::Tag(TagOperator::Heading),
+            "cst_kind_code": CstKind::Code(CodeOperator::BlockCodeEnd),
+            "cst_inline_kind": CstInlineKind::InlineMath
+        });
+
+        assert_golden("enums.json", &pretty(&shapes.to_string()));
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod mathtest {
+    use crate::{parse_markdown, parse_markdown_with_options, render_html, format_markdown, FormatStyle, ParserOptions};
+    use crate::token::tag::TagOperator;
+
+    fn math_options() -> ParserOptions {
+        ParserOptions {
+            math: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_inline_math() {
+        let content = "
+            The value $x_1$ and $a * b$ cost $5 and *bold*
+        ";
+
+        let res = parse_markdown_with_options(content, &math_options()).unwrap();
+        let text = res.get(&1).unwrap().text.as_ref().unwrap();
+        let metas = text.metas.as_ref().unwrap();
+        let maths = metas.inline_math.as_ref().unwrap();
+
+        assert_eq!(maths.len(), 2);
+        assert_eq!(maths[0].word, "x_1");
+        assert_eq!(maths[0].col.unwrap(), 10);
+        assert_eq!(maths[1].word, "a * b");
+
+        // math content is exempt from the emphasis
+        let italic = metas.italic.as_ref().unwrap();
+        assert_eq!(italic.len(), 1);
+        assert_eq!(italic[0].word, "bold");
+        assert_eq!(italic[0].col.unwrap(), 40);
+
+        assert_eq!(text.sanitize_content, "The value $x_1$ and $a * b$ cost $5 and bold");
+    }
+
+    #[test]
+    fn expect_math_to_be_opt_in() {
+        let content = "
+            The value $x_1$
+            $$
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        let text = res.get(&1).unwrap().text.as_ref().unwrap();
+        assert!(text.metas.as_ref().unwrap().inline_math.is_none());
+        assert!(res.get(&2).unwrap().tag.is_none());
+    }
+
+    #[test]
+    fn expect_dollars_to_not_be_math() {
+        let content = "
+            It costs $5 and $6, \\$x\\$ or $ x $
+            `$x$` and $y$
+        ";
+
+        let res = parse_markdown_with_options(content, &math_options()).unwrap();
+        let prices = res.get(&1).unwrap().text.as_ref().unwrap();
+        assert!(prices.metas.as_ref().unwrap().inline_math.is_none());
+
+        // the dollars of a code span aren't math
+        let code = res.get(&2).unwrap().text.as_ref().unwrap();
+        let maths = code.metas.as_ref().unwrap().inline_math.as_ref().unwrap();
+        assert_eq!(maths.len(), 1);
+        assert_eq!(maths[0].word, "y");
+        assert_eq!(maths[0].col.unwrap(), 10);
+    }
+
+    #[test]
+    fn expect_math_to_bind_tighter_than_emphasis() {
+        let content = "
+            *a $b*c$ d*
+        ";
+
+        let res = parse_markdown_with_options(content, &math_options()).unwrap();
+        let text = res.get(&1).unwrap().text.as_ref().unwrap();
+        let italic = text.metas.as_ref().unwrap().italic.as_ref().unwrap();
+        assert_eq!(italic.len(), 1);
+        assert_eq!(italic[0].word, "a $b*c$ d");
+
+        let html = render_html(content, &math_options()).unwrap();
+        assert_eq!(html, "<p><em>a <span class=\"math math-inline\">b*c</span> d</em></p>\n");
+    }
+
+    #[test]
+    fn parse_display_math() {
+        let content = "
+            $$
+            E = mc^2
+            $$
+            $$ a_1 + b_1 $$
+            ```math
+            x^2
+            ```
+            After
+        ";
+
+        let res = parse_markdown_with_options(content, &math_options()).unwrap();
+        let block = res.get(&1).unwrap().tag.as_ref().unwrap();
+        assert_eq!(block.operator, TagOperator::DisplayMath);
+        assert_eq!(block.content, "E = mc^2");
+        assert!(!res.contains_key(&2));
+        assert!(!res.contains_key(&3));
+
+        let single = res.get(&4).unwrap().tag.as_ref().unwrap();
+        assert_eq!(single.operator, TagOperator::DisplayMath);
+        assert_eq!(single.content, "a_1 + b_1");
+
+        let fence = res.get(&5).unwrap().tag.as_ref().unwrap();
+        assert_eq!(fence.operator, TagOperator::DisplayMath);
+        assert_eq!(fence.content, "x^2");
+        assert_eq!(res.get(&8).unwrap().text.as_ref().unwrap().content, "After");
+    }
+
+    #[test]
+    fn expect_display_math_followed_by_text_to_not_open_a_block() {
+        let content = "$$E=mc^2$$ is famous\n\nNext\n\n# Heading";
+
+        let res = parse_markdown_with_options(content, &math_options()).unwrap();
+        assert!(res.get(&0).unwrap().tag.is_none());
+        assert_eq!(res.get(&4).unwrap().tag.as_ref().unwrap().operator, TagOperator::Heading);
+
+        let html = render_html(content, &math_options()).unwrap();
+        assert_eq!(html, concat!(
+            "<p><span class=\"math math-inline\">E=mc^2</span> is famous</p>\n",
+            "<p>Next</p>\n",
+            "<h1 id=\"heading\">Heading</h1>\n"
+        ));
+
+        // the math which can't be closed by a single $ keep its $$
+        let markdown = format_markdown("$$ a $$ and $$b$$\n", &math_options(), &FormatStyle::default()).unwrap();
+        assert_eq!(markdown, "$$ a $$ and $b$\n");
+    }
+
+    #[test]
+    fn render_math_html() {
+        let content = "
+            Inline $x_1 < y$ math
+
+            ```math
+            x^2
+            ```
+        ";
+
+        let html = render_html(content, &math_options()).unwrap();
+        assert_eq!(
+            html,
+            "<p>Inline <span class=\"math math-inline\">x_1 &lt; y</span> math</p>\n<span class=\"math math-display\">x^2</span>\n"
+        );
+    }
+}
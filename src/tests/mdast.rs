@@ -0,0 +1,194 @@
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod mdasttest {
+    use crate::{export_mdast, import_mdast, MdastKind, MdastNode, ParserOptions, Point};
+    use crate::tests::helper::assert_golden;
+
+    /// Get Root Children
+    ///
+    /// # Description
+    /// Export the markdown and return the children of the root
+    ///
+    /// # Arguments
+    /// * `content` &str
+    ///
+    /// # Return
+    /// Vec<MdastNode>
+    fn get_root_children(content: &str) -> Vec<MdastNode> {
+        let json = export_mdast(content, &ParserOptions::default()).unwrap();
+        match serde_json::from_str::<MdastNode>(&json).unwrap().kind {
+            MdastKind::Root { children } => children,
+            kind => panic!("unexpected root {:?}", kind)
+        }
+    }
+
+    #[test]
+    fn export_document_to_mdast() {
+        let content = "# Title\n\nSome **bold** and *italic*\n~~gone~~ `code` [link](https://example.com)\n\n-. first\n-. second\n\n3. third\n4. fourth\n\n> quote\n\n```rust title=\"main.rs\"\nfn main() {}\n```\n\n![logo](logo.png)[^1]\n\n[^1]: A note\n";
+        let json = export_mdast(content, &ParserOptions::default()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let mut pretty = serde_json::to_string_pretty(&value).unwrap();
+        pretty.push('\n');
+
+        assert_golden("mdast.json", &pretty);
+    }
+
+    #[test]
+    fn export_block_positions() {
+        let children = get_root_children("# Title\n\nfirst line\nsecond line\n\n  -. item\n");
+        let positions: Vec<(Point, Point)> = children
+            .iter()
+            .map(|node| {
+                let position = node.position.clone().unwrap();
+                (position.start, position.end)
+            })
+            .collect();
+
+        assert_eq!(positions, vec![
+            (Point { line: 1, column: 1, offset: Some(0) }, Point { line: 1, column: 8, offset: Some(7) }),
+            (Point { line: 3, column: 1, offset: Some(9) }, Point { line: 4, column: 12, offset: Some(31) }),
+            (Point { line: 6, column: 3, offset: Some(35) }, Point { line: 6, column: 10, offset: Some(42) })
+        ]);
+    }
+
+    #[test]
+    fn round_trip_markdown() {
+        let content = "# Title\n\nSome **bold** and *italic* \\*not\\*\n~~gone~~ `code` [link](https://example.com)\n\n-. first\n-. second\n\n3. third\n4. fourth\n\n> quote\n\n```rust title=\"main.rs\"\nfn main() {}\n```\n\n[^1]: A note\n";
+        let json = export_mdast(content, &ParserOptions::default()).unwrap();
+        let markdown = import_mdast(&json).unwrap();
+
+        assert_eq!(markdown, content);
+        assert_eq!(export_mdast(&markdown, &ParserOptions::default()).unwrap(), json);
+    }
+
+    #[test]
+    fn import_remark_tree() {
+        let json = r#"{"type":"root","children":[
+            {"type":"heading","depth":2,"children":[{"type":"text","value":"Usage"}]},
+            {"type":"thematicBreak"},
+            {"type":"paragraph","children":[{"type":"text","value":"1. not a list # or *emphasis*"}]},
+            {"type":"table","align":["left",null,"right"],"children":[
+                {"type":"tableRow","children":[
+                    {"type":"tableCell","children":[{"type":"text","value":"Name"}]},
+                    {"type":"tableCell","children":[{"type":"text","value":"Pipe"}]},
+                    {"type":"tableCell","children":[{"type":"text","value":"Size"}]}
+                ]},
+                {"type":"tableRow","children":[
+                    {"type":"tableCell","children":[{"type":"inlineCode","value":"a|b"}]},
+                    {"type":"tableCell","children":[]},
+                    {"type":"tableCell","children":[{"type":"text","value":"12"}]}
+                ]}
+            ]},
+            {"type":"code","lang":null,"meta":null,"value":"```\nnested\n```"}
+        ]}"#;
+
+        let markdown = import_mdast(json).unwrap();
+
+        assert_eq!(markdown, "## Usage\n\n1\\. not a list # or \\*emphasis\\*\n\n| Name | Pipe | Size |\n| :--- | --- | ---: |\n| `a\\|b` |  | 12 |\n\n~~~\n```\nnested\n```\n~~~\n");
+    }
+
+    #[test]
+    fn round_trip_directives() {
+        let content = "::::tabs\n:::tip[Read *this*]{#intro .note}\nSee :kbd[Ctrl]{.key}\n:::\n::::\n\n::youtube{vid=\"123\"}\n";
+        let options = ParserOptions::new().directives(true);
+        let json = export_mdast(content, &options).unwrap();
+        let children = match serde_json::from_str::<MdastNode>(&json).unwrap().kind {
+            MdastKind::Root { children } => children,
+            kind => panic!("unexpected root {:?}", kind)
+        };
+
+        let tip = match &children[0].kind {
+            MdastKind::ContainerDirective { name, children, .. } if name == "tabs" => children[0].clone(),
+            kind => panic!("unexpected node {:?}", kind)
+        };
+
+        match tip.kind {
+            MdastKind::ContainerDirective { name, attributes, children } => {
+                assert_eq!(name, "tip");
+                assert_eq!(attributes["id"], "intro");
+                assert_eq!(attributes["class"], "note");
+                assert!(children[0].data.as_ref().is_some_and(|data| data.directive_label));
+            },
+            kind => panic!("unexpected node {:?}", kind)
+        }
+
+        assert!(matches!(&children[1].kind, MdastKind::LeafDirective { name, .. } if name == "youtube"));
+        assert_eq!(import_mdast(&json).unwrap(), content);
+    }
+
+    #[test]
+    fn round_trip_link_titles() {
+        let content = "[home](/home \"Home \\\"page\\\"\") ![logo](logo.png 'The logo') [plain](/plain)\n";
+        let json = export_mdast(content, &ParserOptions::default()).unwrap();
+        let paragraph = get_root_children(content).remove(0);
+        let children = match paragraph.kind {
+            MdastKind::Paragraph { children } => children,
+            kind => panic!("unexpected node {:?}", kind)
+        };
+
+        assert!(matches!(&children[0].kind, MdastKind::Link { title: Some(title), .. } if title == "Home \"page\""));
+        assert!(matches!(&children[2].kind, MdastKind::Image { title: Some(title), .. } if title == "The logo"));
+        assert!(matches!(&children[4].kind, MdastKind::Link { title: None, .. }));
+        assert_eq!(import_mdast(&json).unwrap(), "[home](/home \"Home \\\"page\\\"\") ![logo](logo.png \"The logo\") [plain](/plain)\n");
+    }
+
+    #[test]
+    fn round_trip_nested_lists() {
+        let content = "-. a\n  -. nested\n    1. deep\n-. b\n";
+        let children = get_root_children(content);
+        assert_eq!(children.len(), 1);
+
+        let items = match &children[0].kind {
+            MdastKind::List { children, .. } => children,
+            kind => panic!("unexpected node {:?}", kind)
+        };
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].position.as_ref().unwrap().end.line, 3);
+        match &items[0].kind {
+            MdastKind::ListItem { children, .. } => {
+                assert!(matches!(&children[1].kind, MdastKind::List { ordered: false, children, .. } if children.len() == 1));
+            },
+            kind => panic!("unexpected node {:?}", kind)
+        }
+
+        let json = export_mdast(content, &ParserOptions::default()).unwrap();
+        assert_eq!(import_mdast(&json).unwrap(), content);
+    }
+
+    #[test]
+    fn export_html_block_position() {
+        let children = get_root_children("text\n\n<div>\n<p>\nhello\n</p>\n</div>\n");
+        let position = children[1].position.clone().unwrap();
+
+        assert!(matches!(children[1].kind, MdastKind::Html { .. }));
+        assert_eq!((position.start.line, position.start.column), (3, 1));
+        assert_eq!((position.end.line, position.end.column), (7, 7));
+    }
+
+    #[test]
+    fn import_references_and_null_attributes() {
+        let json = r#"{"type":"root","children":[
+            {"type":"paragraph","children":[
+                {"type":"linkReference","identifier":"docs","label":"Docs","referenceType":"full","children":[{"type":"text","value":"the docs"}]},
+                {"type":"text","value":" "},
+                {"type":"imageReference","identifier":"logo","label":"logo","referenceType":"collapsed","alt":"logo"},
+                {"type":"text","value":" "},
+                {"type":"linkReference","identifier":"missing","label":"missing","referenceType":"shortcut","children":[{"type":"emphasis","children":[{"type":"text","value":"gone"}]}]}
+            ]},
+            {"type":"definition","identifier":"docs","label":"Docs","url":"https://docs.rs","title":"Docs"},
+            {"type":"leafDirective","name":"video","attributes":{"autoplay":null,"src":"a.mp4"},"children":[]}
+        ]}"#;
+
+        let markdown = import_mdast(json).unwrap();
+
+        assert_eq!(markdown, "[the docs](https://docs.rs \"Docs\") logo *gone*\n\n[Docs]: https://docs.rs \"Docs\"\n\n::video{autoplay=\"\" src=\"a.mp4\"}\n");
+    }
+
+    #[test]
+    fn import_invalid_json() {
+        let error = import_mdast("{\"children\": []}").unwrap_err();
+
+        assert!(error.message.starts_with("Unable to read the mdast json"));
+    }
+}
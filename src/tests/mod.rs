@@ -1,2 +1,30 @@
+mod borrow;
+mod cst;
+mod directive;
+mod event;
+mod footnote;
+mod format;
+mod extension;
+mod extra;
+mod front_matter;
+mod github;
+#[cfg(test)]
+mod helper;
+mod html;
+mod json;
+mod math;
+mod mdast;
+mod options;
+mod plain;
+mod query;
+mod reader;
+mod render;
+mod sanitize;
+mod section;
+mod slug;
+mod terminal;
+mod toc;
 mod token;
+mod typography;
+mod visit;
 mod wrapper;
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod optionstest {
+    use std::borrow::Cow;
+    use crate::{
+        parse_code_markdown, parse_markdown_with_options, render_html, format_markdown, parse_events, visit_tokens_mut,
+        Event, FormatStyle, LinkMeta, ParserOptions, Tag, TableAlignment, VisitorMut
+    };
+    use crate::token::tag::TagOperator;
+
+    #[test]
+    fn build_options_with_presets() {
+        let options = ParserOptions::new().math(true).tables(true);
+        assert!(options.math);
+        assert!(options.tables);
+        assert!(options.strikethrough);
+        assert!(options.footnotes);
+
+        let commonmark = ParserOptions::commonmark();
+        assert!(!commonmark.tables);
+        assert!(!commonmark.strikethrough);
+        assert!(!commonmark.footnotes);
+
+        let gfm = ParserOptions::gfm();
+        assert!(gfm.tables);
+        assert!(gfm.strikethrough);
+        assert!(gfm.footnotes);
+        assert!(!gfm.math);
+    }
+
+    #[test]
+    fn disable_strikethrough_and_footnotes() {
+        let content = "
+            A ~~strike~~ and a note[^1]
+            [^1]: The note
+        ";
+
+        let html = render_html(content, &ParserOptions::commonmark()).unwrap();
+        assert!(!html.contains("<del>"));
+        assert!(html.contains("~~strike~~"));
+        assert!(!html.contains("footnote"));
+
+        let html = render_html(content, &ParserOptions::new()).unwrap();
+        assert!(html.contains("<del>strike</del>"));
+        assert!(html.contains("footnote"));
+    }
+
+    #[test]
+    fn strikethrough_disabled_keep_the_tildes() {
+        let content = "A ~~strike~~ **bold**";
+
+        let res = parse_markdown_with_options(content, &ParserOptions::commonmark()).unwrap();
+        let text = res.get(&0).unwrap().text.as_ref().unwrap();
+        assert_eq!(text.sanitize_content, "A ~~strike~~ bold");
+        assert!(text.metas.as_ref().unwrap().strike.is_none());
+
+        let res = parse_markdown_with_options(content, &ParserOptions::gfm()).unwrap();
+        let text = res.get(&0).unwrap().text.as_ref().unwrap();
+        assert_eq!(text.sanitize_content, "A strike bold");
+        assert_eq!(text.metas.as_ref().unwrap().strike.as_ref().unwrap()[0].word, "strike");
+    }
+
+    #[test]
+    fn rewritten_text_keep_the_tildes() {
+        struct Rewrite;
+
+        impl VisitorMut for Rewrite {
+            fn visit_link_mut(&mut self, _line: usize, link: &mut LinkMeta) {
+                link.url = Cow::Owned(format!("/v2{}", link.url));
+            }
+        }
+
+        let content = "~~old~~ [docs](/docs)";
+        let mut code_tokens = parse_code_markdown(content).unwrap();
+
+        let mut tokens = parse_markdown_with_options(content, &ParserOptions::commonmark()).unwrap();
+        visit_tokens_mut(&mut tokens, &mut code_tokens, &mut Rewrite, &ParserOptions::commonmark());
+        assert_eq!(tokens.get(&0).unwrap().text.as_ref().unwrap().sanitize_content, "~~old~~ [docs](/v2/docs)");
+
+        let mut tokens = parse_markdown_with_options(content, &ParserOptions::gfm()).unwrap();
+        visit_tokens_mut(&mut tokens, &mut code_tokens, &mut Rewrite, &ParserOptions::gfm());
+        assert_eq!(tokens.get(&0).unwrap().text.as_ref().unwrap().sanitize_content, "old [docs](/v2/docs)");
+    }
+
+    #[test]
+    fn footnotes_disabled_keep_the_definition_as_text() {
+        let content = "note[^1]\n[^1]: The note";
+        let res = parse_markdown_with_options(content, &ParserOptions::commonmark()).unwrap();
+
+        let reference = res.get(&0).unwrap().text.as_ref().unwrap();
+        assert!(reference.metas.as_ref().unwrap().footnotes.is_none());
+
+        let definition = res.get(&1).unwrap();
+        assert!(definition.tag.is_none());
+        assert_eq!(definition.text.as_ref().unwrap().content, "[^1]: The note");
+    }
+
+    #[test]
+    fn setters_override_the_presets() {
+        let content = "~~a~~\n| x |\n|---|";
+
+        let res = parse_markdown_with_options(content, &ParserOptions::commonmark().strikethrough(true)).unwrap();
+        assert!(res.get(&0).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap().strike.is_some());
+        assert!(res.get(&1).unwrap().tag.is_none());
+
+        let res = parse_markdown_with_options(content, &ParserOptions::gfm().tables(false).strikethrough(false)).unwrap();
+        assert!(res.get(&0).unwrap().text.as_ref().unwrap().metas.as_ref().unwrap().strike.is_none());
+        assert!(res.get(&1).unwrap().tag.is_none());
+        assert!(!ParserOptions::gfm().math);
+    }
+
+    #[test]
+    fn extra_fields_are_ignored_without_their_feature() {
+        // the struct literal builds whatever the enabled features
+        let options = ParserOptions { definition_list: true, abbreviation: true, ..ParserOptions::new() };
+        let res = parse_markdown_with_options("Term\n: definition", &options).unwrap();
+
+        let is_list = res
+            .get(&0)
+            .and_then(|token| token.tag.as_ref())
+            .is_some_and(|tag| tag.operator == TagOperator::DefinitionList);
+
+        assert_eq!(is_list, cfg!(feature = "definition_list"));
+    }
+
+    #[test]
+    fn parse_table() {
+        let content = "
+            | Name | Price | Stock |
+            | :--- | :---: | ----: |
+            | *Apple* | 1 | 10 |
+            | Pear \\| Plum | 2 |
+
+            After the table
+        ";
+
+        let res = parse_markdown_with_options(content, &ParserOptions::gfm()).unwrap();
+        let tag = res.get(&1).unwrap().tag.as_ref().unwrap();
+        assert_eq!(tag.operator, TagOperator::Table);
+
+        let table = tag.metas.as_ref().unwrap().table.as_ref().unwrap();
+        assert_eq!(table.alignments, vec![TableAlignment::Left, TableAlignment::Center, TableAlignment::Right]);
+        assert_eq!(table.header, vec!["Name", "Price", "Stock"]);
+        assert_eq!(table.rows[1], vec!["Pear | Plum", "2", ""]);
+
+        // the delimiter and the rows are part of the table
+        assert!(!res.contains_key(&2));
+        assert!(!res.contains_key(&4));
+        assert!(res.get(&6).unwrap().text.is_some());
+
+        let html = render_html(content, &ParserOptions::gfm()).unwrap();
+        assert!(html.contains("<th align=\"left\">Name</th>"));
+        assert!(html.contains("<td align=\"left\"><em>Apple</em></td>"));
+        assert!(html.contains("<td align=\"right\"></td>"));
+
+        let markdown = format_markdown(content, &ParserOptions::gfm(), &FormatStyle::default()).unwrap();
+        assert!(markdown.starts_with("| Name         | Price | Stock |\n| :----------- | :---: | ----: |\n"));
+        assert!(markdown.contains("| Pear \\| Plum | 2     |       |"));
+    }
+
+    #[test]
+    fn interrupt_table() {
+        let content = "
+            a | b
+            --|--
+            1 | 2
+            # Heading
+            | x |
+            |---|---|
+        ";
+
+        let options = ParserOptions::gfm();
+        let res = parse_markdown_with_options(content, &options).unwrap();
+        let table = res.get(&1).unwrap().tag.as_ref().unwrap().metas.as_ref().unwrap().table.as_ref().unwrap();
+        assert_eq!(table.rows.len(), 1);
+        assert_eq!(res.get(&4).unwrap().tag.as_ref().unwrap().operator, TagOperator::Heading);
+
+        // the header and the delimiter row must have the same number of cells
+        assert!(res.get(&5).unwrap().tag.is_none());
+
+        let events: Vec<Event> = parse_events(content, &options).collect();
+        assert!(events.contains(&Event::Start(Tag::Table(vec![TableAlignment::None, TableAlignment::None]))));
+        assert_eq!(events.iter().filter(|e| **e == Event::Start(Tag::TableCell)).count(), 4);
+    }
+
+    #[test]
+    fn expect_tables_to_be_opt_in() {
+        let content = "
+            | a | b |
+            |---|---|
+        ";
+
+        let res = parse_markdown_with_options(content, &ParserOptions::new()).unwrap();
+        assert!(res.get(&1).unwrap().tag.is_none());
+        assert!(res.contains_key(&2));
+    }
+}
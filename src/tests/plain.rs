@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod plaintest {
+    use crate::{render_plain_text, ParserOptions};
+
+    #[test]
+    fn render_document_as_plain_text() {
+        let content = "
+            # Guide **bold**
+
+            Read the [docs](https://example.com/docs) and ![logo](logo.png)
+            with <b>inline</b> html
+
+            -. first `item`
+            -. second
+            1. third
+            > quoted _text_
+            ```rust
+            let a = 1;
+            ```
+        ";
+
+        let res = render_plain_text(content, &ParserOptions::default(), None).unwrap();
+        let expected = "Guide bold\nRead the docs and logo with inline html\nfirst item\nsecond\nthird\nquoted text\nlet a = 1;";
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn keep_punctuation_which_isnt_markup() {
+        let content = "snake_case_name and 2 * 3 * 4 with a*b\n\nescaped \\*stars\\*";
+        let res = render_plain_text(content, &ParserOptions::default(), None).unwrap();
+
+        assert_eq!(res, "snake_case_name and 2 * 3 * 4 with a*b\nescaped *stars*");
+    }
+
+    #[test]
+    fn drop_html_blocks_and_footnote_refs() {
+        let content = "Text[^1]\n\n<div>\nblock\n</div>\n\n[^1]: The note";
+        let res = render_plain_text(content, &ParserOptions::default(), None).unwrap();
+
+        assert_eq!(res, "Text\nThe note");
+    }
+
+    #[test]
+    fn truncate_at_word_boundary() {
+        let options = ParserOptions::default();
+        let content = "The quick brown fox jumps";
+
+        assert_eq!(render_plain_text(content, &options, Some(14)).unwrap(), "The quick…");
+        assert_eq!(render_plain_text(content, &options, Some(16)).unwrap(), "The quick brown…");
+        assert_eq!(render_plain_text(content, &options, Some(25)).unwrap(), content);
+        assert_eq!(render_plain_text("Supercalifragilistic", &options, Some(6)).unwrap(), "Super…");
+        assert_eq!(render_plain_text("Ünïcödé wörds", &options, Some(9)).unwrap(), "Ünïcödé…");
+    }
+}
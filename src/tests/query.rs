@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod querytest {
+    use crate::{parse_document, HeadingLevel, Node, ParserOptions};
+    use crate::token::tag::TagOperator;
+
+    const CONTENT: &str = "# Project\n\nSee [docs](/docs) and [docs](/docs).\n\n## Install\n\n```sh\ncargo add project\n```\n\n### From source\n\n![build](/build.svg)\n\n```sh\nmake\n```\n\n## Usage\n\n-. Run [it](/run)\n";
+
+    #[test]
+    fn headings_and_links_with_spans() {
+        let doc = parse_document(CONTENT, &ParserOptions::default()).unwrap();
+
+        let headings: Vec<(HeadingLevel, String)> = doc.headings().map(|h| (h.level, h.title)).collect();
+        assert_eq!(headings, vec![
+            (HeadingLevel::H1, "Project".to_string()),
+            (HeadingLevel::H2, "Install".to_string()),
+            (HeadingLevel::H3, "From source".to_string()),
+            (HeadingLevel::H2, "Usage".to_string())
+        ]);
+
+        let links: Vec<_> = doc.links().collect();
+        assert_eq!(links.len(), 3);
+        assert_eq!(&CONTENT[links[0].span.clone()], "[docs](/docs)");
+        assert_eq!(&CONTENT[links[1].span.clone()], "[docs](/docs)");
+        assert_eq!(&CONTENT[links[2].span.clone()], "[it](/run)");
+        assert!(links[0].span.end <= links[1].span.start);
+
+        let level_two = doc.headings().filter(|h| h.level == HeadingLevel::H2).count();
+        assert_eq!(level_two, 2);
+    }
+
+    #[test]
+    fn code_blocks_and_images() {
+        let doc = parse_document(CONTENT, &ParserOptions::default()).unwrap();
+        let blocks: Vec<_> = doc.code_blocks().collect();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].info, "sh");
+        assert_eq!(blocks[0].code(), "cargo add project");
+        assert_eq!(&CONTENT[blocks[0].span.clone()], "```sh\ncargo add project\n```");
+        assert_eq!((blocks[1].start_line, blocks[1].end_line), (14, 16));
+
+        let image = doc.images().next().unwrap();
+        assert_eq!(image.url, "/build.svg");
+        assert_eq!(&CONTENT[image.span], "![build](/build.svg)");
+    }
+
+    #[test]
+    fn section_restrict_the_queries() {
+        let doc = parse_document(CONTENT, &ParserOptions::default()).unwrap();
+        let install = doc.section("Install").unwrap();
+
+        assert_eq!((install.start_line, install.end_line), (4, 17));
+        assert!(install.raw().starts_with("## Install"));
+        assert_eq!(install.code_blocks().count(), 2);
+        assert_eq!(install.code_blocks().next().unwrap().code(), "cargo add project");
+        assert_eq!(install.links().count(), 0);
+
+        let source = install.section("from-source").unwrap();
+        assert_eq!(source.code_blocks().next().unwrap().code(), "make");
+
+        let usage = doc.section("Usage").unwrap();
+        assert_eq!(usage.end_line, 20);
+        assert!(doc.section("Missing").is_none());
+    }
+
+    #[test]
+    fn find_nodes_with_a_predicate() {
+        let doc = parse_document(CONTENT, &ParserOptions::default()).unwrap();
+
+        let items: Vec<Node> = doc
+            .find(|node| matches!(node, Node::Tag { token, .. } if token.operator == TagOperator::UnorderedList))
+            .collect();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].line(), 20);
+        assert_eq!(&CONTENT[items[0].span()], "-. Run [it](/run)");
+
+        let first_after_install = doc
+            .find(|node| matches!(node, Node::CodeBlock(_)) && node.line() > 4)
+            .next()
+            .unwrap();
+
+        assert_eq!(first_after_install.line(), 6);
+    }
+
+    #[test]
+    fn links_of_the_tags_with_exact_spans() {
+        let content = "# See [home](/home)\n\n-. [docs](/docs)\n> [quote](/q)\n`[no](/no)` [a](</a b> \"T\") \\[x](/x) [![i](/i.png)](/l)\n";
+        let doc = parse_document(content, &ParserOptions::default()).unwrap();
+
+        let links: Vec<(&str, &str, &str)> = doc
+            .links()
+            .map(|link| (link.title, link.url, &content[link.span]))
+            .collect();
+
+        assert_eq!(links, vec![
+            ("home", "/home", "[home](/home)"),
+            ("docs", "/docs", "[docs](/docs)"),
+            ("quote", "/q", "[quote](/q)"),
+            ("a", "/a b", "[a](</a b> \"T\")"),
+            ("![i](/i.png)", "/l", "[![i](/i.png)](/l)")
+        ]);
+
+        let image = doc.images().next().unwrap();
+        assert_eq!((image.alt_text, image.url, image.line), ("i", "/i.png", 4));
+        assert_eq!(&content[image.span], "![i](/i.png)");
+    }
+
+    #[test]
+    fn links_of_the_table_cells() {
+        let options = ParserOptions {
+            tables: true,
+            ..Default::default()
+        };
+
+        let content = "| Name | Docs |\n|---|---|\n| baozi | [docs](/docs) ![logo](/logo.png) |\n\n[after](/after)\n";
+        let doc = parse_document(content, &options).unwrap();
+
+        let links: Vec<(&str, usize, &str)> = doc
+            .links()
+            .map(|link| (link.url, link.line, &content[link.span]))
+            .collect();
+
+        assert_eq!(links, vec![("/docs", 2, "[docs](/docs)"), ("/after", 4, "[after](/after)")]);
+
+        let image = doc.images().next().unwrap();
+        assert_eq!((image.url, image.line), ("/logo.png", 2));
+    }
+
+    #[test]
+    #[cfg(feature = "definition_list")]
+    fn links_of_the_definition_lists() {
+        let options = ParserOptions {
+            definition_list: true,
+            ..Default::default()
+        };
+
+        let content = "Term [t](/t)\n: def [x](/x)\n";
+        let doc = parse_document(content, &options).unwrap();
+        let links: Vec<(&str, usize)> = doc.links().map(|link| (link.url, link.line)).collect();
+
+        assert_eq!(links, vec![("/t", 0), ("/x", 1)]);
+    }
+}
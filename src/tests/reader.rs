@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod readertest {
+    use std::collections::BTreeMap;
+    use std::io::{self, BufReader, Cursor, Read};
+    use crate::{parse_code_markdown, parse_markdown_with_options, parse_reader, ParserOptions, ReaderToken};
+
+    // Reader failing once the content has been read
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disconnected"))
+        }
+    }
+
+    #[test]
+    fn reader_tokens_match_parse_markdown() {
+        let options = ParserOptions { math: true, ..Default::default() };
+        let content = "---\ntitle: log\n---\n# Title\r\n\n[^1]: note\n    more\n\n<div>\nhtml\n</div>\n\n```rust\nlet a = 1;\n```\n$$\nx\n$$\ntext[^1]\n";
+        let mut textual = BTreeMap::new();
+        let mut code = BTreeMap::new();
+
+        for token in parse_reader(Cursor::new(content), &options) {
+            match token.unwrap() {
+                (idx, ReaderToken::Textual(token)) => textual.insert(idx, format!("{:?}", token.tag.map(|t| t.content))),
+                (idx, ReaderToken::Code(token)) => code.insert(idx, token.content.to_string())
+            };
+        }
+
+        let parsed: BTreeMap<usize, String> = parse_markdown_with_options(&content.replace('\r', ""), &options)
+            .unwrap()
+            .into_iter()
+            .map(|(idx, token)| (idx, format!("{:?}", token.tag.map(|t| t.content))))
+            .collect();
+
+        let parsed_code: BTreeMap<usize, String> = parse_code_markdown(content)
+            .unwrap()
+            .into_iter()
+            .map(|(idx, token)| (idx, token.content.to_string()))
+            .collect();
+
+        assert_eq!(textual, parsed);
+        assert_eq!(code, parsed_code);
+    }
+
+    #[test]
+    fn tokens_are_returned_in_line_order() {
+        let options = ParserOptions::default();
+        let content = "text\n```\ncode\n```\n> quote\n";
+        let lines: Vec<usize> = parse_reader(Cursor::new(content), &options)
+            .map(|token| token.unwrap().0)
+            .collect();
+
+        // the fences are both a textual and a code token
+        assert_eq!(lines, vec![0, 1, 1, 2, 3, 3, 4]);
+    }
+
+    #[test]
+    fn closed_blocks_are_returned_before_the_end() {
+        let options = ParserOptions::default();
+        let reader = BufReader::new(Cursor::new("# Title\n\ntext\n").chain(FailingReader));
+        let mut tokens = parse_reader(reader, &options);
+
+        let (idx, token) = tokens.next().unwrap().unwrap();
+        assert_eq!(idx, 0);
+        assert!(matches!(token, ReaderToken::Textual(t) if t.tag.as_ref().unwrap().content == "Title"));
+
+        let err = tokens.by_ref().find_map(Result::err).unwrap();
+        assert!(err.message.contains("disconnected"));
+        assert!(tokens.next().is_none());
+    }
+
+    #[test]
+    fn read_error_keep_the_blocks_read() {
+        let options = ParserOptions::default();
+        let reader = BufReader::new(Cursor::new("---\n# Changelog\n-. entry\n").chain(FailingReader));
+        let tokens: Vec<Result<(usize, ReaderToken), _>> = parse_reader(reader, &options).collect();
+
+        // the front matter isn't closed, its lines are parsed before the error is returned
+        let lines: Vec<usize> = tokens.iter().filter_map(|token| token.as_ref().ok()).map(|(idx, _)| *idx).collect();
+        assert_eq!(lines, vec![0, 1, 2]);
+        assert!(matches!(&tokens[1], Ok((_, ReaderToken::Textual(t))) if t.tag.as_ref().unwrap().content == "Changelog"));
+        assert!(tokens.last().unwrap().as_ref().is_err_and(|err| err.message.contains("disconnected")));
+    }
+
+    #[test]
+    fn front_matter_lookahead_is_bounded() {
+        let options = ParserOptions::default();
+        let content = format!("---\n{}---\n# Title\n", "key: value\n".repeat(10_000));
+        let mut tokens = parse_reader(Cursor::new(content), &options);
+
+        // the head is parsed as markdown once too large, before its closing delimiter is read
+        let (idx, _) = tokens.next().unwrap().unwrap();
+        assert_eq!(idx, 0);
+        assert!(tokens.front_matter().is_none());
+        assert_eq!(tokens.filter(Result::is_ok).count(), 10_002);
+    }
+
+    #[test]
+    fn invalid_utf8_report_the_line() {
+        let options = ParserOptions::default();
+        let content: &[u8] = b"# Title\ntext\nbad \xff byte\n";
+        let err = parse_reader(content, &options)
+            .find_map(Result::err)
+            .unwrap();
+
+        assert!(err.message.starts_with("The markdown isn't valid UTF-8: line 3"));
+    }
+
+    #[test]
+    fn front_matter_is_skipped() {
+        let options = ParserOptions::default();
+        let mut tokens = parse_reader(Cursor::new("+++\ntitle = \"log\"\n+++\ntext\n"), &options);
+        let lines: Vec<usize> = tokens.by_ref().map(|token| token.unwrap().0).collect();
+
+        assert_eq!(lines, vec![3]);
+        assert_eq!(tokens.front_matter().unwrap().raw, "title = \"log\"\n");
+
+        let content: &[u8] = b"";
+        assert!(parse_reader(content, &options).next().unwrap().is_err());
+    }
+}
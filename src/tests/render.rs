@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod rendertest {
+    use crate::{parse_markdown, parse_code_markdown, render_html, render_tokens_html, sanitize_markdown, ParserOptions, SanitizePolicy};
+
+    #[test]
+    fn render_blocks_html() {
+        let content = "
+            # Title
+
+            A little *turtle* is walking
+            down the **road**
+
+            +. Baozi
+            +. Xiaolongbao
+            > A quote
+
+            ```rust
+            let a = 1 < 2;
+            ```
+        ";
+
+        let html = render_html(content, &ParserOptions::default()).unwrap();
+        assert_eq!(html, concat!(
+            "<h1 id=\"title\">Title</h1>\n",
+            "<p>A little <em>turtle</em> is walking\ndown the <strong>road</strong></p>\n",
+            "<ul>\n<li>Baozi</li>\n<li>Xiaolongbao</li>\n</ul>\n",
+            "<blockquote>\n<p>A quote</p>\n</blockquote>\n",
+            "<pre><code class=\"language-rust\">let a = 1 &lt; 2;\n</code></pre>\n"
+        ));
+    }
+
+    #[test]
+    fn render_inline_html() {
+        let content = "
+            A [link](https://baozi.com) ![img](cat.png) `a_b` snake_case_name <kbd>Ctrl</kbd>
+        ";
+
+        let html = render_html(content, &ParserOptions::default()).unwrap();
+        assert_eq!(
+            html,
+            "<p>A <a href=\"https://baozi.com\">link</a> <img src=\"cat.png\" alt=\"img\" /> <code>a_b</code> snake_case_name <kbd>Ctrl</kbd></p>\n"
+        );
+    }
+
+    #[test]
+    fn render_footnotes_html() {
+        let content = "
+            Baozi[^1]
+
+            [^1]: A steamed bun
+        ";
+
+        let html = render_html(content, &ParserOptions::default()).unwrap();
+        assert_eq!(html, concat!(
+            "<p>Baozi<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup></p>\n",
+            "<section class=\"footnotes\">\n<ol>\n<li id=\"fn-1\">\n",
+            "<p>A steamed bun <a href=\"#fnref-1\" class=\"footnote-backref\">↩</a></p>\n",
+            "</li>\n</ol>\n</section>\n"
+        ));
+    }
+
+    #[test]
+    fn render_sanitized_html() {
+        let content = "
+            <script>alert(1)</script>
+
+            [click](javascript:alert(1)) [out](https://baozi.com)
+        ";
+
+        let mut tokens = parse_markdown(content).unwrap();
+        let code_tokens = parse_code_markdown(content).unwrap();
+        sanitize_markdown(&mut tokens, &SanitizePolicy::default(), &ParserOptions::default());
+
+        let html = render_tokens_html(&tokens, &code_tokens, &ParserOptions::default());
+        assert_eq!(html, concat!(
+            "&lt;script>alert(1)&lt;/script>\n",
+            "<p><a href=\"\">click</a> <a href=\"https://baozi.com\" rel=\"nofollow noopener\">out</a></p>\n"
+        ));
+    }
+
+    #[test]
+    fn render_code_block_edges() {
+        // the block which isn't closed ends with the document
+        let content = "```rust ignore title=\"a.rs\"\nlet a = 1;\n```\n~~~\nnot closed";
+
+        let html = render_html(content, &ParserOptions::default()).unwrap();
+        assert_eq!(html, concat!(
+            "<pre><code class=\"language-rust\">let a = 1;\n</code></pre>\n",
+            "<pre><code>not closed\n</code></pre>\n"
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod sanitizetest {
+    use crate::{parse_markdown, parse_markdown_with_options, parse_code_markdown, render_tokens_html, sanitize_markdown, ParserOptions, SanitizePolicy};
+
+    #[test]
+    fn expect_disallowed_tags_to_be_escaped() {
+        let content = "
+            <script>alert(1)</script>
+
+            <iframe src=\"https://evil.com\"></iframe>
+
+            Hello <style>body{}</style> <kbd>Ctrl</kbd>
+        ";
+
+        let mut res = parse_markdown(content).unwrap();
+        sanitize_markdown(&mut res, &SanitizePolicy::default(), &ParserOptions::default());
+
+        assert_eq!(res.get(&1).unwrap().tag.as_ref().unwrap().content, "&lt;script>alert(1)&lt;/script>");
+        assert_eq!(res.get(&3).unwrap().tag.as_ref().unwrap().content, "&lt;iframe src=\"https://evil.com\">&lt;/iframe>");
+
+        let text = res.get(&5).unwrap().text.as_ref().unwrap();
+        let html = text.metas.as_ref().unwrap().html.as_ref().unwrap();
+        assert_eq!(text.content, "Hello &lt;style>body{}&lt;/style> <kbd>Ctrl</kbd>");
+        assert_eq!(html[0].word, "&lt;style>");
+        assert_eq!(html[2].word, "<kbd>");
+    }
+
+    #[test]
+    fn expect_unsafe_urls_to_be_removed() {
+        let content = "
+            [click](javascript:alert(1))
+            [click](JaVaScRiPt:alert(1))
+            [click](java\tscript:alert(1))
+            [click](&#106;avascript:alert(1))
+            ![img](data:text/html;base64,PHNjcmlwdD4=)
+            [safe](https://baozi.com)
+        ";
+
+        let mut res = parse_markdown(content).unwrap();
+        sanitize_markdown(&mut res, &SanitizePolicy::default(), &ParserOptions::default());
+
+        for idx in 1..5 {
+            let text = res.get(&idx).unwrap().text.as_ref().unwrap();
+            assert_eq!(text.metas.as_ref().unwrap().links.as_ref().unwrap()[0].url, "");
+            assert!(!text.content.to_lowercase().contains("alert"));
+        }
+
+        let image = res.get(&5).unwrap().text.as_ref().unwrap();
+        assert_eq!(image.metas.as_ref().unwrap().images.as_ref().unwrap()[0].url, "");
+
+        let safe = res.get(&6).unwrap().text.as_ref().unwrap();
+        let link = &safe.metas.as_ref().unwrap().links.as_ref().unwrap()[0];
+        assert_eq!(link.url, "https://baozi.com");
+        assert_eq!(link.rel.as_ref().unwrap(), "nofollow noopener");
+    }
+
+    #[test]
+    fn expect_html_attributes_to_be_sanitized() {
+        let content = "
+            <img src=x onerror=alert(1)> <a href=\"javascript:alert(1)\" onclick='steal()'>x</a>
+        ";
+
+        let mut res = parse_markdown(content).unwrap();
+        sanitize_markdown(&mut res, &SanitizePolicy::default(), &ParserOptions::default());
+
+        let block = res.get(&1).unwrap().text.as_ref().unwrap();
+        assert_eq!(block.content, "<img src=x> <a href=\"\">x</a>");
+    }
+
+    #[test]
+    fn expect_custom_policy() {
+        let content = "
+            ![img](data:image/png;base64,iVBORw0KGgo=) [link](https://baozi.com) <script>
+        ";
+
+        let policy = SanitizePolicy {
+            tag_filter: vec![],
+            unsafe_schemes: vec!["javascript".to_string()],
+            strip_event_handlers: false,
+            external_link_rel: None
+        };
+
+        let mut res = parse_markdown(content).unwrap();
+        sanitize_markdown(&mut res, &policy, &ParserOptions::default());
+
+        let text = res.get(&1).unwrap().text.as_ref().unwrap();
+        let metas = text.metas.as_ref().unwrap();
+        assert_eq!(metas.images.as_ref().unwrap()[0].url, "data:image/png;base64,iVBORw0KGgo=");
+        assert!(metas.links.as_ref().unwrap()[0].rel.is_none());
+        assert!(text.content.ends_with("<script>"));
+    }
+
+    #[test]
+    fn expect_quoted_bracket_to_not_end_the_tag() {
+        let content = "
+            <a title=\">\" href=\"javascript:alert(1)\">x</a> <b title='a>b' onclick=steal()>y</b>
+        ";
+
+        let mut res = parse_markdown(content).unwrap();
+        sanitize_markdown(&mut res, &SanitizePolicy::default(), &ParserOptions::default());
+
+        let block = res.get(&1).unwrap().text.as_ref().unwrap();
+        assert_eq!(block.content, "<a title=\">\" href=\"\">x</a> <b title='a>b'>y</b>");
+    }
+
+    #[test]
+    fn expect_non_ascii_attribute_names() {
+        let content = "x <b é>\n<b é\nx <a 日=1 onclick=steal()>y</a>";
+
+        let mut res = parse_markdown(content).unwrap();
+        sanitize_markdown(&mut res, &SanitizePolicy::default(), &ParserOptions::default());
+
+        let lines: Vec<&str> = res.values().map(|t| t.text.as_ref().unwrap().content.as_ref()).collect();
+        assert_eq!(lines, vec!["x <b é>", "<b é", "x <a 日=1>y</a>"]);
+    }
+
+    #[test]
+    fn expect_rendered_urls_to_be_safe() {
+        let content = "
+            [x](java&Tab;script:alert(1)) [y](&#x6A;ava&NewLine;script&colon;alert(1))
+
+            [x](<javascript:alert(1)>) ![img](<data:text/html,x> \"title\")
+
+            [ok](<https://baozi.com/a b>) [amp](https://baozi.com?a=1&amp;b=2)
+        ";
+
+        let mut tokens = parse_markdown(content).unwrap();
+        let code_tokens = parse_code_markdown(content).unwrap();
+        sanitize_markdown(&mut tokens, &SanitizePolicy::default(), &ParserOptions::default());
+
+        let html = render_tokens_html(&tokens, &code_tokens, &ParserOptions::default());
+        assert!(!html.to_lowercase().contains("script:"));
+        assert!(!html.contains("data:"));
+        assert_eq!(html.matches("href=\"\"").count(), 3);
+        assert!(html.contains("href=\"https://baozi.com/a\""));
+        assert!(html.contains("href=\"https://baozi.com?a=1&amp;b=2\""));
+    }
+
+    #[test]
+    fn sanitize_keep_the_inline_math() {
+        let options = ParserOptions {
+            math: true,
+            strikethrough: false,
+            ..Default::default()
+        };
+
+        let content = "Let $x_1$ be ~~kept~~ *here*";
+        let mut tokens = parse_markdown_with_options(content, &options).unwrap();
+        let expected = tokens[&0].text.as_ref().unwrap().sanitize_content.to_string();
+        sanitize_markdown(&mut tokens, &SanitizePolicy::default(), &options);
+
+        assert_eq!(tokens[&0].text.as_ref().unwrap().sanitize_content, expected);
+        assert!(expected.contains("x_1") && expected.contains("~~kept~~"));
+    }
+}
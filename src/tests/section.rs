@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod sectiontest {
+    use crate::{parse_markdown, split_sections, HeadingLevel};
+
+    #[test]
+    fn split_document_sections() {
+        let content = "Intro text\n\n# Guide\nWelcome\n## Install\n### Linux\napt install\n## Usage\nRun it\n# FAQ\n";
+        let tokens = parse_markdown(content).unwrap();
+        let sections = split_sections(content, &tokens, HeadingLevel::H3);
+
+        assert_eq!(sections.len(), 6);
+        assert!(sections[0].heading.is_none());
+        assert_eq!(sections[0].raw, "Intro text\n");
+
+        assert_eq!(sections[1].heading.as_deref(), Some("Guide"));
+        // the sections are flat, the guide ends before its first sub heading
+        assert_eq!(sections[1].path, vec!["Guide"]);
+        assert_eq!(sections[1].raw, "# Guide\nWelcome");
+
+        let linux = &sections[3];
+        assert_eq!(linux.path, vec!["Guide", "Install", "Linux"]);
+        assert_eq!(linux.level, Some(HeadingLevel::H3));
+        assert_eq!(linux.slug.as_deref(), Some("linux"));
+        assert_eq!((linux.start_line, linux.end_line), (5, 6));
+        assert_eq!(&content[linux.span.clone()], "### Linux\napt install");
+        assert!(linux.contains_line(6));
+
+        assert_eq!(sections[4].path, vec!["Guide", "Usage"]);
+        assert_eq!(sections[5].path, vec!["FAQ"]);
+        assert_eq!(sections[5].raw, "# FAQ");
+    }
+
+    #[test]
+    fn keep_deeper_headings_within_section() {
+        let content = "---\ntitle: Doc\n---\n## Install\n### Linux\napt install\n## Usage\n";
+        let tokens = parse_markdown(content).unwrap();
+        let sections = split_sections(content, &tokens, HeadingLevel::H2);
+
+        // the front matter isn't a section
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].path, vec!["Install"]);
+        assert_eq!(sections[0].raw, "## Install\n### Linux\napt install");
+        assert_eq!(sections[0].start_line, 3);
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod slugtest {
+    use std::sync::Arc;
+    use crate::{parse_markdown, parse_markdown_with_options, render_html, ParserOptions, Slugger, GitlabSlugger, MdBookSlugger};
+
+    fn get_slug(res: &std::collections::BTreeMap<usize, crate::token::TextualToken>, line: usize) -> String {
+        res.get(&line).unwrap().tag.as_ref().unwrap().metas.as_ref().unwrap().slug.clone().unwrap()
+    }
+
+    #[test]
+    fn parse_github_slugs() {
+        let content = "
+            # Hello, **World**!
+            ## Install `cargo` & [run](https://run.rs)
+            ## C++ -- the 2nd édition
+            ### Hello, World
+            # Hello World-1
+            # Hello World
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        assert_eq!(get_slug(&res, 1), "hello-world");
+        assert_eq!(get_slug(&res, 2), "install-cargo--run");
+        assert_eq!(get_slug(&res, 3), "c----the-2nd-édition");
+        assert_eq!(get_slug(&res, 4), "hello-world-1");
+        // an existing -1 suffix is skipped
+        assert_eq!(get_slug(&res, 5), "hello-world-1-1");
+        assert_eq!(get_slug(&res, 6), "hello-world-2");
+    }
+
+    #[test]
+    fn parse_custom_id() {
+        let content = "
+            ## Getting started {#start}
+            # Start
+            # Other {#start}
+        ";
+
+        let res = parse_markdown(content).unwrap();
+        let tag = res.get(&1).unwrap().tag.as_ref().unwrap();
+        assert_eq!(tag.content, "Getting started");
+        assert_eq!(tag.metas.as_ref().unwrap().id.as_deref(), Some("start"));
+        assert_eq!(format!("{:?}", tag.metas.as_ref().unwrap().heading_kind), "H2");
+        assert_eq!(get_slug(&res, 1), "start");
+
+        // the explicit ids are reserved for the generated slugs
+        assert_eq!(get_slug(&res, 2), "start-1");
+        assert_eq!(get_slug(&res, 3), "start");
+    }
+
+    #[test]
+    fn parse_slugs_with_other_slugger() {
+        let content = "
+            # C++ -- Guide
+            # C++ -- Guide
+        ";
+
+        let options = ParserOptions {
+            slugger: Some(Arc::new(GitlabSlugger)),
+            ..Default::default()
+        };
+
+        let res = parse_markdown_with_options(content, &options).unwrap();
+        assert_eq!(get_slug(&res, 1), "c-guide");
+        assert_eq!(get_slug(&res, 2), "c-guide-1");
+
+        assert_eq!(MdBookSlugger.slugify("Hello\tWorld!"), "hello-world");
+    }
+
+    #[test]
+    fn render_heading_anchor() {
+        let content = "
+            # Title {#main}
+            ## Sub title
+        ";
+
+        let html = render_html(content, &ParserOptions::default()).unwrap();
+        assert_eq!(html, "<h1 id=\"main\">Title</h1>\n<h2 id=\"sub-title\">Sub title</h2>\n");
+    }
+}
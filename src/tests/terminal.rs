@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod terminaltest {
+    use crate::{render_terminal, ParserOptions, TerminalOptions};
+    use crate::tests::helper::assert_golden;
+
+    const RELEASE_NOTES: &str = "# Release 1.2.0
+
+The **parser** now handles _emphasis_, ~~strike~~ and `code spans`. Read the [changelog](https://example.com/changelog) for the full list of changes.
+
+## Fixes
+
+-. Crash on empty input
+-. Wrong heading depth with a closing sequence
+
+1. Update the dependency
+2. Run the migration
+
+> Breaking changes are listed in the migration guide
+
+```rust
+let html = render_html(content, &options)?;
+```
+";
+
+    #[test]
+    fn render_release_notes_with_colour() {
+        let terminal = TerminalOptions { width: 40, ..Default::default() };
+        let res = render_terminal(RELEASE_NOTES, &ParserOptions::default(), &terminal).unwrap();
+
+        assert_golden("release_notes_colour.txt", &res);
+    }
+
+    #[test]
+    fn render_release_notes_without_colour() {
+        let terminal = TerminalOptions { width: 40, colour: false };
+        let res = render_terminal(RELEASE_NOTES, &ParserOptions::default(), &terminal).unwrap();
+
+        assert!(!res.contains('\x1b'));
+        assert_golden("release_notes_plain.txt", &res);
+    }
+
+    #[test]
+    fn render_osc8_hyperlink() {
+        let res = render_terminal("See [docs](https://example.com)", &ParserOptions::default(), &TerminalOptions::default()).unwrap();
+        assert_eq!(res, "See \x1b]8;;https://example.com\x1b\\\x1b[4mdocs\x1b[24m\x1b]8;;\x1b\\\n");
+    }
+
+    #[test]
+    fn strip_hostile_escape_sequences() {
+        let content = concat!(
+            "# Title\x1b]0;pwned\x07\n\n",
+            "Text\x1b[2J with `code\x1b[31m` and [link](https://a.com/\x1b]8;;https://evil.com\x07) \u{9b}31m\n\n",
+            "<div>\x1b[5m</div>\n\n",
+            "```sh\x1b[1m\necho \x1b]52;c;Y2xpcA==\x07\t!\n```\n"
+        );
+
+        for colour in [true, false] {
+            let terminal = TerminalOptions { colour, ..Default::default() };
+            let res = render_terminal(content, &ParserOptions::default(), &terminal).unwrap();
+
+            for sequence in ["\x1b]0;", "\x1b[2J", "\x1b[31m", "\x1b[5m", "\x1b[1m\n", "\x1b]52;", "\u{9b}", "\x07"] {
+                assert!(!res.contains(sequence), "{:?} in {:?}", sequence, res);
+            }
+
+            // the url holding an escape sequence isn't emitted
+            assert!(!res.contains("evil.com"));
+            assert!(res.contains("link"));
+        }
+
+        let plain = render_terminal(content, &ParserOptions::default(), &TerminalOptions { colour: false, ..Default::default() }).unwrap();
+        assert!(!plain.contains('\x1b'));
+    }
+
+    #[test]
+    fn wrap_without_counting_escape_sequences() {
+        let terminal = TerminalOptions { width: 11, ..Default::default() };
+        let res = render_terminal("**aaaaa** bbbbb ccccc", &ParserOptions::default(), &terminal).unwrap();
+
+        assert_eq!(res, "\x1b[1maaaaa\x1b[22m bbbbb\nccccc\n");
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod toctest {
+    use std::env;
+    use std::fs;
+    use std::sync::Arc;
+    use crate::{parse_markdown, table_of_contents, render_toc_markdown, render_toc_html, update_toc, update_toc_file, update_toc_with_options};
+    use crate::{GitlabSlugger, HeadingLevel, ParserOptions};
+
+    const CONTENT: &str = "
+            # Project
+            ## Install
+            ### Linux
+            ```sh
+            # not a heading
+            ```
+            ### **macOS**
+            #### Brew
+            ## Usage
+            ### Usage
+        ";
+
+    #[test]
+    fn build_table_of_contents() {
+        let tokens = parse_markdown(CONTENT).unwrap();
+        let toc = table_of_contents(&tokens, HeadingLevel::H2, HeadingLevel::H3);
+
+        assert_eq!(toc.len(), 2);
+        assert_eq!(toc[0].title, "Install");
+        assert_eq!(toc[0].slug, "install");
+        assert_eq!(toc[0].level, HeadingLevel::H2);
+        assert_eq!(toc[0].line, 2);
+
+        let children = &toc[0].children;
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].title, "Linux");
+        assert_eq!(children[1].title, "macOS");
+        assert_eq!(children[1].line, 7);
+        assert!(children[1].children.is_empty());
+
+        assert_eq!(toc[1].children[0].slug, "usage-1");
+    }
+
+    #[test]
+    fn render_table_of_contents() {
+        let tokens = parse_markdown(CONTENT).unwrap();
+        let toc = table_of_contents(&tokens, HeadingLevel::H2, HeadingLevel::H3);
+
+        assert_eq!(render_toc_markdown(&toc), concat!(
+            "- [Install](#install)\n",
+            "  - [Linux](#linux)\n",
+            "  - [macOS](#macos)\n",
+            "- [Usage](#usage)\n",
+            "  - [Usage](#usage-1)\n"
+        ));
+
+        let toc = table_of_contents(&tokens, HeadingLevel::H3, HeadingLevel::H4);
+        assert_eq!(render_toc_html(&toc[1..]), concat!(
+            "<nav class=\"toc\">\n<ul>\n",
+            "<li><a href=\"#macos\">macOS</a>\n<ul>\n<li><a href=\"#brew\">Brew</a></li>\n</ul>\n</li>\n",
+            "<li><a href=\"#usage-1\">Usage</a></li>\n",
+            "</ul>\n</nav>\n"
+        ));
+    }
+
+    #[test]
+    fn update_toc_region() {
+        let content = "# Project\n<!-- toc -->\n- [Old](#old)\n<!-- tocstop -->\n## Install\n## Usage\n";
+
+        let updated = update_toc(content, HeadingLevel::H2, HeadingLevel::H6).unwrap();
+        assert_eq!(updated, "# Project\n<!-- toc -->\n\n- [Install](#install)\n- [Usage](#usage)\n\n<!-- tocstop -->\n## Install\n## Usage\n");
+
+        // updating twice doesn't change the content
+        assert_eq!(update_toc(&updated, HeadingLevel::H2, HeadingLevel::H6).unwrap(), updated);
+        assert!(update_toc("# Project", HeadingLevel::H2, HeadingLevel::H6).is_err());
+    }
+
+    #[test]
+    fn update_toc_with_custom_slugger() {
+        let content = "<!-- toc -->\n<!-- tocstop -->\n## C++ -- guide\n## C++ -- guide\n";
+        let options = ParserOptions {
+            slugger: Some(Arc::new(GitlabSlugger)),
+            ..Default::default()
+        };
+
+        let updated = update_toc_with_options(content, HeadingLevel::H2, HeadingLevel::H6, &options).unwrap();
+        assert!(updated.contains("- [C++ -- guide](#c-guide)\n- [C++ -- guide](#c-guide-1)\n"));
+        assert!(update_toc(content, HeadingLevel::H2, HeadingLevel::H6).unwrap().contains("(#c----guide)"));
+    }
+
+    #[test]
+    fn update_toc_file_in_place() {
+        let path = env::temp_dir().join(format!("xiaomarkdwn-toc-{}.md", std::process::id()));
+        fs::write(&path, "# Project\n<!-- toc -->\n<!-- tocstop -->\n## Install\n").unwrap();
+
+        let options = ParserOptions::default();
+        assert!(update_toc_file(&path, HeadingLevel::H2, HeadingLevel::H6, &options).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Project\n<!-- toc -->\n\n- [Install](#install)\n\n<!-- tocstop -->\n## Install\n"
+        );
+
+        // an up to date file isn't written
+        assert!(!update_toc_file(&path, HeadingLevel::H2, HeadingLevel::H6, &options).unwrap());
+
+        fs::remove_file(&path).unwrap();
+        assert!(update_toc_file(&path, HeadingLevel::H2, HeadingLevel::H6, &options).is_err());
+    }
+}
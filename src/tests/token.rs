@@ -80,6 +80,58 @@ mod textual {
         assert_eq!(second.operator, TagOperator::OrderedList);
     }
 
+    #[test]
+    fn parse_markdown_heading_with_hash_in_title() {
+        let content = "
+            # C# tips
+            ## F# and C#
+        ";
+
+        let res = token::get_textual_tokens(content).unwrap();
+        let first = res.get(&1).unwrap().tag.as_ref().unwrap();
+        let second = res.get(&2).unwrap().tag.as_ref().unwrap();
+
+        assert_eq!(first.content, "C# tips");
+        assert_eq!(first.metas.as_ref().unwrap().heading_kind, heading::HeadingLevel::H1);
+        assert_eq!(second.content, "F# and C#");
+        assert_eq!(second.metas.as_ref().unwrap().heading_kind, heading::HeadingLevel::H2);
+    }
+
+    #[test]
+    fn parse_markdown_ordered_content() {
+        let content = "
+            1. 2024 results
+            2. 3 apples
+        ";
+
+        let res = token::get_textual_tokens(content).unwrap();
+        let first = res.get(&1).unwrap().tag.as_ref().unwrap();
+        let second = res.get(&2).unwrap().tag.as_ref().unwrap();
+
+        assert_eq!(first.content, "2024 results");
+        assert_eq!(second.content, "3 apples");
+    }
+
+    #[test]
+    fn parse_markdown_unordered_marker_within_text() {
+        let content = "
+            a -. b
+            x *. y
+        ";
+
+        let res = token::get_textual_tokens(content).unwrap();
+
+        assert!(res.get(&1).unwrap().tag.is_none());
+        assert!(res.get(&2).unwrap().tag.is_none());
+    }
+
+    #[test]
+    fn parse_markdown_multibyte_after_hash() {
+        let res = token::get_textual_tokens("#é").unwrap();
+
+        assert!(res.contains_key(&0));
+    }
+
     #[test]
     fn parse_markdown_unordered_text() {
         let content = "
@@ -331,4 +383,27 @@ mod codetest {
         let code_footer = res.get(&5).unwrap();
         assert_eq!(code_footer.operator, CodeOperator::BlockCodeEnd);
     }
+
+    #[test]
+    fn expect_closing_fence_to_match_opening() {
+        let content = "echo ```\n````md\n```\n~~~~\n    ````\n`````  \n~~~\nempty\n~~~\n```\n```";
+
+        let res = token::get_code_tokens(content).unwrap();
+        // a fence must start the line
+        assert!(!res.contains_key(&0));
+
+        assert_eq!(res.get(&1).unwrap().operator, CodeOperator::BlockCodeStart);
+        // shorter, other character, too indented
+        assert_eq!(res.get(&2).unwrap().operator, CodeOperator::BlockCodeContent);
+        assert_eq!(res.get(&3).unwrap().operator, CodeOperator::BlockCodeContent);
+        assert_eq!(res.get(&4).unwrap().operator, CodeOperator::BlockCodeContent);
+        assert_eq!(res.get(&5).unwrap().operator, CodeOperator::BlockCodeEnd);
+
+        assert_eq!(res.get(&6).unwrap().operator, CodeOperator::BlockCodeStart);
+        assert_eq!(res.get(&8).unwrap().operator, CodeOperator::BlockCodeEnd);
+
+        // an empty block
+        assert_eq!(res.get(&9).unwrap().operator, CodeOperator::BlockCodeStart);
+        assert_eq!(res.get(&10).unwrap().operator, CodeOperator::BlockCodeEnd);
+    }
 }
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod typographytest {
+    use crate::{render_html, render_plain_text, format_markdown, FormatStyle, ParserOptions};
+
+    fn smart_options() -> ParserOptions {
+        ParserOptions::new().smart_punctuation(true)
+    }
+
+    #[test]
+    fn replace_quotes() {
+        let content = "\"Hello\" she said, 'don't' (\"nested 'single'\") and 5 o'clock \"";
+
+        let text = render_plain_text(content, &smart_options(), None).unwrap();
+        assert_eq!(text, "“Hello” she said, ‘don’t’ (“nested ‘single’”) and 5 o’clock ”");
+    }
+
+    #[test]
+    fn replace_dashes_and_ellipsis() {
+        let content = "pages 1--2 --- wait... a - b ---- c ----- d ------- e";
+
+        let text = render_plain_text(content, &smart_options(), None).unwrap();
+        assert_eq!(text, "pages 1–2 — wait… a - b –– c —– d —–– e");
+    }
+
+    #[test]
+    fn replace_nested_and_adjacent_quotes() {
+        let content = "\"'x'\" \"a\"'s \"\" '' \"b\".";
+
+        let text = render_plain_text(content, &smart_options(), None).unwrap();
+        assert_eq!(text, "“‘x’” “a”’s “” ‘’ “b”.");
+    }
+
+    #[test]
+    fn replace_dots_and_hyphens_runs_only() {
+        let content = "-- start - x-- .. one.... two...";
+
+        let text = render_plain_text(content, &smart_options(), None).unwrap();
+        assert_eq!(text, "– start - x– .. one…. two…");
+    }
+
+    #[test]
+    fn keep_code_and_urls() {
+        let content = "Run `a -- \"b\"...` on [\"docs\"](https://example.com/a--b...) or https://example.com/it's--fine \\\"raw\\\"\n\n```\n\"code\" -- block...\n```";
+
+        let html = render_html(content, &smart_options()).unwrap();
+        assert_eq!(html, concat!(
+            "<p>Run <code>a -- &quot;b&quot;...</code> on <a href=\"https://example.com/a--b...\">“docs”</a> ",
+            "or https://example.com/it's--fine &quot;raw&quot;</p>\n",
+            "<pre><code>&quot;code&quot; -- block...\n</code></pre>\n"
+        ));
+    }
+
+    #[test]
+    fn keep_escapes_math_and_link_titles() {
+        let options = smart_options().math(true);
+        let content = "\\-- \\... [a](/a \"T--itle\") $a--b$";
+
+        let html = render_html(content, &options).unwrap();
+        assert_eq!(html, "<p>-- ... <a href=\"/a\" title=\"T--itle\">a</a> <span class=\"math math-inline\">a--b</span></p>\n");
+    }
+
+    #[test]
+    fn replace_quotes_around_emphasis() {
+        let content = "\"*quoted emphasis*\" and **\"strong\"**'s _snake_case_ -- \"x\"";
+
+        let html = render_html(content, &smart_options()).unwrap();
+        assert_eq!(html, "<p>“<em>quoted emphasis</em>” and <strong>“strong”</strong>’s <em>snake_case</em> – “x”</p>\n");
+    }
+
+    #[test]
+    fn expect_smart_punctuation_to_be_opt_in() {
+        let content = "# \"Quotes\" -- and...\n\nSome \"text\"\n";
+
+        let html = render_html(content, &ParserOptions::new()).unwrap();
+        assert_eq!(html, "<h1 id=\"quotes----and\">&quot;Quotes&quot; -- and...</h1>\n<p>Some &quot;text&quot;</p>\n");
+
+        // the formatter and the anchor keep the source punctuation
+        let markdown = format_markdown(content, &smart_options(), &FormatStyle::default()).unwrap();
+        assert_eq!(markdown, content);
+
+        let html = render_html(content, &smart_options()).unwrap();
+        assert_eq!(html, "<h1 id=\"quotes----and\">“Quotes” – and…</h1>\n<p>Some “text”</p>\n");
+    }
+}
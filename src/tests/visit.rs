@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod visittest {
+    use std::borrow::Cow;
+    use crate::{
+        fold_tokens, parse_code_markdown, parse_markdown, parse_markdown_with_options, render_tokens_html, visit_tokens, visit_tokens_mut,
+        walk_fold_textual, walk_text, CodeToken, Fold, HeadingLevel, ImageMeta, LinkMeta, ParserOptions,
+        TagToken, TextOption, TextToken, TextualToken, Visitor, VisitorMut
+    };
+
+    const CONTENT: &str = "# Title\n\nSee [docs](/docs) and ![logo](/logo.png) `inline`\n\n```rust\nlet a = 1;\n```\n\n## Usage\n";
+
+    #[derive(Default)]
+    struct Collector {
+        items: Vec<String>
+    }
+
+    impl Visitor for Collector {
+        fn visit_heading(&mut self, heading: &TagToken) {
+            self.items.push(format!("heading {}", heading.content));
+        }
+
+        fn visit_link(&mut self, line: usize, link: &LinkMeta) {
+            self.items.push(format!("link {} {}", line, link.url));
+        }
+
+        fn visit_inline_code(&mut self, _line: usize, code: &TextOption) {
+            self.items.push(format!("code {}", code.word));
+        }
+
+        fn visit_code_block(&mut self, block: &[&CodeToken]) {
+            self.items.push(format!("block {} {}", block[0].content, block.len()));
+        }
+    }
+
+    #[test]
+    fn visitor_walk_the_document_in_order() {
+        let tokens = parse_markdown(CONTENT).unwrap();
+        let code_tokens = parse_code_markdown(CONTENT).unwrap();
+        let mut collector = Collector::default();
+
+        visit_tokens(&tokens, &code_tokens, &mut collector);
+
+        assert_eq!(collector.items, vec![
+            "heading Title",
+            "link 2 /docs",
+            "code inline",
+            "block ```rust 3",
+            "heading Usage"
+        ]);
+    }
+
+    #[test]
+    fn overridden_method_stop_the_walk() {
+        struct SkipText(Collector, bool);
+
+        impl Visitor for SkipText {
+            fn visit_text(&mut self, text: &TextToken) {
+                if self.1 {
+                    walk_text(self, text);
+                }
+            }
+
+            fn visit_link(&mut self, line: usize, link: &LinkMeta) {
+                self.0.visit_link(line, link);
+            }
+        }
+
+        let tokens = parse_markdown(CONTENT).unwrap();
+        let code_tokens = parse_code_markdown(CONTENT).unwrap();
+
+        let mut skip = SkipText(Collector::default(), false);
+        visit_tokens(&tokens, &code_tokens, &mut skip);
+        assert!(skip.0.items.is_empty());
+
+        let mut walk = SkipText(Collector::default(), true);
+        visit_tokens(&tokens, &code_tokens, &mut walk);
+        assert_eq!(walk.0.items, vec!["link 2 /docs"]);
+    }
+
+    #[test]
+    fn visitor_mut_rewrite_links_and_shift_headings() {
+        struct Rewrite;
+
+        impl VisitorMut for Rewrite {
+            fn visit_heading_mut(&mut self, heading: &mut TagToken) {
+                if let Some(metas) = heading.metas.as_mut() {
+                    metas.heading_kind = match metas.heading_kind {
+                        HeadingLevel::H1 => HeadingLevel::H2,
+                        _ => HeadingLevel::H3
+                    };
+                }
+            }
+
+            fn visit_link_mut(&mut self, _line: usize, link: &mut LinkMeta) {
+                link.url = Cow::Owned(format!("https://example.com{}", link.url));
+            }
+
+            fn visit_code_block_mut(&mut self, block: &mut [&mut CodeToken]) {
+                block[1].content = Cow::Borrowed("let b = 2;");
+            }
+        }
+
+        let mut tokens = parse_markdown(CONTENT).unwrap();
+        let mut code_tokens = parse_code_markdown(CONTENT).unwrap();
+        visit_tokens_mut(&mut tokens, &mut code_tokens, &mut Rewrite, &ParserOptions::default());
+
+        let text = tokens[&2].text.as_ref().unwrap();
+        assert_eq!(text.content, "See [docs](https://example.com/docs) and ![logo](/logo.png) `inline`");
+
+        let html = render_tokens_html(&tokens, &code_tokens, &ParserOptions::default());
+        assert!(html.contains("<h2"));
+        assert!(html.contains("<h3"));
+        assert!(html.contains("href=\"https://example.com/docs\""));
+        assert!(html.contains("let b = 2;"));
+    }
+
+    #[test]
+    fn fold_drop_and_replace_tokens() {
+        struct Strip;
+
+        impl<'a> Fold<'a> for Strip {
+            fn fold_textual(&mut self, line: usize, token: TextualToken<'a>) -> Option<TextualToken<'a>> {
+                if token.tag.is_some() {
+                    return None;
+                }
+
+                Some(walk_fold_textual(self, line, token))
+            }
+
+            fn fold_image(&mut self, _line: usize, mut image: ImageMeta<'a>) -> ImageMeta<'a> {
+                image.url = Cow::Borrowed("/cdn/logo.png");
+                image
+            }
+
+            fn fold_code_block(&mut self, _block: Vec<CodeToken<'a>>) -> Vec<CodeToken<'a>> {
+                Vec::new()
+            }
+        }
+
+        let tokens = parse_markdown(CONTENT).unwrap();
+        let code_tokens = parse_code_markdown(CONTENT).unwrap();
+        let (tokens, code_tokens) = fold_tokens(tokens, code_tokens, &mut Strip, &ParserOptions::default());
+
+        assert!(code_tokens.is_empty());
+        assert!(tokens.values().all(|t| t.tag.is_none()));
+        assert!(!tokens.contains_key(&4));
+        assert!(tokens[&2].text.as_ref().unwrap().content.contains("![logo](/cdn/logo.png)"));
+    }
+
+    #[test]
+    fn links_of_the_tags_are_visited() {
+        struct Prefix;
+
+        impl VisitorMut for Prefix {
+            fn visit_link_mut(&mut self, _line: usize, link: &mut LinkMeta) {
+                link.url = Cow::Owned(format!("/v2{}", link.url));
+            }
+        }
+
+        let content = "# See [home](/home)\n\n-. [docs](/docs)\n> [quote](/q)\n";
+        let mut tokens = parse_markdown(content).unwrap();
+        let mut code_tokens = parse_code_markdown(content).unwrap();
+
+        let mut collector = Collector::default();
+        visit_tokens(&tokens, &code_tokens, &mut collector);
+        assert_eq!(collector.items, vec!["heading See [home](/home)", "link 0 /home", "link 2 /docs", "link 3 /q"]);
+
+        visit_tokens_mut(&mut tokens, &mut code_tokens, &mut Prefix, &ParserOptions::default());
+        let contents: Vec<&str> = tokens.values().filter_map(|t| t.tag.as_ref()).map(|t| t.content.as_ref()).collect();
+        assert_eq!(contents, vec!["See [home](/v2/home)", "[docs](/v2/docs)", "[quote](/v2/q)"]);
+    }
+
+    #[test]
+    fn urls_are_replaced_at_their_span() {
+        struct Host;
+
+        impl VisitorMut for Host {
+            fn visit_link_mut(&mut self, _line: usize, link: &mut LinkMeta) {
+                link.url = Cow::Owned(link.url.replace("/a", "https://a.com/a"));
+            }
+        }
+
+        let content = "`[x](/a)` [x](/a \"Title\") [y](</a b>) [x](/a)\n";
+        let mut tokens = parse_markdown(content).unwrap();
+        let mut code_tokens = parse_code_markdown(content).unwrap();
+        visit_tokens_mut(&mut tokens, &mut code_tokens, &mut Host, &ParserOptions::default());
+
+        // the link within the inline code isn't a link
+        let text = tokens[&0].text.as_ref().unwrap();
+        assert_eq!(text.content, "`[x](/a)` [x](https://a.com/a \"Title\") [y](<https://a.com/a b>) [x](https://a.com/a)");
+    }
+
+    struct Version;
+
+    impl VisitorMut for Version {
+        fn visit_link_mut(&mut self, _line: usize, link: &mut LinkMeta) {
+            link.url = Cow::Owned(format!("/v2{}", link.url));
+        }
+    }
+
+    #[test]
+    fn links_of_the_table_cells_are_visited() {
+        let options = ParserOptions {
+            tables: true,
+            ..Default::default()
+        };
+
+        let content = "| a | [b](/b) |\n|---|---|\n| [c](/b) | d |\n";
+        let mut tokens = parse_markdown_with_options(content, &options).unwrap();
+        let mut code_tokens = parse_code_markdown(content).unwrap();
+
+        let mut collector = Collector::default();
+        visit_tokens(&tokens, &code_tokens, &mut collector);
+        assert_eq!(collector.items, vec!["link 0 /b", "link 0 /b"]);
+
+        visit_tokens_mut(&mut tokens, &mut code_tokens, &mut Version, &options);
+        let table = tokens[&0].tag.as_ref().unwrap();
+        let meta = table.metas.as_ref().unwrap().table.as_ref().unwrap();
+        assert_eq!(table.content, "| a | [b](/v2/b) |\n|---|---|\n| [c](/v2/b) | d |");
+        assert_eq!(meta.header[1], "[b](/v2/b)");
+        assert_eq!(meta.rows[0][0], "[c](/v2/b)");
+    }
+
+    #[test]
+    #[cfg(feature = "definition_list")]
+    fn links_of_the_definition_lists_are_visited() {
+        let options = ParserOptions {
+            definition_list: true,
+            ..Default::default()
+        };
+
+        let content = "Term [t](/t)\n: def [x](/x)\n";
+        let mut tokens = parse_markdown_with_options(content, &options).unwrap();
+        let mut code_tokens = parse_code_markdown(content).unwrap();
+
+        let mut collector = Collector::default();
+        visit_tokens(&tokens, &code_tokens, &mut collector);
+        assert_eq!(collector.items, vec!["link 0 /t", "link 0 /x"]);
+
+        visit_tokens_mut(&mut tokens, &mut code_tokens, &mut Version, &options);
+        let list = tokens[&0].tag.as_ref().unwrap();
+        let item = &list.metas.as_ref().unwrap().definition_list.as_ref().unwrap().items[0];
+        assert_eq!(item.term, "Term [t](/v2/t)");
+        assert_eq!(item.definitions[0], "def [x](/v2/x)");
+    }
+
+    #[test]
+    fn rewritten_text_keeps_the_inline_math() {
+        struct Host;
+
+        impl VisitorMut for Host {
+            fn visit_link_mut(&mut self, _line: usize, link: &mut LinkMeta) {
+                link.url = Cow::Owned(format!("https://a.com{}", link.url));
+            }
+        }
+
+        impl<'a> Fold<'a> for Host {
+            fn fold_link(&mut self, _line: usize, mut link: LinkMeta<'a>) -> LinkMeta<'a> {
+                link.url = Cow::Owned(format!("https://a.com{}", link.url));
+                link
+            }
+        }
+
+        let options = ParserOptions {
+            math: true,
+            ..Default::default()
+        };
+
+        let content = "Let $x_1$ and $y_2$ see [docs](/docs)\n";
+        let mut tokens = parse_markdown_with_options(content, &options).unwrap();
+        let mut code_tokens = parse_code_markdown(content).unwrap();
+        let expected = tokens[&0].text.as_ref().unwrap().sanitize_content.replace("/docs", "https://a.com/docs");
+
+        let (folded, _) = fold_tokens(tokens.clone(), code_tokens.clone(), &mut Host, &options);
+        assert_eq!(folded[&0].text.as_ref().unwrap().sanitize_content, expected);
+
+        visit_tokens_mut(&mut tokens, &mut code_tokens, &mut Host, &options);
+        let text = tokens[&0].text.as_ref().unwrap();
+        assert_eq!(text.content, "Let $x_1$ and $y_2$ see [docs](https://a.com/docs)");
+        assert_eq!(text.sanitize_content, expected);
+        assert!(text.sanitize_content.contains("x_1"));
+    }
+}
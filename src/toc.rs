@@ -0,0 +1,276 @@
+use std::clone::Clone;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::error;
+use crate::options::ParserOptions;
+use crate::parser::heading::HeadingLevel;
+use crate::parser::inline::{get_inline_nodes, get_plain_text};
+use crate::render::html::escape_html;
+use crate::token::{self, TextualToken};
+use crate::token::tag::TagOperator;
+
+// Opening marker of a generated table of contents
+const TOC_START: &str = "<!-- toc -->";
+// Closing marker of a generated table of contents
+const TOC_END: &str = "<!-- tocstop -->";
+// Indentation of a nested entry of the markdown list
+const TOC_INDENT: &str = "  ";
+
+/// Toc Entry
+///
+/// # Description
+/// Heading of the table of contents with the headings nested under it
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TocEntry {
+    pub title: String,
+    pub slug: String,
+    pub level: HeadingLevel,
+    pub line: usize,
+    pub children: Vec<TocEntry>
+}
+
+/// Table Of Contents
+///
+/// # Description
+/// Build the tree of the headings between the min and the max level. A heading is nested under
+/// the closest preceding heading of a lower level. The options must be the ones used to parse the
+/// tokens so that the titles are read with the same syntaxes
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `min_level` HeadingLevel
+/// * `max_level` HeadingLevel
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Vec<TocEntry>
+pub fn table_of_contents(
+    tokens: &BTreeMap<usize, TextualToken>,
+    min_level: HeadingLevel,
+    max_level: HeadingLevel,
+    options: &ParserOptions
+) -> Vec<TocEntry> {
+    let mut roots: Vec<TocEntry> = Vec::new();
+    // Entries which may still receive children
+    let mut stack: Vec<TocEntry> = Vec::new();
+
+    for (idx, token) in tokens {
+        let tag = match token.tag.as_ref().filter(|t| t.operator == TagOperator::Heading) {
+            Some(tag) => tag,
+            None => continue
+        };
+
+        let metas = tag.metas.clone().unwrap_or_default();
+        if metas.heading_kind < min_level || metas.heading_kind > max_level {
+            continue;
+        }
+
+        let entry = TocEntry {
+            title: get_plain_text(&get_inline_nodes(&tag.content, options)),
+            slug: metas.slug.unwrap_or_default(),
+            level: metas.heading_kind,
+            line: *idx,
+            children: Vec::new()
+        };
+
+        while stack.last().is_some_and(|last| last.level >= entry.level) {
+            close_entry(&mut stack, &mut roots);
+        }
+
+        stack.push(entry);
+    }
+
+    while !stack.is_empty() {
+        close_entry(&mut stack, &mut roots);
+    }
+
+    roots
+}
+
+/// Render Toc Markdown
+///
+/// # Description
+/// Render the table of contents as a nested markdown list of links
+///
+/// # Arguments
+/// * `entries` &[TocEntry]
+///
+/// # Return
+/// String
+pub fn render_toc_markdown(entries: &[TocEntry]) -> String {
+    let mut markdown = String::new();
+    push_markdown_entries(&mut markdown, entries, 0);
+
+    markdown
+}
+
+/// Render Toc Html
+///
+/// # Description
+/// Render the table of contents as a html `<nav>`
+///
+/// # Arguments
+/// * `entries` &[TocEntry]
+///
+/// # Return
+/// String
+pub fn render_toc_html(entries: &[TocEntry]) -> String {
+    if entries.is_empty() {
+        return String::new();
+    }
+
+    format!("<nav class=\"toc\">\n{}</nav>\n", get_html_list(entries))
+}
+
+/// Update Toc
+///
+/// # Description
+/// Replace the content between the `<!-- toc -->` and the `<!-- tocstop -->` markers by the
+/// table of contents of the document
+///
+/// # Arguments
+/// * `content` &str
+/// * `min_level` HeadingLevel
+/// * `max_level` HeadingLevel
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<String, ParserError>
+pub fn update_toc(content: &str, min_level: HeadingLevel, max_level: HeadingLevel, options: &ParserOptions) -> Result<String, error::ParserError> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.iter().position(|l| l.trim() == TOC_START);
+    let end = start.and_then(|s| lines[s..].iter().position(|l| l.trim() == TOC_END).map(|e| s + e));
+
+    let (start, end) = match (start, end) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Err(error::ParserError{message: error::TOC_NOT_FOUND.to_string()})
+    };
+
+    let tokens = token::get_textual_tokens_with_options(content, options)?;
+    let toc = render_toc_markdown(&table_of_contents(&tokens, min_level, max_level, options));
+
+    let mut updated: Vec<&str> = lines[..=start].to_vec();
+    updated.push("");
+    updated.extend(toc.lines());
+    updated.push("");
+    updated.extend(&lines[end..]);
+
+    let mut updated = updated.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+
+    Ok(updated)
+}
+
+/// Update Toc File
+///
+/// # Description
+/// Regenerate the table of contents of a markdown file in place. The file is only written when
+/// its table of contents is outdated
+///
+/// # Arguments
+/// * `path` &Path
+/// * `min_level` HeadingLevel
+/// * `max_level` HeadingLevel
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<bool, ParserError> whether the file has been updated
+pub fn update_toc_file(path: &Path, min_level: HeadingLevel, max_level: HeadingLevel, options: &ParserOptions) -> Result<bool, error::ParserError> {
+    let content = fs::read_to_string(path).map_err(|err| error::ParserError {
+        message: format!("{}: {}, {}", error::READ_INPUT, path.display(), err)
+    })?;
+
+    let updated = update_toc(&content, min_level, max_level, options)?;
+    if updated == content {
+        return Ok(false);
+    }
+
+    fs::write(path, updated).map_err(|err| error::ParserError {
+        message: format!("{}: {}, {}", error::WRITE_OUTPUT, path.display(), err)
+    })?;
+
+    Ok(true)
+}
+
+/// Close Entry
+///
+/// # Description
+/// Pop the last entry of the stack and attach it to its parent or to the roots
+///
+/// # Arguments
+/// * `stack` &mut Vec<TocEntry>
+/// * `roots` &mut Vec<TocEntry>
+fn close_entry(stack: &mut Vec<TocEntry>, roots: &mut Vec<TocEntry>) {
+    if let Some(entry) = stack.pop() {
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(entry),
+            None => roots.push(entry)
+        }
+    }
+}
+
+/// Push Markdown Entries
+///
+/// # Description
+/// Append the entries and their children to the markdown list
+///
+/// # Arguments
+/// * `markdown` &mut String
+/// * `entries` &[TocEntry]
+/// * `depth` usize
+fn push_markdown_entries(markdown: &mut String, entries: &[TocEntry], depth: usize) {
+    for entry in entries {
+        markdown.push_str(&format!(
+            "{}- [{}](#{})\n",
+            TOC_INDENT.repeat(depth),
+            escape_markdown_label(&entry.title),
+            entry.slug
+        ));
+
+        push_markdown_entries(markdown, &entry.children, depth + 1);
+    }
+}
+
+/// Get Html List
+///
+/// # Description
+/// Render the entries and their children as nested html list
+///
+/// # Arguments
+/// * `entries` &[TocEntry]
+///
+/// # Return
+/// String
+fn get_html_list(entries: &[TocEntry]) -> String {
+    let mut html = String::from("<ul>\n");
+    for entry in entries {
+        html.push_str(&format!("<li><a href=\"#{}\">{}</a>", escape_html(&entry.slug), escape_html(&entry.title)));
+        if !entry.children.is_empty() {
+            html.push('\n');
+            html.push_str(&get_html_list(&entry.children));
+        }
+
+        html.push_str("</li>\n");
+    }
+
+    html.push_str("</ul>\n");
+    html
+}
+
+/// Escape Markdown Label
+///
+/// # Description
+/// Escape the brackets of a link label
+///
+/// # Arguments
+/// * `label` &str
+///
+/// # Return
+/// String
+fn escape_markdown_label(label: &str) -> String {
+    label.replace('[', "\\[").replace(']', "\\]")
+}
@@ -1,13 +1,113 @@
+use std::borrow::Cow;
 use std::default::Default;
 use std::collections::BTreeMap;
 use std::clone::Clone;
+use std::iter::Enumerate;
+use std::str::Lines;
 use crate::parser::code;
+use crate::parser::front_matter::{FrontMatter, get_front_matter};
 
 #[derive(Default, Debug, PartialEq, Clone)]
-pub struct CodeToken {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodeToken<'a> {
     pub line: usize,
-    pub content: String,
-    pub operator: code::CodeOperator
+    pub content: Cow<'a, str>,
+    pub operator: code::CodeOperator,
+    // Fence opening the block
+    pub fence: Option<code::CodeFence>
+}
+
+impl CodeToken<'_> {
+    /// Into Owned
+    ///
+    /// # Description
+    /// Copy the borrowed content in order to keep the token beyond the lifetime of the markdown
+    ///
+    /// # Return
+    /// CodeToken<'static>
+    pub fn into_owned(self) -> CodeToken<'static> {
+        CodeToken {
+            line: self.line,
+            content: Cow::Owned(self.content.into_owned()),
+            operator: self.operator,
+            fence: self.fence
+        }
+    }
+}
+
+/// Code Tokens
+///
+/// Iterator over the code tokens of a content, line after line
+pub struct CodeTokens<'a> {
+    lines: Enumerate<Lines<'a>>,
+    front_matter: Option<FrontMatter>,
+    previous_token: Option<CodeToken<'a>>
+}
+
+impl<'a> CodeTokens<'a> {
+    /// New
+    ///
+    /// # Description
+    /// Create the iterator. The lines are only read when iterating
+    ///
+    /// # Arguments
+    /// * `content` &'a str
+    ///
+    /// # Return
+    /// CodeTokens<'a>
+    pub fn new(content: &'a str) -> Self {
+        CodeTokens {
+            lines: content.lines().enumerate(),
+            front_matter: get_front_matter(content),
+            previous_token: None
+        }
+    }
+}
+
+impl<'a> Iterator for CodeTokens<'a> {
+    type Item = (usize, CodeToken<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (idx, line) in self.lines.by_ref() {
+            if self.front_matter.as_ref().is_some_and(|f| f.contains_line(idx)) {
+                continue;
+            }
+
+            if let Some(token) = get_code_token(idx, line, &self.previous_token) {
+                self.previous_token = Some(token.clone());
+                return Some((idx, token));
+            }
+        }
+
+        None
+    }
+}
+
+/// Get Code Token
+///
+/// # Description
+/// Get the code token of a line from the previous code token
+///
+/// # Arguments
+/// * `idx` usize
+/// * `line` &str
+/// * `previous_token` &Option<CodeToken>
+///
+/// # Return
+/// Option<CodeToken>
+pub fn get_code_token<'a>(idx: usize, line: &'a str, previous_token: &Option<CodeToken>) -> Option<CodeToken<'a>> {
+    let operator = code::get_block_code_operator(line, previous_token)?;
+    let fence = match operator {
+        code::CodeOperator::BlockCodeStart => code::get_fence(line),
+        _ => previous_token.as_ref().and_then(|token| token.fence)
+    };
+
+    Some(CodeToken {
+        line: idx,
+        content: Cow::Borrowed(line.trim()),
+        operator,
+        fence
+    })
 }
 
 /// Get Code Block Tokens
@@ -20,23 +120,6 @@ pub struct CodeToken {
 ///
 /// # Return
 /// Option<CodeToken>
-pub fn get_code_block_tokens(content: &str) -> BTreeMap<usize, CodeToken> {
-    let mut code_tokens: BTreeMap<usize, CodeToken> = BTreeMap::new();
-    let mut previous_token: Option<CodeToken> = None;
-
-    for (idx, line) in content.lines().enumerate() {
-        let operator = code::get_block_code_operator(line, &previous_token);
-        if let Some(op) = operator {
-            let token = CodeToken {
-                line: idx,
-                content: line.trim().to_string(),
-                operator: op
-            };
-
-            previous_token = Some(token.clone());
-            code_tokens.insert(idx, token);
-        }
-    }
-
-    code_tokens
-}
\ No newline at end of file
+pub fn get_code_block_tokens(content: &str) -> BTreeMap<usize, CodeToken<'_>> {
+    CodeTokens::new(content).collect()
+}
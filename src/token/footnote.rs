@@ -0,0 +1,240 @@
+use std::borrow::Cow;
+use std::clone::Clone;
+use std::collections::BTreeMap;
+use crate::parser::footnote::{FootnoteMeta, get_footnote_refs};
+use super::TextualToken;
+use super::tag::{TagToken, TagOperator};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteReference {
+    pub label: String,
+    pub number: usize,
+    pub line: usize,
+    pub col: Option<usize>
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Footnote {
+    pub label: String,
+    pub number: Option<usize>,
+    pub line: usize,
+    pub content: String,
+    pub paragraphs: Vec<String>
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Footnotes {
+    pub definitions: Vec<Footnote>,
+    pub references: Vec<FootnoteReference>
+}
+
+impl Footnotes {
+    /// Unused
+    ///
+    /// # Description
+    /// Return the footnote definitions which are never referenced
+    ///
+    /// # Return
+    /// Vec<&Footnote>
+    pub fn unused(&self) -> Vec<&Footnote> {
+        self.definitions
+            .iter()
+            .filter(|def| def.number.is_none())
+            .collect()
+    }
+
+    /// Undefined
+    ///
+    /// # Description
+    /// Return the footnote references which doesn't have any definition. Only the first
+    /// reference of a label is returned
+    ///
+    /// # Return
+    /// Vec<&FootnoteReference>
+    pub fn undefined(&self) -> Vec<&FootnoteReference> {
+        self.references
+            .iter()
+            .filter(|r| !self.definitions.iter().any(|def| def.label == r.label))
+            .fold(Vec::new(), |mut acc: Vec<&FootnoteReference>, r| {
+                if !acc.iter().any(|a| a.label == r.label) {
+                    acc.push(r);
+                }
+
+                acc
+            })
+    }
+}
+
+/// Append Footnote Line
+///
+/// # Description
+/// Append an indented continuation line to a footnote definition
+///
+/// # Arguments
+/// * `token` &mut TagToken
+/// * `line` &str
+/// * `new_paragraph` bool
+pub fn append_footnote_line(token: &mut TagToken, line: &str, new_paragraph: bool) {
+    let meta = match token.metas.as_mut().and_then(|m| m.footnote.as_mut()) {
+        Some(meta) => meta,
+        None => return
+    };
+
+    match meta.paragraphs.last_mut() {
+        Some(paragraph) if !new_paragraph && !paragraph.is_empty() => {
+            paragraph.push('\n');
+            paragraph.push_str(line.trim());
+        },
+        Some(paragraph) if paragraph.is_empty() => paragraph.push_str(line.trim()),
+        _ => meta.paragraphs.push(line.trim().to_string())
+    }
+
+    token.content = Cow::Owned(meta.paragraphs.join("\n\n"));
+}
+
+/// Set Footnote Numbers
+///
+/// # Description
+/// Number the footnotes in order of their first reference. Footnote references and
+/// definitions are updated with the computed number
+///
+/// # Arguments
+/// * `tokens` &mut BTreeMap<usize, TextualToken>
+pub fn set_footnote_numbers(tokens: &mut BTreeMap<usize, TextualToken>) {
+    let mut labels: Vec<String> = Vec::new();
+
+    for token in tokens.values_mut() {
+        let refs = token.text
+            .as_mut()
+            .and_then(|t| t.metas.as_mut())
+            .and_then(|m| m.footnotes.as_mut());
+
+        if let Some(refs) = refs {
+            for r in refs.iter_mut() {
+                r.number = Some(get_number(&mut labels, &r.label));
+            }
+        }
+
+        let tag_refs = token.tag
+            .iter()
+            .flat_map(|tag| tag.get_inline_contents())
+            .filter_map(get_footnote_refs)
+            .flatten();
+
+        for r in tag_refs {
+            get_number(&mut labels, &r.label);
+        }
+    }
+
+    for token in tokens.values_mut() {
+        if let Some(meta) = get_footnote_meta_mut(token) {
+            meta.number = labels
+                .iter()
+                .position(|l| *l == meta.label)
+                .map(|idx| idx + 1);
+        }
+    }
+}
+
+/// Get Footnotes
+///
+/// # Description
+/// List the footnotes of a document. Definitions are sorted by their number. Unused definitions
+/// are listed at the end in their order of appearance
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+///
+/// # Return
+/// Footnotes
+pub fn get_footnotes(tokens: &BTreeMap<usize, TextualToken>) -> Footnotes {
+    let mut footnotes = Footnotes::default();
+    let mut labels: Vec<String> = Vec::new();
+
+    for (line, token) in tokens {
+        let text_refs = token.text
+            .as_ref()
+            .and_then(|t| t.metas.as_ref())
+            .and_then(|m| m.footnotes.clone());
+
+        let tag_refs = token.tag
+            .iter()
+            .flat_map(|tag| tag.get_inline_contents())
+            .filter_map(get_footnote_refs);
+
+        for r in text_refs.into_iter().chain(tag_refs).flatten() {
+            footnotes.references.push(FootnoteReference {
+                number: get_number(&mut labels, &r.label),
+                label: r.label,
+                line: *line,
+                col: r.col
+            });
+        }
+
+        let meta = token.tag
+            .as_ref()
+            .filter(|t| t.operator == TagOperator::FootnoteDefinition)
+            .and_then(|t| t.metas.as_ref())
+            .and_then(|m| m.footnote.as_ref());
+
+        if let Some(meta) = meta {
+            // Only the first definition of a label is kept
+            if footnotes.definitions.iter().any(|def| def.label == meta.label) {
+                continue;
+            }
+
+            footnotes.definitions.push(Footnote {
+                label: meta.label.clone(),
+                number: meta.number,
+                line: *line,
+                content: meta.paragraphs.join("\n\n"),
+                paragraphs: meta.paragraphs.clone()
+            });
+        }
+    }
+
+    footnotes.definitions.sort_by_key(|def| def.number.unwrap_or(usize::MAX));
+
+    footnotes
+}
+
+/// Get Number
+///
+/// # Description
+/// Return the number of a footnote label. A new number is assigned if the label has never been seen
+///
+/// # Arguments
+/// * `labels` &mut Vec<String>
+/// * `label` &str
+///
+/// # Return
+/// usize
+fn get_number(labels: &mut Vec<String>, label: &str) -> usize {
+    if let Some(idx) = labels.iter().position(|l| l == label) {
+        return idx + 1;
+    }
+
+    labels.push(label.to_string());
+    labels.len()
+}
+
+/// Get Footnote Meta Mut
+///
+/// # Description
+/// Return the footnote meta of a footnote definition token
+///
+/// # Arguments
+/// * `token` &mut TextualToken
+///
+/// # Return
+/// Option<&mut FootnoteMeta>
+fn get_footnote_meta_mut<'t>(token: &'t mut TextualToken) -> Option<&'t mut FootnoteMeta> {
+    token.tag
+        .as_mut()
+        .filter(|t| t.operator == TagOperator::FootnoteDefinition)
+        .and_then(|t| t.metas.as_mut())
+        .and_then(|m| m.footnote.as_mut())
+}
@@ -1,18 +1,46 @@
 pub mod code;
+pub mod footnote;
 pub mod tag;
 pub mod text;
 
 use std::collections::btree_map::BTreeMap;
 use std::clone::Clone;
+use std::iter::Enumerate;
+use std::str::Lines;
 use text::{TextToken, get_text_tokens};
-use tag::{TagToken, get_tag_token};
-use code::{CodeToken};
+use tag::{TagToken, TagOperator, get_tag_token};
+use code::{CodeToken, get_code_token};
 use crate::error;
+use crate::parser::{abbreviation, definition_list, directive, extension, slug, table};
+use crate::parser::directive::DirectiveMeta;
+use crate::parser::extension::BlockContinuation;
+use crate::parser::code::CodeOperator;
+use crate::parser::front_matter::{FrontMatter, get_front_matter};
+use crate::parser::html::{self, HtmlBlockKind};
+use crate::parser::math::{self, MathDelimiter};
+use crate::options::ParserOptions;
 
 #[derive(Debug, Clone)]
-pub struct TextualToken {
-    pub text: Option<TextToken>,
-    pub tag: Option<TagToken>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextualToken<'a> {
+    pub text: Option<TextToken<'a>>,
+    pub tag: Option<TagToken<'a>>
+}
+
+impl TextualToken<'_> {
+    /// Into Owned
+    ///
+    /// # Description
+    /// Copy the borrowed parts of the token in order to keep it beyond the lifetime of the markdown
+    ///
+    /// # Return
+    /// TextualToken<'static>
+    pub fn into_owned(self) -> TextualToken<'static> {
+        TextualToken {
+            text: self.text.map(TextToken::into_owned),
+            tag: self.tag.map(TagToken::into_owned)
+        }
+    }
 }
 
 /// Get Textual Tokens
@@ -25,30 +53,435 @@ pub struct TextualToken {
 ///
 /// # Return
 /// Result<BTreeMap<usize, TextualToken>, ParserError>
-pub fn get_textual_tokens(content: &str) -> Result<BTreeMap<usize, TextualToken>, error::ParserError> {
+pub fn get_textual_tokens(content: &str) -> Result<BTreeMap<usize, TextualToken<'_>>, error::ParserError> {
+    get_textual_tokens_with_options(content, &ParserOptions::default())
+}
+
+/// Get Textual Tokens With Options
+///
+/// # Description
+/// Retrieve tokens related to the text styling and markdown tag with the syntaxes configured by the options
+///
+/// # Arguments
+/// * `content` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Result<BTreeMap<usize, TextualToken>, ParserError>
+pub fn get_textual_tokens_with_options<'a>(content: &'a str, options: &ParserOptions) -> Result<BTreeMap<usize, TextualToken<'a>>, error::ParserError> {
     if content.is_empty() {
         return Err(error::ParserError{message: error::EMPTY_CONTENT.to_string()});
     }
 
-    let mut tokens = BTreeMap::new();
+    let mut tokens: BTreeMap<usize, TextualToken> = TextualTokens::new(content, options).collect();
 
-    for (idx, line) in content.lines().enumerate() {
-        let tag_token = get_tag_token(line.trim());
-        if let Some(token) = tag_token {
-            tokens.insert(idx, TextualToken {
+    footnote::set_footnote_numbers(&mut tokens);
+    slug::set_heading_slugs(&mut tokens, options);
+    if options.has_abbreviation() {
+        abbreviation::set_abbreviations(&mut tokens);
+    }
+
+    Ok(tokens)
+}
+
+/// Textual Tokens
+///
+/// Iterator over the textual tokens of a content in the order of their lines. Only the tokens which
+/// can still be extended by the following lines (html block, display math, footnote definition,
+/// definition list) are held back, the document wide metas (footnote numbers, slugs, abbreviations)
+/// aren't set
+pub struct TextualTokens<'a, 'o> {
+    lines: Enumerate<Lines<'a>>,
+    front_matter: Option<FrontMatter>,
+    parser: LineParser<'a, 'o>
+}
+
+impl<'a, 'o> TextualTokens<'a, 'o> {
+    /// New
+    ///
+    /// # Description
+    /// Create the iterator. The lines are only read when iterating
+    ///
+    /// # Arguments
+    /// * `content` &'a str
+    /// * `options` &'o ParserOptions
+    ///
+    /// # Return
+    /// TextualTokens<'a, 'o>
+    pub fn new(content: &'a str, options: &'o ParserOptions) -> Self {
+        TextualTokens {
+            lines: content.lines().enumerate(),
+            front_matter: get_front_matter(content),
+            parser: LineParser::new(options)
+        }
+    }
+}
+
+impl<'a> Iterator for TextualTokens<'a, '_> {
+    type Item = (usize, TextualToken<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.parser.pop_ready() {
+                return Some(token);
+            }
+
+            if self.parser.done {
+                return None;
+            }
+
+            match self.lines.next() {
+                Some((idx, _)) if self.front_matter.as_ref().is_some_and(|f| f.contains_line(idx)) => {},
+                Some((idx, line)) => {
+                    let token = self.parser.parse_line(idx, line);
+                    self.parser.push_token(idx, token);
+                },
+                None => self.parser.done = true
+            }
+        }
+    }
+}
+
+/// Line Parser
+///
+/// State of the blocks spanning several lines. The lines are given one by one and the tokens are
+/// held until no following line can update them
+pub struct LineParser<'a, 'o> {
+    options: &'o ParserOptions,
+    // Last code token, used to detect the lines within a code block
+    code_token: Option<CodeToken<'static>>,
+    // Tokens which aren't returned yet
+    tokens: BTreeMap<usize, TextualToken<'a>>,
+    // Line index and indentation of the footnote definition being parsed
+    footnote_def: Option<(usize, usize)>,
+    // Line index, indentation and kind of the html block being parsed
+    html_block: Option<(usize, usize, HtmlBlockKind)>,
+    // Line index and delimiter of the display math being parsed
+    math_block: Option<(usize, MathDelimiter)>,
+    // Line index of the definition list, index and indentation of its last line
+    definition_list: Option<(usize, usize, usize)>,
+    // Line index of the table being parsed
+    table: Option<usize>,
+    // Line index of the custom block being parsed
+    custom_block: Option<usize>,
+    // Containers which aren't closed yet, the innermost last
+    directives: Vec<DirectiveMeta>,
+    previous_blank: bool,
+    // Whether every line has been given
+    pub done: bool
+}
+
+impl<'a, 'o> LineParser<'a, 'o> {
+    /// New
+    ///
+    /// # Description
+    /// Create a parser without any line
+    ///
+    /// # Arguments
+    /// * `options` &'o ParserOptions
+    ///
+    /// # Return
+    /// LineParser<'a, 'o>
+    pub fn new(options: &'o ParserOptions) -> Self {
+        LineParser {
+            options,
+            code_token: None,
+            tokens: BTreeMap::new(),
+            footnote_def: None,
+            html_block: None,
+            math_block: None,
+            definition_list: None,
+            table: None,
+            custom_block: None,
+            directives: Vec::new(),
+            previous_blank: false,
+            done: false
+        }
+    }
+
+    /// Parse Line
+    ///
+    /// # Description
+    /// Parse a line into a new token or append it to the block being parsed. The new token borrows
+    /// the line and has to be given back with `push_token`
+    ///
+    /// # Arguments
+    /// * `idx` usize
+    /// * `line` &str
+    ///
+    /// # Return
+    /// Option<TextualToken>
+    pub fn parse_line<'l>(&mut self, idx: usize, line: &'l str) -> Option<TextualToken<'l>> {
+        // Lines within a code block are only part of the code tokens
+        let code_token = get_code_token(idx, line, &self.code_token);
+        let code_operator = code_token.as_ref().map(|token| token.operator.clone());
+        if let Some(token) = code_token {
+            // the content of the code isn't kept
+            self.code_token = Some(CodeToken {
+                line: idx,
+                operator: token.operator,
+                fence: token.fence,
+                ..Default::default()
+            });
+        }
+
+        let tokens = &mut self.tokens;
+        let options = self.options;
+
+        // a code block ends the display math, the html block and the footnote definition, the fence
+        // isn't part of them
+        if code_operator == Some(CodeOperator::BlockCodeStart) {
+            self.math_block = None;
+            self.html_block = None;
+            self.footnote_def = None;
+        }
+
+        if let Some((block_idx, delimiter)) = &self.math_block {
+            let closed = tokens
+                .get_mut(block_idx)
+                .and_then(|t: &mut TextualToken| t.tag.as_mut())
+                .is_some_and(|block| math::append_display_math_line(block, line, delimiter));
+
+            if closed {
+                self.math_block = None;
+            }
+
+            return None;
+        }
+
+        if code_operator == Some(CodeOperator::BlockCodeContent) {
+            return None;
+        }
+
+        if let Some(block_idx) = self.custom_block {
+            // a code block ends the custom block
+            let continuation = match tokens.get_mut(&block_idx).and_then(|t: &mut TextualToken| t.tag.as_mut()) {
+                Some(block) if code_operator.is_none() => extension::append_custom_line(block, line),
+                _ => BlockContinuation::Stop
+            };
+
+            if continuation != BlockContinuation::Append {
+                self.custom_block = None;
+            }
+
+            if continuation != BlockContinuation::Stop {
+                return None;
+            }
+        }
+
+        if let Some((block_idx, block_indent, kind)) = &self.html_block {
+            if html::is_html_block_end(kind, line) && line.trim().is_empty() {
+                self.html_block = None;
+            } else {
+                if let Some(block) = tokens.get_mut(block_idx).and_then(|t: &mut TextualToken| t.tag.as_mut()) {
+                    html::append_html_line(block, line, *block_indent);
+                }
+
+                if html::is_html_block_end(kind, line) {
+                    self.html_block = None;
+                }
+
+                return None;
+            }
+        }
+
+        if let Some((def_idx, def_indent)) = self.footnote_def {
+            if !line.trim().is_empty() && get_indent(line) > def_indent {
+                if let Some(def) = tokens.get_mut(&def_idx).and_then(|t: &mut TextualToken| t.tag.as_mut()) {
+                    footnote::append_footnote_line(def, line, self.previous_blank);
+                }
+
+                self.previous_blank = false;
+                return None;
+            }
+
+            if !line.trim().is_empty() {
+                self.footnote_def = None;
+            }
+        }
+
+        if let Some(table_idx) = self.table {
+            if line.trim().is_empty() || code_operator.is_some() || is_block_start(line, options) {
+                self.table = None;
+            } else {
+                if let Some(table) = tokens.get_mut(&table_idx).and_then(|t: &mut TextualToken| t.tag.as_mut()) {
+                    table::append_table_row(table, line);
+                }
+
+                return None;
+            }
+        }
+
+        self.previous_blank = line.trim().is_empty();
+
+        if let Some(token) = extension::get_custom_block_token(idx, line, &options.block_parsers) {
+            self.custom_block = Some(idx);
+            return Some(TextualToken {
                 text: None,
                 tag: Some(token)
             });
-        } else {
-            let text_token = get_text_tokens(line.trim(), idx);
-            tokens.insert(idx, TextualToken {
-                text: text_token,
-                tag: None
+        }
+
+        if options.directives {
+            if let Some(token) = get_directive_token(&mut self.directives, line) {
+                return Some(TextualToken {
+                    text: None,
+                    tag: Some(token)
+                });
+            }
+        }
+
+        if let Some((token, delimiter, closed)) = get_display_math(line.trim(), options) {
+            if !closed {
+                self.math_block = Some((idx, delimiter));
+            }
+
+            return Some(TextualToken {
+                text: None,
+                tag: Some(token)
+            });
+        }
+
+        if let Some(kind) = html::get_html_block_kind(line.trim(), is_in_paragraph(tokens, idx)) {
+            if !html::is_html_block_end(&kind, line) {
+                self.html_block = Some((idx, get_indent(line), kind.clone()));
+            }
+
+            if options.drop_html {
+                return None;
+            }
+
+            return Some(TextualToken {
+                text: None,
+                tag: Some(html::get_html_block_token(line.trim(), kind))
             });
         }
+
+        if options.tables && parse_table_line(tokens, &mut self.table, idx, line) {
+            return None;
+        }
+
+        if options.has_definition_list() && parse_definition_line(tokens, &mut self.definition_list, idx, line) {
+            return None;
+        }
+
+        if options.has_abbreviation() {
+            if let Some(token) = abbreviation::get_abbreviation_definition(line.trim()) {
+                return Some(TextualToken {
+                    text: None,
+                    tag: Some(token)
+                });
+            }
+        }
+
+        let tag_token = get_tag_token(line.trim())
+            .filter(|t| options.footnotes || t.operator != TagOperator::FootnoteDefinition);
+
+        if let Some(token) = tag_token {
+            if token.operator == TagOperator::FootnoteDefinition {
+                self.footnote_def = Some((idx, get_indent(line)));
+            }
+
+            return Some(TextualToken {
+                text: None,
+                tag: Some(token)
+            });
+        }
+
+        Some(TextualToken {
+            text: get_text_tokens(line.trim(), idx, options),
+            tag: None
+        })
     }
 
-    Ok(tokens)
+    /// Push Token
+    ///
+    /// # Description
+    /// Store the token parsed from the line and stop tracking the definition list if the line
+    /// doesn't extend it
+    ///
+    /// # Arguments
+    /// * `idx` usize
+    /// * `token` Option<TextualToken>
+    pub fn push_token(&mut self, idx: usize, token: Option<TextualToken<'a>>) {
+        if let Some(token) = token {
+            self.tokens.insert(idx, token);
+        }
+
+        self.close_definition_list(idx);
+    }
+
+    /// Close Definition List
+    ///
+    /// # Description
+    /// Stop tracking the definition list once the line prevent it from being extended, a definition
+    /// list is only extended by a term or a definition separated by blank lines
+    ///
+    /// # Arguments
+    /// * `idx` usize
+    fn close_definition_list(&mut self, idx: usize) {
+        let last_idx = match self.definition_list {
+            Some((_, last_idx, _)) if last_idx < idx => last_idx,
+            _ => return
+        };
+
+        let is_blank = |line: usize| self.tokens
+            .get(&line)
+            .and_then(|t| t.text.as_ref())
+            .is_some_and(|t| t.content.is_empty());
+
+        // the line may be the term of a following definition
+        let is_term = self.tokens
+            .get(&idx)
+            .and_then(|t| t.text.as_ref())
+            .is_some_and(|t| !t.content.is_empty());
+
+        if !(last_idx + 1..idx).all(is_blank) || !(is_blank(idx) || is_term) {
+            self.definition_list = None;
+        }
+    }
+
+    /// First Line
+    ///
+    /// # Description
+    /// Return the line of the first token which hasn't been returned yet
+    ///
+    /// # Return
+    /// Option<usize>
+    pub fn first_line(&self) -> Option<usize> {
+        self.tokens.first_key_value().map(|(idx, _)| *idx)
+    }
+
+    /// Pop Ready
+    ///
+    /// # Description
+    /// Return the first token unless it can still be updated. The last token is always held back as the
+    /// next line may look at it
+    ///
+    /// # Return
+    /// Option<(usize, TextualToken)>
+    pub fn pop_ready(&mut self) -> Option<(usize, TextualToken<'a>)> {
+        if self.done {
+            return self.tokens.pop_first();
+        }
+
+        let first = *self.tokens.first_key_value()?.0;
+        let last = *self.tokens.last_key_value()?.0;
+        let open = [
+            self.footnote_def.map(|(idx, _)| idx),
+            self.html_block.as_ref().map(|(idx, _, _)| *idx),
+            self.math_block.as_ref().map(|(idx, _)| *idx),
+            self.definition_list.map(|(idx, _, _)| idx),
+            self.table,
+            self.custom_block
+        ];
+
+        if first == last || open.iter().flatten().any(|idx| *idx <= first) {
+            return None;
+        }
+
+        self.tokens.pop_first()
+    }
 }
 
 /// Get Code Tokens
@@ -61,10 +494,293 @@ pub fn get_textual_tokens(content: &str) -> Result<BTreeMap<usize, TextualToken>
 ///
 /// # Return
 /// Result<BTreeMap<usize, CodeToken>, ParseError> 
-pub fn get_code_tokens(content: &str) -> Result<BTreeMap<usize, CodeToken>, error::ParserError> {
+pub fn get_code_tokens(content: &str) -> Result<BTreeMap<usize, CodeToken<'_>>, error::ParserError> {
     if content.is_empty() {
         return Err(error::ParserError{message: error::EMPTY_CONTENT.to_string()});
     }
     
     Ok(code::get_code_block_tokens(content))
-}
\ No newline at end of file
+}
+
+/// Get Indent
+///
+/// # Description
+/// Return the number of leading whitespace of a line
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// usize
+fn get_indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Is In Paragraph
+///
+/// # Description
+/// Check whether the previous line is a non empty text line
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `idx` usize
+///
+/// # Return
+/// bool
+fn is_in_paragraph(tokens: &BTreeMap<usize, TextualToken>, idx: usize) -> bool {
+    if idx == 0 {
+        return false;
+    }
+
+    tokens
+        .get(&(idx - 1))
+        .and_then(|t| t.text.as_ref())
+        .is_some_and(|t| !t.content.is_empty())
+}
+
+/// Get Display Math
+///
+/// # Description
+/// Get the display math started by the line when the math are enabled
+///
+/// # Arguments
+/// * `line` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// Option<(TagToken, MathDelimiter, bool)>
+fn get_display_math<'a>(line: &'a str, options: &ParserOptions) -> Option<(TagToken<'a>, MathDelimiter, bool)> {
+    if !options.math {
+        return None;
+    }
+
+    math::get_display_math_start(line)
+}
+
+/// Parse Table Line
+///
+/// # Description
+/// Turn the previous line of text into the header of a table when the line is a delimiter row
+/// with as many cells as the header
+///
+/// # Arguments
+/// * `tokens` &mut BTreeMap<usize, TextualToken>
+/// * `table` &mut Option<usize>
+/// * `idx` usize
+/// * `line` &str
+///
+/// # Return
+/// bool whether the line start a table
+fn parse_table_line(tokens: &mut BTreeMap<usize, TextualToken>, table: &mut Option<usize>, idx: usize, line: &str) -> bool {
+    let alignments = match table::get_delimiter_row(line.trim()) {
+        Some(alignments) => alignments,
+        None => return false
+    };
+
+    let token = get_term(tokens, idx).and_then(|header| table::get_table_token(&header, line, alignments));
+    let token = match token {
+        Some(token) => token,
+        None => return false
+    };
+
+    tokens.insert(idx - 1, TextualToken {
+        text: None,
+        tag: Some(token)
+    });
+
+    *table = Some(idx - 1);
+    true
+}
+
+/// Is Block Start
+///
+/// # Description
+/// Check whether the line start a block which interrupt a table
+///
+/// # Arguments
+/// * `line` &str
+/// * `options` &ParserOptions
+///
+/// # Return
+/// bool
+fn is_block_start(line: &str, options: &ParserOptions) -> bool {
+    let line = line.trim();
+
+    get_tag_token(line).is_some()
+        || html::get_html_block_kind(line, false).is_some()
+        || get_display_math(line, options).is_some()
+        || (options.directives && is_directive(line))
+}
+
+/// Is Directive
+///
+/// # Description
+/// Check whether the line is a container fence or a leaf directive
+///
+/// # Arguments
+/// * `line` &str
+///
+/// # Return
+/// bool
+fn is_directive(line: &str) -> bool {
+    directive::get_container_end(line).is_some()
+        || directive::get_container_start(line).is_some()
+        || directive::get_leaf_directive(line).is_some()
+}
+
+/// Get Directive Token
+///
+/// # Description
+/// Get the token of a container fence or a leaf directive. A closing fence closes the innermost
+/// container when it has at least as many colons as its opening fence
+///
+/// # Arguments
+/// * `directives` &mut Vec<DirectiveMeta> the containers being parsed
+/// * `line` &str
+///
+/// # Return
+/// Option<TagToken>
+fn get_directive_token<'l>(directives: &mut Vec<DirectiveMeta>, line: &'l str) -> Option<TagToken<'l>> {
+    if let Some(fence) = directive::get_container_end(line) {
+        let container = directives.pop_if(|container| container.fence <= fence)?;
+        return Some(directive::get_directive_token(TagOperator::ContainerDirectiveEnd, container, ""));
+    }
+
+    if let Some((meta, label)) = directive::get_container_start(line) {
+        directives.push(meta.clone());
+        return Some(directive::get_directive_token(TagOperator::ContainerDirective, meta, label));
+    }
+
+    directive::get_leaf_directive(line)
+        .map(|(meta, label)| directive::get_directive_token(TagOperator::LeafDirective, meta, label))
+}
+
+/// Parse Definition Line
+///
+/// # Description
+/// Fold the line into a definition list. A `: definition` line turn the previous line of text
+/// into a term, and the following terms and definitions are appended to the same list as long as
+/// they're only separated by blank lines
+///
+/// # Arguments
+/// * `tokens` &mut BTreeMap<usize, TextualToken>
+/// * `list` &mut Option<(usize, usize, usize)>
+/// * `idx` usize
+/// * `line` &str
+///
+/// # Return
+/// bool whether the line belong to a definition list
+fn parse_definition_line(
+    tokens: &mut BTreeMap<usize, TextualToken>,
+    list: &mut Option<(usize, usize, usize)>,
+    idx: usize,
+    line: &str
+) -> bool {
+    let trimmed = line.trim();
+    let definition = definition_list::get_definition(trimmed);
+
+    if let Some((list_idx, last_idx, indent)) = *list {
+        let block = tokens.get_mut(&list_idx).and_then(|t: &mut TextualToken| t.tag.as_mut());
+
+        // indented continuation of the last definition
+        if definition.is_none() && !trimmed.is_empty() && last_idx + 1 == idx && get_indent(line) > indent {
+            if let Some(block) = block {
+                definition_list::append_definition_line(block, trimmed);
+            }
+
+            *list = Some((list_idx, idx, indent));
+            return true;
+        }
+    }
+
+    let definition = match definition {
+        Some(definition) => definition,
+        None => return false
+    };
+
+    if let Some((list_idx, last_idx, _)) = *list {
+        // other definition of the last term
+        if is_blank_between(tokens, last_idx, idx) {
+            if let Some(block) = tokens.get_mut(&list_idx).and_then(|t: &mut TextualToken| t.tag.as_mut()) {
+                definition_list::append_definition(block, definition);
+            }
+
+            *list = Some((list_idx, idx, get_indent(line)));
+            return true;
+        }
+    }
+
+    let term = match get_term(tokens, idx) {
+        Some(term) => term,
+        None => return false
+    };
+
+    if let Some((list_idx, last_idx, _)) = *list {
+        // new term of the list
+        if is_blank_between(tokens, last_idx, idx - 1) {
+            tokens.remove(&(idx - 1));
+            if let Some(block) = tokens.get_mut(&list_idx).and_then(|t: &mut TextualToken| t.tag.as_mut()) {
+                definition_list::append_term(block, &term, definition);
+            }
+
+            *list = Some((list_idx, idx, get_indent(line)));
+            return true;
+        }
+    }
+
+    tokens.insert(idx - 1, TextualToken {
+        text: None,
+        tag: Some(definition_list::get_definition_list_token(&term, definition))
+    });
+
+    *list = Some((idx - 1, idx, get_indent(line)));
+    true
+}
+
+/// Get Term
+///
+/// # Description
+/// Get the content of the line of text preceding the index
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `idx` usize
+///
+/// # Return
+/// Option<String>
+fn get_term(tokens: &BTreeMap<usize, TextualToken>, idx: usize) -> Option<String> {
+    if idx == 0 {
+        return None;
+    }
+
+    tokens
+        .get(&(idx - 1))
+        .and_then(|t| t.text.as_ref())
+        .filter(|t| !t.content.is_empty())
+        .map(|t| t.content.to_string())
+}
+
+/// Is Blank Between
+///
+/// # Description
+/// Check that the lines between the two indexes (excluded) are blank lines
+///
+/// # Arguments
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `from` usize
+/// * `to` usize
+///
+/// # Return
+/// bool
+fn is_blank_between(tokens: &BTreeMap<usize, TextualToken>, from: usize, to: usize) -> bool {
+    if to <= from {
+        return false;
+    }
+
+    (from + 1..to).all(|line| {
+        tokens
+            .get(&line)
+            .and_then(|t| t.text.as_ref())
+            .is_some_and(|t| t.content.is_empty())
+    })
+}
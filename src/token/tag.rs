@@ -1,34 +1,128 @@
+use std::borrow::Cow;
 use std::default::Default;
 use std::clone::Clone;
-use crate::parser::{list, heading};
+use crate::parser::{abbreviation, definition_list, directive, list, heading, footnote, html, table};
+use crate::parser::extension::CustomNode;
 use crate::parser::operator::bytes;
 
 // Minimum character length
 const MIN_CHAR_LENGTH: usize = 2;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum TagOperator {
+    #[default]
     Heading,
     UnorderedList,
     OrderedList,
-    BlockQuote
-}
-
-impl Default for TagOperator {
-    fn default() -> Self { TagOperator::Heading }
+    BlockQuote,
+    FootnoteDefinition,
+    HtmlBlock,
+    DisplayMath,
+    DefinitionList,
+    AbbreviationDefinition,
+    Table,
+    // Opening fence of a :::container, the blocks until the closing fence are within the container
+    ContainerDirective,
+    // Closing fence of a container, the metas are the ones of the container
+    ContainerDirectiveEnd,
+    LeafDirective,
+    // Block parsed by a BlockParserExt
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+    Custom(Box<dyn CustomNode>)
 }
 
 #[derive(Debug, Default, Clone)]
-pub struct TagToken { 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TagToken<'a> {
     pub line: usize,
-    pub content: String,
+    pub content: Cow<'a, str>,
     pub operator: TagOperator,
     pub metas: Option<TagMeta>
 }
 
-#[derive(Debug, Clone)]
+impl TagToken<'_> {
+    /// Into Owned
+    ///
+    /// # Description
+    /// Copy the borrowed content in order to keep the token beyond the lifetime of the markdown
+    ///
+    /// # Return
+    /// TagToken<'static>
+    pub fn into_owned(self) -> TagToken<'static> {
+        TagToken {
+            line: self.line,
+            content: Cow::Owned(self.content.into_owned()),
+            operator: self.operator,
+            metas: self.metas
+        }
+    }
+
+    /// Has Inline Content
+    ///
+    /// # Description
+    /// Whether the content of the tag is inline markdown i.e a heading, a list item, a block quote or
+    /// a footnote definition. The content of the other tags isn't parsed for links, styles...
+    ///
+    /// # Return
+    /// bool
+    pub(crate) fn has_inline_content(&self) -> bool {
+        matches!(
+            self.operator,
+            TagOperator::Heading
+                | TagOperator::UnorderedList
+                | TagOperator::OrderedList
+                | TagOperator::BlockQuote
+                | TagOperator::FootnoteDefinition
+        )
+    }
+
+    /// Get Inline Contents
+    ///
+    /// # Description
+    /// Get the inline markdown of the tag: the content of a tag with inline content, the cells of a
+    /// table or the terms and the definitions of a definition list
+    ///
+    /// # Return
+    /// Vec<&str>
+    pub(crate) fn get_inline_contents(&self) -> Vec<&str> {
+        if self.has_inline_content() {
+            return vec![&self.content];
+        }
+
+        let metas = match &self.metas {
+            Some(metas) => metas,
+            None => return Vec::new()
+        };
+
+        let cells = metas.table
+            .iter()
+            .flat_map(|table| table.header.iter().chain(table.rows.iter().flatten()));
+
+        let items = metas.definition_list
+            .iter()
+            .flat_map(|list| list.items.iter())
+            .flat_map(|item| std::iter::once(&item.term).chain(item.definitions.iter()));
+
+        cells.chain(items).map(String::as_str).collect()
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TagMeta {
-    pub heading_kind: heading::HeadingLevel
+    pub heading_kind: heading::HeadingLevel,
+    pub footnote: Option<footnote::FootnoteMeta>,
+    pub html_kind: Option<html::HtmlBlockKind>,
+    pub definition_list: Option<definition_list::DefinitionListMeta>,
+    pub abbreviation: Option<abbreviation::AbbreviationMeta>,
+    pub table: Option<table::TableMeta>,
+    pub directive: Option<directive::DirectiveMeta>,
+    // Explicit {#custom-id} of a heading
+    pub id: Option<String>,
+    // Anchor of a heading, unique within the document
+    pub slug: Option<String>
 }
 
 /// Get Tag Token
@@ -41,12 +135,17 @@ pub struct TagMeta {
 ///
 /// # Return
 /// Option<TagToken>
-pub fn get_tag_token(line: &str) -> Option<TagToken> {
+pub fn get_tag_token(line: &str) -> Option<TagToken<'_>> {
     let list = list::get_any_list(line);
     if list.is_some() {
         return list;
     }
 
+    let footnote = footnote::get_footnote_definition(line);
+    if footnote.is_some() {
+        return footnote;
+    }
+
     if line.len() > MIN_CHAR_LENGTH {
         return match_single_indice(line);
     }
@@ -64,24 +163,27 @@ pub fn get_tag_token(line: &str) -> Option<TagToken> {
 ///
 /// # Return
 /// TagToken
-fn match_single_indice(content: &str) -> Option<TagToken> {
+fn match_single_indice(content: &str) -> Option<TagToken<'_>> {
     let trimmed_content = bytes::trim_matches_content(content);
-    // Getting the first character of the string
-    let start_chars = content.split_at(MIN_CHAR_LENGTH);
+    // Getting the first bytes of the string, a multibyte character may be split
+    let start_chars = content.as_bytes().split_at(MIN_CHAR_LENGTH);
     
     // match each byte of the byte array
     // See: https://www.reddit.com/r/rust/comments/f4usb4/pattern_matching_on_string_content_as_chars/fhtwe1f?utm_source=share&utm_medium=web2x
     // See: https://doc.rust-lang.org/edition-guide/rust-2018/slice-patterns.html
-    match start_chars.0.as_bytes() {
+    match start_chars.0 {
         // #
         [bytes::HEADING, ..] => {
+            let (content, id) = heading::get_custom_id(content);
             Some(
                 TagToken {
                     operator: TagOperator::Heading,
-                    content: trimmed_content,
+                    content: Cow::Borrowed(heading::get_heading_content(content)),
                     metas: Some(
                         TagMeta {
-                            heading_kind: heading::get_heading_depth(content)
+                            heading_kind: heading::get_heading_depth(content),
+                            id,
+                            ..Default::default()
                         }
                     ),
                     ..Default::default()
@@ -92,7 +194,7 @@ fn match_single_indice(content: &str) -> Option<TagToken> {
         [bytes::BLOCKQUOTE, ..] => Some(
             TagToken {
                 operator: TagOperator::BlockQuote,
-                content: trimmed_content,
+                content: Cow::Borrowed(trimmed_content),
                 ..Default::default()
             }
         ),
@@ -1,39 +1,108 @@
 use std::clone::Clone;
+use std::borrow::Cow;
 use crate::parser::text_style::{TextMetas, get_text_metas, sanitze_content};
-use crate::parser::code;
+use crate::parser::{code, html, math};
+use crate::options::ParserOptions;
 
 #[derive(Debug, Clone)]
-pub struct TextToken { 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextToken<'a> {
     pub line: usize,
-    pub content: String,
-    pub sanitize_content: String,
-    pub metas: Option<TextMetas>
+    pub content: Cow<'a, str>,
+    pub sanitize_content: Cow<'a, str>,
+    pub metas: Option<TextMetas<'a>>
+}
+
+impl TextToken<'_> {
+    /// Into Owned
+    ///
+    /// # Description
+    /// Copy the borrowed content and metas in order to keep the token beyond the lifetime of the markdown
+    ///
+    /// # Return
+    /// TextToken<'static>
+    pub fn into_owned(self) -> TextToken<'static> {
+        TextToken {
+            line: self.line,
+            content: Cow::Owned(self.content.into_owned()),
+            sanitize_content: Cow::Owned(self.sanitize_content.into_owned()),
+            metas: self.metas.map(TextMetas::into_owned)
+        }
+    }
+
+    /// Resanitize
+    ///
+    /// # Description
+    /// Sanitize the content again once it has been rewritten. The inline math are kept as is like
+    /// when the token is parsed
+    ///
+    /// # Arguments
+    /// * `options` &ParserOptions
+    pub(crate) fn resanitize(&mut self, options: &ParserOptions) {
+        let maths = if options.math {
+            math::get_inline_math(&self.content)
+        } else {
+            None
+        };
+
+        self.sanitize_content = Cow::Owned(match maths {
+            Some(maths) => math::sanitize_math_content(&self.content, &maths, options),
+            None => sanitze_content(&self.content, options).into_owned()
+        });
+    }
 }
 
 /// Get Text Tokens
 ///
 /// # Description
-/// Retrieve the text token
+/// Retrieve the text token. The token borrows the content unless the inline html is dropped
 ///
 /// # Argument
 /// * `content` &str
 /// * `line` usize
+/// * `options` &ParserOptions
 ///
 /// # Return
 /// Option<TextToken>
-pub fn get_text_tokens(content: &str, line: usize) -> Option<TextToken> {
+pub fn get_text_tokens<'a>(content: &'a str, line: usize, options: &ParserOptions) -> Option<TextToken<'a>> {
     if code::is_code(content) {
         return None;
     }
 
-    let metas = get_text_metas(content);
-
-    Some(
-        TextToken {
-            line,
-            content: String::from(content.trim()),
-            sanitize_content: sanitze_content(content),
-            metas
+    if options.drop_html {
+        let stripped = html::strip_inline_html(content);
+        if stripped != content {
+            return Some(get_text_token(&stripped, line, options).into_owned());
         }
-    )
+    }
+
+    Some(get_text_token(content, line, options))
+}
+
+/// Get Text Token
+///
+/// # Description
+/// Build the text token of a content
+///
+/// # Argument
+/// * `content` &str
+/// * `line` usize
+/// * `options` &ParserOptions
+///
+/// # Return
+/// TextToken
+fn get_text_token<'a>(content: &'a str, line: usize, options: &ParserOptions) -> TextToken<'a> {
+    let metas = get_text_metas(content, options);
+    let maths = metas.as_ref().and_then(|m| m.inline_math.as_ref());
+    let sanitize_content = match maths {
+        Some(maths) => Cow::Owned(math::sanitize_math_content(content, maths, options)),
+        None => sanitze_content(content, options)
+    };
+
+    TextToken {
+        line,
+        content: Cow::Borrowed(content.trim()),
+        sanitize_content,
+        metas
+    }
 }
@@ -0,0 +1,935 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::RangeInclusive;
+use crate::options::ParserOptions;
+use crate::parser::code::CodeOperator;
+use crate::parser::external::{ImageMeta, LinkMeta};
+use crate::parser::inline::match_link;
+use crate::parser::text_style::{TextMetas, TextOption, get_text_metas, mask_code_spans};
+use crate::token::TextualToken;
+use crate::token::code::CodeToken;
+use crate::token::tag::{TagToken, TagOperator};
+use crate::token::text::TextToken;
+
+/// Visitor
+///
+/// # Description
+/// Walk the tokens of a document in the order of their lines. Every method walks the children of
+/// the token by default, an overridden method calls the matching `walk_*` function to keep walking
+pub trait Visitor {
+    /// Visit Textual
+    ///
+    /// # Description
+    /// Visit the textual token of a line. Walk its tag and its text by default
+    ///
+    /// # Arguments
+    /// * `line` usize
+    /// * `token` &TextualToken
+    fn visit_textual(&mut self, line: usize, token: &TextualToken) {
+        walk_textual(self, line, token)
+    }
+
+    /// Visit Tag
+    ///
+    /// # Description
+    /// Visit a tag. Dispatch it to the method of its operator then visit the links, the images and
+    /// the inline code of its content by default
+    ///
+    /// # Arguments
+    /// * `line` usize
+    /// * `tag` &TagToken
+    fn visit_tag(&mut self, line: usize, tag: &TagToken) {
+        walk_tag(self, line, tag)
+    }
+
+    /// Visit Heading
+    ///
+    /// # Description
+    /// Visit a heading, the level and the slug are within its metas
+    ///
+    /// # Arguments
+    /// * `_heading` &TagToken
+    fn visit_heading(&mut self, _heading: &TagToken) {}
+
+    /// Visit List Item
+    ///
+    /// # Description
+    /// Visit an item of an ordered or an unordered list
+    ///
+    /// # Arguments
+    /// * `_item` &TagToken
+    fn visit_list_item(&mut self, _item: &TagToken) {}
+
+    /// Visit Block Quote
+    ///
+    /// # Description
+    /// Visit a line of a block quote
+    ///
+    /// # Arguments
+    /// * `_quote` &TagToken
+    fn visit_block_quote(&mut self, _quote: &TagToken) {}
+
+    /// Visit Text
+    ///
+    /// # Description
+    /// Visit a text. Visit its links, its images and its inline code by default
+    ///
+    /// # Arguments
+    /// * `text` &TextToken
+    fn visit_text(&mut self, text: &TextToken) {
+        walk_text(self, text)
+    }
+
+    /// Visit Link
+    ///
+    /// # Description
+    /// Visit a link of a text, a tag, a table cell or a definition list
+    ///
+    /// # Arguments
+    /// * `_line` usize
+    /// * `_link` &LinkMeta
+    fn visit_link(&mut self, _line: usize, _link: &LinkMeta) {}
+
+    /// Visit Image
+    ///
+    /// # Description
+    /// Visit an image of a text, a tag, a table cell or a definition list
+    ///
+    /// # Arguments
+    /// * `_line` usize
+    /// * `_image` &ImageMeta
+    fn visit_image(&mut self, _line: usize, _image: &ImageMeta) {}
+
+    /// Visit Inline Code
+    ///
+    /// # Description
+    /// Visit an inline code span
+    ///
+    /// # Arguments
+    /// * `_line` usize
+    /// * `_code` &TextOption
+    fn visit_inline_code(&mut self, _line: usize, _code: &TextOption) {}
+
+    /// Visit Code Block
+    ///
+    /// # Description
+    /// Visit the lines of a fenced code block, from the opening fence to the closing one
+    ///
+    /// # Arguments
+    /// * `_block` &[&CodeToken]
+    fn visit_code_block(&mut self, _block: &[&CodeToken]) {}
+}
+
+/// Visitor Mut
+///
+/// # Description
+/// Walk the tokens of a document and update them in place. The urls of the links and the images
+/// which have been changed are updated within the content of their text
+pub trait VisitorMut {
+    /// Visit Textual Mut
+    ///
+    /// # Description
+    /// Update the textual token of a line. Walk its tag and its text by default
+    ///
+    /// # Arguments
+    /// * `line` usize
+    /// * `token` &mut TextualToken
+    fn visit_textual_mut(&mut self, line: usize, token: &mut TextualToken) {
+        walk_textual_mut(self, line, token)
+    }
+
+    /// Visit Tag Mut
+    ///
+    /// # Description
+    /// Update a tag. Dispatch it to the method of its operator then visit the links and the images
+    /// of its content by default
+    ///
+    /// # Arguments
+    /// * `line` usize
+    /// * `tag` &mut TagToken
+    fn visit_tag_mut(&mut self, line: usize, tag: &mut TagToken) {
+        walk_tag_mut(self, line, tag)
+    }
+
+    /// Visit Heading Mut
+    ///
+    /// # Description
+    /// Update a heading i.e to shift its level
+    ///
+    /// # Arguments
+    /// * `_heading` &mut TagToken
+    fn visit_heading_mut(&mut self, _heading: &mut TagToken) {}
+
+    /// Visit List Item Mut
+    ///
+    /// # Description
+    /// Update an item of an ordered or an unordered list
+    ///
+    /// # Arguments
+    /// * `_item` &mut TagToken
+    fn visit_list_item_mut(&mut self, _item: &mut TagToken) {}
+
+    /// Visit Block Quote Mut
+    ///
+    /// # Description
+    /// Update a line of a block quote
+    ///
+    /// # Arguments
+    /// * `_quote` &mut TagToken
+    fn visit_block_quote_mut(&mut self, _quote: &mut TagToken) {}
+
+    /// Visit Text Mut
+    ///
+    /// # Description
+    /// Update a text. Visit its links and its images by default
+    ///
+    /// # Arguments
+    /// * `text` &mut TextToken
+    fn visit_text_mut(&mut self, text: &mut TextToken) {
+        walk_text_mut(self, text)
+    }
+
+    /// Visit Link Mut
+    ///
+    /// # Description
+    /// Update a link. A new url is written back into the content holding the link
+    ///
+    /// # Arguments
+    /// * `_line` usize
+    /// * `_link` &mut LinkMeta
+    fn visit_link_mut(&mut self, _line: usize, _link: &mut LinkMeta) {}
+
+    /// Visit Image Mut
+    ///
+    /// # Description
+    /// Update an image. A new url is written back into the content holding the image
+    ///
+    /// # Arguments
+    /// * `_line` usize
+    /// * `_image` &mut ImageMeta
+    fn visit_image_mut(&mut self, _line: usize, _image: &mut ImageMeta) {}
+
+    /// Visit Code Block Mut
+    ///
+    /// # Description
+    /// Update the lines of a fenced code block, from the opening fence to the closing one
+    ///
+    /// # Arguments
+    /// * `_block` &mut [&mut CodeToken]
+    fn visit_code_block_mut(&mut self, _block: &mut [&mut CodeToken]) {}
+}
+
+/// Fold
+///
+/// # Description
+/// Consume the tokens of a document and rebuild them. Returning None from `fold_textual` drops the
+/// token, the code blocks are dropped by returning an empty Vec
+pub trait Fold<'a> {
+    /// Fold Textual
+    ///
+    /// # Description
+    /// Rebuild the textual token of a line. Fold its tag and its text by default
+    ///
+    /// # Arguments
+    /// * `line` usize
+    /// * `token` TextualToken
+    ///
+    /// # Return
+    /// Option<TextualToken> None to drop the token
+    fn fold_textual(&mut self, line: usize, token: TextualToken<'a>) -> Option<TextualToken<'a>> {
+        Some(walk_fold_textual(self, line, token))
+    }
+
+    /// Fold Tag
+    ///
+    /// # Description
+    /// Rebuild a tag. Dispatch it to the method of its operator then fold the links and the images
+    /// of its content by default
+    ///
+    /// # Arguments
+    /// * `line` usize
+    /// * `tag` TagToken
+    ///
+    /// # Return
+    /// TagToken
+    fn fold_tag(&mut self, line: usize, tag: TagToken<'a>) -> TagToken<'a> {
+        walk_fold_tag(self, line, tag)
+    }
+
+    /// Fold Heading
+    ///
+    /// # Description
+    /// Rebuild a heading. Return it as is by default
+    ///
+    /// # Arguments
+    /// * `heading` TagToken
+    ///
+    /// # Return
+    /// TagToken
+    fn fold_heading(&mut self, heading: TagToken<'a>) -> TagToken<'a> {
+        heading
+    }
+
+    /// Fold Text
+    ///
+    /// # Description
+    /// Rebuild a text. Fold its links and its images by default
+    ///
+    /// # Arguments
+    /// * `text` TextToken
+    ///
+    /// # Return
+    /// TextToken
+    fn fold_text(&mut self, text: TextToken<'a>) -> TextToken<'a> {
+        walk_fold_text(self, text)
+    }
+
+    /// Fold Link
+    ///
+    /// # Description
+    /// Rebuild a link. A new url is written back into the content holding the link
+    ///
+    /// # Arguments
+    /// * `_line` usize
+    /// * `link` LinkMeta
+    ///
+    /// # Return
+    /// LinkMeta
+    fn fold_link(&mut self, _line: usize, link: LinkMeta<'a>) -> LinkMeta<'a> {
+        link
+    }
+
+    /// Fold Image
+    ///
+    /// # Description
+    /// Rebuild an image. A new url is written back into the content holding the image
+    ///
+    /// # Arguments
+    /// * `_line` usize
+    /// * `image` ImageMeta
+    ///
+    /// # Return
+    /// ImageMeta
+    fn fold_image(&mut self, _line: usize, image: ImageMeta<'a>) -> ImageMeta<'a> {
+        image
+    }
+
+    /// Fold Code Block
+    ///
+    /// # Description
+    /// Rebuild the lines of a fenced code block, from the opening fence to the closing one
+    ///
+    /// # Arguments
+    /// * `block` Vec<CodeToken>
+    ///
+    /// # Return
+    /// Vec<CodeToken> an empty Vec to drop the block
+    fn fold_code_block(&mut self, block: Vec<CodeToken<'a>>) -> Vec<CodeToken<'a>> {
+        block
+    }
+}
+
+/// Walk Document
+///
+/// # Description
+/// Visit the textual tokens and the code blocks in the order of their lines. The textual tokens of
+/// the lines of a code block i.e the fences, aren't visited
+///
+/// # Arguments
+/// * `visitor` &mut V
+/// * `tokens` &BTreeMap<usize, TextualToken>
+/// * `code_tokens` &BTreeMap<usize, CodeToken>
+pub fn walk_document<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    tokens: &BTreeMap<usize, TextualToken>,
+    code_tokens: &BTreeMap<usize, CodeToken>
+) {
+    let mut blocks = get_code_blocks(code_tokens).into_iter().peekable();
+    let lines: BTreeSet<usize> = tokens.keys().chain(code_tokens.keys()).copied().collect();
+
+    for idx in lines {
+        if let Some(block) = blocks.next_if(|block| *block.start() == idx) {
+            let block: Vec<&CodeToken> = code_tokens.range(block).map(|(_, t)| t).collect();
+            visitor.visit_code_block(&block);
+            continue;
+        }
+
+        if code_tokens.contains_key(&idx) {
+            continue;
+        }
+
+        if let Some(token) = tokens.get(&idx) {
+            visitor.visit_textual(idx, token);
+        }
+    }
+}
+
+/// Walk Textual
+///
+/// # Description
+/// Visit the tag and the text of a textual token
+///
+/// # Arguments
+/// * `visitor` &mut V
+/// * `line` usize
+/// * `token` &TextualToken
+pub fn walk_textual<V: Visitor + ?Sized>(visitor: &mut V, line: usize, token: &TextualToken) {
+    if let Some(tag) = &token.tag {
+        visitor.visit_tag(line, tag);
+    }
+
+    if let Some(text) = &token.text {
+        visitor.visit_text(text);
+    }
+}
+
+/// Walk Tag
+///
+/// # Description
+/// Dispatch the tag to the method of its operator, then visit the links, the images and the
+/// inline code of its content, of its table cells or of its definition list
+///
+/// # Arguments
+/// * `visitor` &mut V
+/// * `line` usize
+/// * `tag` &TagToken
+pub fn walk_tag<V: Visitor + ?Sized>(visitor: &mut V, line: usize, tag: &TagToken) {
+    match tag.operator {
+        TagOperator::Heading => visitor.visit_heading(tag),
+        TagOperator::UnorderedList | TagOperator::OrderedList => visitor.visit_list_item(tag),
+        TagOperator::BlockQuote => visitor.visit_block_quote(tag),
+        _ => {}
+    }
+
+    for metas in get_tag_metas(tag).iter().flatten() {
+        for link in metas.links.iter().flatten() {
+            visitor.visit_link(line, link);
+        }
+
+        for image in metas.images.iter().flatten() {
+            visitor.visit_image(line, image);
+        }
+
+        for code in metas.inline_code.iter().flatten() {
+            visitor.visit_inline_code(line, code);
+        }
+    }
+}
+
+/// Walk Text
+///
+/// # Description
+/// Visit the links, the images and the inline code of a text
+///
+/// # Arguments
+/// * `visitor` &mut V
+/// * `text` &TextToken
+pub fn walk_text<V: Visitor + ?Sized>(visitor: &mut V, text: &TextToken) {
+    let metas = match &text.metas {
+        Some(metas) => metas,
+        None => return
+    };
+
+    for link in metas.links.iter().flatten() {
+        visitor.visit_link(text.line, link);
+    }
+
+    for image in metas.images.iter().flatten() {
+        visitor.visit_image(text.line, image);
+    }
+
+    for code in metas.inline_code.iter().flatten() {
+        visitor.visit_inline_code(text.line, code);
+    }
+}
+
+/// Walk Document Mut
+///
+/// # Description
+/// Visit and update the textual tokens and the code blocks in the order of their lines. The texts
+/// whose content has been rewritten are sanitized again with the options
+///
+/// # Arguments
+/// * `visitor` &mut V
+/// * `tokens` &mut BTreeMap<usize, TextualToken>
+/// * `code_tokens` &mut BTreeMap<usize, CodeToken>
+/// * `options` &ParserOptions
+pub fn walk_document_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    tokens: &mut BTreeMap<usize, TextualToken>,
+    code_tokens: &mut BTreeMap<usize, CodeToken>,
+    options: &ParserOptions
+) {
+    let mut blocks = get_code_blocks(code_tokens).into_iter().peekable();
+    let lines: BTreeSet<usize> = tokens.keys().chain(code_tokens.keys()).copied().collect();
+
+    for idx in lines {
+        if let Some(block) = blocks.next_if(|block| *block.start() == idx) {
+            let mut block: Vec<&mut CodeToken> = code_tokens.range_mut(block).map(|(_, t)| t).collect();
+            visitor.visit_code_block_mut(&mut block);
+            continue;
+        }
+
+        if code_tokens.contains_key(&idx) {
+            continue;
+        }
+
+        if let Some(token) = tokens.get_mut(&idx) {
+            let content = get_text_content(token);
+            visitor.visit_textual_mut(idx, token);
+            resanitize_text(token, content, options);
+        }
+    }
+}
+
+/// Walk Textual Mut
+///
+/// # Description
+/// Visit the tag and the text of a textual token
+///
+/// # Arguments
+/// * `visitor` &mut V
+/// * `line` usize
+/// * `token` &mut TextualToken
+pub fn walk_textual_mut<V: VisitorMut + ?Sized>(visitor: &mut V, line: usize, token: &mut TextualToken) {
+    if let Some(tag) = token.tag.as_mut() {
+        visitor.visit_tag_mut(line, tag);
+    }
+
+    if let Some(text) = token.text.as_mut() {
+        visitor.visit_text_mut(text);
+    }
+}
+
+/// Walk Tag Mut
+///
+/// # Description
+/// Dispatch the tag to the method of its operator, then visit the links and the images of its
+/// content, of its table cells or of its definition list. The content, the cells and the items are
+/// updated with the new urls
+///
+/// # Arguments
+/// * `visitor` &mut V
+/// * `line` usize
+/// * `tag` &mut TagToken
+pub fn walk_tag_mut<V: VisitorMut + ?Sized>(visitor: &mut V, line: usize, tag: &mut TagToken) {
+    match tag.operator {
+        TagOperator::Heading => visitor.visit_heading_mut(tag),
+        TagOperator::UnorderedList | TagOperator::OrderedList => visitor.visit_list_item_mut(tag),
+        TagOperator::BlockQuote => visitor.visit_block_quote_mut(tag),
+        _ => {}
+    }
+
+    let contents: Vec<Option<TextMetas>> = get_tag_metas(tag)
+        .into_iter()
+        .map(|metas| metas.map(TextMetas::into_owned))
+        .collect();
+
+    let mut urls = Vec::new();
+    for metas in contents {
+        let (links, images) = metas.map_or((None, None), |m| (m.links, m.images));
+        let mut content_urls = Vec::new();
+        for mut link in links.into_iter().flatten() {
+            let url = link.url.to_string();
+            visitor.visit_link_mut(line, &mut link);
+            content_urls.push((false, url, link.url.to_string()));
+        }
+
+        for mut image in images.into_iter().flatten() {
+            let url = image.url.to_string();
+            visitor.visit_image_mut(line, &mut image);
+            content_urls.push((true, url, image.url.to_string()));
+        }
+
+        urls.push(content_urls);
+    }
+
+    set_tag_urls(tag, &urls);
+}
+
+/// Walk Text Mut
+///
+/// # Description
+/// Visit the links and the images of a text. The content is updated with the new urls
+///
+/// # Arguments
+/// * `visitor` &mut V
+/// * `text` &mut TextToken
+pub fn walk_text_mut<V: VisitorMut + ?Sized>(visitor: &mut V, text: &mut TextToken) {
+    let line = text.line;
+    let mut urls = Vec::new();
+
+    if let Some(metas) = text.metas.as_mut() {
+        for link in metas.links.iter_mut().flatten() {
+            let url = link.url.to_string();
+            visitor.visit_link_mut(line, link);
+            urls.push((false, url, link.url.to_string()));
+        }
+
+        for image in metas.images.iter_mut().flatten() {
+            let url = image.url.to_string();
+            visitor.visit_image_mut(line, image);
+            urls.push((true, url, image.url.to_string()));
+        }
+    }
+
+    set_urls(&mut text.content, &urls);
+}
+
+/// Fold Document
+///
+/// # Description
+/// Fold the textual tokens and the code blocks. The textual tokens of the lines of a code block
+/// are kept along with their code tokens
+///
+/// # Arguments
+/// * `folder` &mut F
+/// * `tokens` BTreeMap<usize, TextualToken>
+/// * `code_tokens` BTreeMap<usize, CodeToken>
+/// * `options` &ParserOptions used to sanitize the rewritten texts
+///
+/// # Return
+/// (BTreeMap<usize, TextualToken>, BTreeMap<usize, CodeToken>)
+pub fn fold_document<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    tokens: BTreeMap<usize, TextualToken<'a>>,
+    code_tokens: BTreeMap<usize, CodeToken<'a>>,
+    options: &ParserOptions
+) -> (BTreeMap<usize, TextualToken<'a>>, BTreeMap<usize, CodeToken<'a>>) {
+    let blocks = get_code_blocks(&code_tokens);
+    let code_lines: BTreeSet<usize> = code_tokens.keys().copied().collect();
+    let mut code_tokens = code_tokens;
+    let mut folded_code = BTreeMap::new();
+
+    for block in blocks {
+        let lines: Vec<usize> = code_tokens.range(block).map(|(idx, _)| *idx).collect();
+        let block: Vec<CodeToken> = lines.iter().filter_map(|idx| code_tokens.remove(idx)).collect();
+
+        for token in folder.fold_code_block(block) {
+            folded_code.insert(token.line, token);
+        }
+    }
+
+    let folded = tokens
+        .into_iter()
+        .filter_map(|(idx, token)| {
+            // the fences of a dropped code block are dropped as well
+            if code_lines.contains(&idx) {
+                return Some((idx, token)).filter(|_| code_tokens.contains_key(&idx) || folded_code.contains_key(&idx));
+            }
+
+            let content = get_text_content(&token);
+            folder.fold_textual(idx, token).map(|mut token| {
+                resanitize_text(&mut token, content, options);
+                (idx, token)
+            })
+        })
+        .collect();
+
+    (folded, folded_code)
+}
+
+/// Walk Fold Textual
+///
+/// # Description
+/// Fold the tag and the text of a textual token
+///
+/// # Arguments
+/// * `folder` &mut F
+/// * `line` usize
+/// * `token` TextualToken
+///
+/// # Return
+/// TextualToken
+pub fn walk_fold_textual<'a, F: Fold<'a> + ?Sized>(folder: &mut F, line: usize, token: TextualToken<'a>) -> TextualToken<'a> {
+    TextualToken {
+        tag: token.tag.map(|tag| folder.fold_tag(line, tag)),
+        text: token.text.map(|text| folder.fold_text(text))
+    }
+}
+
+/// Walk Fold Tag
+///
+/// # Description
+/// Dispatch the tag to the method of its operator, then fold the links and the images of its
+/// content, of its table cells or of its definition list. The content, the cells and the items are
+/// updated with the new urls
+///
+/// # Arguments
+/// * `folder` &mut F
+/// * `line` usize
+/// * `tag` TagToken
+///
+/// # Return
+/// TagToken
+pub fn walk_fold_tag<'a, F: Fold<'a> + ?Sized>(folder: &mut F, line: usize, tag: TagToken<'a>) -> TagToken<'a> {
+    let mut tag = match tag.operator {
+        TagOperator::Heading => folder.fold_heading(tag),
+        _ => tag
+    };
+
+    let contents: Vec<Option<TextMetas>> = get_tag_metas(&tag)
+        .into_iter()
+        .map(|metas| metas.map(TextMetas::into_owned))
+        .collect();
+
+    let mut urls = Vec::new();
+    for metas in contents {
+        let (links, images) = metas.map_or((None, None), |m| (m.links, m.images));
+        let mut content_urls = Vec::new();
+        for link in links.into_iter().flatten() {
+            let url = link.url.to_string();
+            content_urls.push((false, url, folder.fold_link(line, link).url.to_string()));
+        }
+
+        for image in images.into_iter().flatten() {
+            let url = image.url.to_string();
+            content_urls.push((true, url, folder.fold_image(line, image).url.to_string()));
+        }
+
+        urls.push(content_urls);
+    }
+
+    set_tag_urls(&mut tag, &urls);
+    tag
+}
+
+/// Walk Fold Text
+///
+/// # Description
+/// Fold the links and the images of a text. The content is updated with the new urls
+///
+/// # Arguments
+/// * `folder` &mut F
+/// * `text` TextToken
+///
+/// # Return
+/// TextToken
+pub fn walk_fold_text<'a, F: Fold<'a> + ?Sized>(folder: &mut F, mut text: TextToken<'a>) -> TextToken<'a> {
+    let line = text.line;
+    let mut urls = Vec::new();
+
+    if let Some(metas) = text.metas.as_mut() {
+        if let Some(links) = metas.links.take() {
+            metas.links = Some(links.into_iter().map(|link| {
+                let url = link.url.to_string();
+                let link = folder.fold_link(line, link);
+                urls.push((false, url, link.url.to_string()));
+
+                link
+            }).collect());
+        }
+
+        if let Some(images) = metas.images.take() {
+            metas.images = Some(images.into_iter().map(|image| {
+                let url = image.url.to_string();
+                let image = folder.fold_image(line, image);
+                urls.push((true, url, image.url.to_string()));
+
+                image
+            }).collect());
+        }
+    }
+
+    set_urls(&mut text.content, &urls);
+    text
+}
+
+/// Get Code Blocks
+///
+/// # Description
+/// Return the lines of each code block, from its opening fence to its closing fence or the last
+/// line of an unclosed block
+///
+/// # Arguments
+/// * `code_tokens` &BTreeMap<usize, CodeToken>
+///
+/// # Return
+/// Vec<RangeInclusive<usize>>
+pub fn get_code_blocks(code_tokens: &BTreeMap<usize, CodeToken>) -> Vec<RangeInclusive<usize>> {
+    let mut blocks = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (idx, token) in code_tokens {
+        match token.operator {
+            CodeOperator::BlockCodeStart => start = Some(*idx),
+            CodeOperator::BlockCodeEnd => {
+                if let Some(start) = start.take() {
+                    blocks.push(start..=*idx);
+                }
+            },
+            CodeOperator::BlockCodeContent => {}
+        }
+    }
+
+    if let (Some(start), Some(last)) = (start, code_tokens.keys().last()) {
+        blocks.push(start..=*last);
+    }
+
+    blocks
+}
+
+/// Get Tag Metas
+///
+/// # Description
+/// Get the metas of each inline content of a tag: the content of a heading, a list item, a block
+/// quote or a footnote definition, the cells of a table or the terms and the definitions of a
+/// definition list
+///
+/// # Arguments
+/// * `tag` &TagToken
+///
+/// # Return
+/// Vec<Option<TextMetas>> in the order of `get_inline_contents`
+fn get_tag_metas<'t>(tag: &'t TagToken) -> Vec<Option<TextMetas<'t>>> {
+    tag.get_inline_contents()
+        .into_iter()
+        .map(|content| get_text_metas(content, &ParserOptions::default()))
+        .collect()
+}
+
+/// Set Tag Urls
+///
+/// # Description
+/// Replace the urls which have been changed within the content of a tag. The cells of a table and
+/// the items of a definition list are updated with the urls of their own content
+///
+/// # Arguments
+/// * `tag` &mut TagToken
+/// * `urls` &[Vec<(bool, String, String)>] the urls of each inline content
+fn set_tag_urls(tag: &mut TagToken, urls: &[Vec<(bool, String, String)>]) {
+    set_urls(&mut tag.content, &urls.concat());
+    if tag.has_inline_content() {
+        return;
+    }
+
+    let metas = match tag.metas.as_mut() {
+        Some(metas) => metas,
+        None => return
+    };
+
+    let cells = metas.table
+        .iter_mut()
+        .flat_map(|table| table.header.iter_mut().chain(table.rows.iter_mut().flatten()));
+
+    let items = metas.definition_list
+        .iter_mut()
+        .flat_map(|list| list.items.iter_mut())
+        .flat_map(|item| std::iter::once(&mut item.term).chain(item.definitions.iter_mut()));
+
+    for (content, urls) in cells.chain(items).zip(urls) {
+        let mut replaced = Cow::Borrowed(content.as_str());
+        if set_urls(&mut replaced, urls) {
+            *content = replaced.into_owned();
+        }
+    }
+}
+
+/// Get Text Content
+///
+/// # Description
+/// Copy the content of the text of a token, to find out whether a visitor has rewritten it
+///
+/// # Arguments
+/// * `token` &TextualToken
+///
+/// # Return
+/// Option<String>
+fn get_text_content(token: &TextualToken) -> Option<String> {
+    token.text.as_ref().map(|text| text.content.to_string())
+}
+
+/// Resanitize Text
+///
+/// # Description
+/// Sanitize the text of a token again when its content has been rewritten
+///
+/// # Arguments
+/// * `token` &mut TextualToken
+/// * `previous` Option<String> the content before the visit
+/// * `options` &ParserOptions
+fn resanitize_text(token: &mut TextualToken, previous: Option<String>, options: &ParserOptions) {
+    if let Some(text) = token.text.as_mut().filter(|text| previous.as_deref() != Some(text.content.as_ref())) {
+        text.resanitize(options);
+    }
+}
+
+/// Set Urls
+///
+/// # Description
+/// Replace the urls which have been changed within the content. The n-th link (or image) of a url
+/// is replaced at the span of the n-th destination starting with this url, so that the title and
+/// the rest of the destination are kept
+///
+/// # Arguments
+/// * `content` &mut Cow<str>
+/// * `urls` &[(bool, String, String)] whether it is an image, previous and new url
+///
+/// # Return
+/// bool whether the content has been changed
+fn set_urls(content: &mut Cow<str>, urls: &[(bool, String, String)]) -> bool {
+    let mut destinations = get_destinations(content);
+    let mut replacements = Vec::new();
+
+    for (image, previous, url) in urls {
+        let found = destinations
+            .iter()
+            .position(|(is_image, start)| is_image == image && content[*start..].starts_with(previous.as_str()));
+
+        // the destination is used even when its url is kept
+        if let Some(pos) = found {
+            let (_, start) = destinations.remove(pos);
+            if previous != url {
+                replacements.push((start..start + previous.len(), url));
+            }
+        }
+    }
+
+    if replacements.is_empty() {
+        return false;
+    }
+
+    replacements.sort_by_key(|(span, _)| std::cmp::Reverse(span.start));
+    let mut replaced = content.to_string();
+    for (span, url) in replacements {
+        replaced.replace_range(span, url);
+    }
+
+    *content = Cow::Owned(replaced);
+    true
+}
+
+/// Get Destinations
+///
+/// # Description
+/// Get the start of the destination of the links and the images of a content in the order of the
+/// content. The links within an inline code or escaped aren't part of it
+///
+/// # Arguments
+/// * `content` &str
+///
+/// # Return
+/// Vec<(bool, usize)> whether it is an image and the index following the opening parenthesis
+fn get_destinations(content: &str) -> Vec<(bool, usize)> {
+    let masked = mask_code_spans(content);
+    let mut destinations = Vec::new();
+    let mut idx = 0;
+
+    while let Some(pos) = masked[idx..].find('[') {
+        let start = idx + pos;
+        if masked[..start].ends_with('\\') {
+            idx = start + 1;
+            continue;
+        }
+
+        match match_link(&masked[start..]) {
+            Some((label, _, len)) => {
+                destinations.push((masked[..start].ends_with('!'), start + label.len() + 3));
+                idx = start + len;
+            },
+            None => idx = start + 1
+        }
+    }
+
+    destinations
+}